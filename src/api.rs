@@ -0,0 +1,207 @@
+#[cfg(feature = "audio_transcription")]
+use crate::audio_transcription::{
+    AudioTranscriptionError, AudioTranscriptionRequest, AudioTranscriptionResponse,
+};
+#[cfg(feature = "audio_transcription")]
+use crate::audio_translation::AudioTranslationRequest;
+#[cfg(feature = "chat_completition")]
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse,
+};
+#[cfg(feature = "chat_completition")]
+use crate::chat_stream::ChatCompletionStream;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "image_generation",
+    feature = "inference",
+    feature = "models"
+))]
+use crate::client::DeepinfraClient;
+#[cfg(feature = "image_generation")]
+use crate::image_generation::{
+    ImageGenerationError, ImageGenerationRequest, ImageGenerationResponse,
+};
+#[cfg(feature = "inference")]
+use crate::inference::{InferenceError, InferenceResponse};
+#[cfg(feature = "models")]
+use crate::models::{Model, ModelsError};
+
+/// Chat completions, streamed or not.
+///
+/// This and the other capability traits of this module are implemented by
+/// [`DeepinfraClient`], so that application code can be written against a trait and
+/// tested with a mock instead of an HTTP server.
+///
+/// Each trait comes in two variants: the `Send` one, e.g. [`ChatCompletions`], whose
+/// futures can be spawned on a multi-threaded runtime, and a `Local` one without
+/// that bound, which every `Send` implementation also implements.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::api::ChatCompletions;
+/// use deepinfra_client_rs::chat_completition::*;
+/// use deepinfra_client_rs::chat_stream::ChatCompletionStream;
+///
+/// async fn greet(api: &impl ChatCompletions) -> Result<String, ChatCompletionError> {
+///     let request = ChatCompletionRequest::builder()
+///         .messages(vec![Message::User(UserMessage::builder().content("Hi!").build())])
+///         .build();
+///     let response = api.chat_completition(request).await?;
+///     Ok(response.content().unwrap_or_default().to_string())
+/// }
+///
+/// struct Mock;
+///
+/// impl ChatCompletions for Mock {
+///     async fn chat_completition(
+///         &self,
+///         _request: ChatCompletionRequest,
+///     ) -> Result<ChatCompletionResponse, ChatCompletionError> {
+///         Ok(serde_json::from_value(serde_json::json!({
+///             "choices": [{
+///                 "index": 0,
+///                 "message": { "role": "assistant", "content": "Hello!" },
+///                 "finish_reason": "stop"
+///             }]
+///         }))
+///         .unwrap())
+///     }
+///
+///     async fn chat_completion_stream(
+///         &self,
+///         _request: ChatCompletionRequest,
+///     ) -> Result<ChatCompletionStream, ChatCompletionError> {
+///         Ok(ChatCompletionStream::from_chunks(futures::stream::empty()))
+///     }
+/// }
+///
+/// let reply = futures::executor::block_on(greet(&Mock)).unwrap();
+/// assert_eq!(reply, "Hello!");
+/// ```
+#[cfg(feature = "chat_completition")]
+#[trait_variant::make(ChatCompletions: Send)]
+pub trait LocalChatCompletions {
+    async fn chat_completition(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, ChatCompletionError>;
+
+    async fn chat_completion_stream(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionStream, ChatCompletionError>;
+}
+
+/// Audio transcriptions and translations.
+#[cfg(feature = "audio_transcription")]
+#[trait_variant::make(AudioTranscriptions: Send)]
+pub trait LocalAudioTranscriptions {
+    async fn audio_transcription(
+        &self,
+        request: AudioTranscriptionRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError>;
+
+    async fn audio_translation(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError>;
+}
+
+/// Image generation from text prompts.
+#[cfg(feature = "image_generation")]
+#[trait_variant::make(ImageGenerations: Send)]
+pub trait LocalImageGenerations {
+    async fn image_generation(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, ImageGenerationError>;
+}
+
+/// Calls through the native inference API.
+#[cfg(feature = "inference")]
+#[trait_variant::make(Inference: Send)]
+pub trait LocalInference {
+    async fn inference<I, O>(
+        &self,
+        model: &str,
+        input: &I,
+    ) -> Result<InferenceResponse<O>, InferenceError>
+    where
+        I: serde::Serialize + Sync,
+        O: serde::de::DeserializeOwned;
+}
+
+/// The model catalogue.
+#[cfg(feature = "models")]
+#[trait_variant::make(ModelCatalog: Send)]
+pub trait LocalModelCatalog {
+    async fn list_models(&self) -> Result<Vec<Model>, ModelsError>;
+}
+
+#[cfg(feature = "chat_completition")]
+impl ChatCompletions for DeepinfraClient {
+    async fn chat_completition(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, ChatCompletionError> {
+        DeepinfraClient::chat_completition(self, request).await
+    }
+
+    async fn chat_completion_stream(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionStream, ChatCompletionError> {
+        DeepinfraClient::chat_completion_stream(self, request).await
+    }
+}
+
+#[cfg(feature = "audio_transcription")]
+impl AudioTranscriptions for DeepinfraClient {
+    async fn audio_transcription(
+        &self,
+        request: AudioTranscriptionRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
+        DeepinfraClient::audio_transcription(self, request).await
+    }
+
+    async fn audio_translation(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
+        DeepinfraClient::audio_translation(self, request).await
+    }
+}
+
+#[cfg(feature = "image_generation")]
+impl ImageGenerations for DeepinfraClient {
+    async fn image_generation(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, ImageGenerationError> {
+        DeepinfraClient::image_generation(self, request).await
+    }
+}
+
+#[cfg(feature = "inference")]
+impl Inference for DeepinfraClient {
+    async fn inference<I, O>(
+        &self,
+        model: &str,
+        input: &I,
+    ) -> Result<InferenceResponse<O>, InferenceError>
+    where
+        I: serde::Serialize + Sync,
+        O: serde::de::DeserializeOwned,
+    {
+        DeepinfraClient::inference(self, model, input).await
+    }
+}
+
+#[cfg(feature = "models")]
+impl ModelCatalog for DeepinfraClient {
+    async fn list_models(&self) -> Result<Vec<Model>, ModelsError> {
+        DeepinfraClient::list_models(self).await
+    }
+}
@@ -0,0 +1,312 @@
+use http::HeaderMap;
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// The response header identifying a request to DeepInfra support.
+pub(crate) const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Number of bytes of a body kept in a [`DecodeError`].
+const BODY_SNIPPET_LEN: usize = 512;
+
+/// A successful response whose body did not have the expected shape.
+///
+/// Unlike a transport error, this usually means the API schema changed rather than
+/// that the API is unavailable. `body_snippet` holds the start of the body, and
+/// `request_id` the `x-request-id` header of the response, when known.
+#[derive(Debug, thiserror::Error)]
+#[error("{source}; body: {body_snippet}")]
+pub struct DecodeError {
+    pub body_snippet: String,
+    pub request_id: Option<String>,
+    #[source]
+    pub source: serde_json::Error,
+}
+
+/// Decodes a JSON response body, keeping a snippet of it on failure.
+pub(crate) fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, DecodeError> {
+    serde_json::from_slice(body).map_err(|source| {
+        let snippet = &body[..body.len().min(BODY_SNIPPET_LEN)];
+        DecodeError {
+            // A snippet cut inside a character only loses that character.
+            body_snippet: String::from_utf8_lossy(snippet).into_owned(),
+            request_id: None,
+            source,
+        }
+    })
+}
+
+/// Reads the body of `response` and decodes it as JSON, recording the request ID
+/// of the response on failure.
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models",
+    feature = "deploy"
+))]
+pub(crate) async fn read_json<T: DeserializeOwned>(
+    response: Response,
+) -> reqwest::Result<Result<T, DecodeError>> {
+    let request_id = request_id(response.headers());
+    let body = response.bytes().await?;
+    Ok(decode(&body).map_err(|error| error.with_request_id(request_id)))
+}
+
+/// The value of the request ID header, if present and valid UTF-8.
+pub(crate) fn request_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+impl DecodeError {
+    pub(crate) fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+}
+
+/// A non-success response from the DeepInfra API.
+///
+/// `request_id` is taken from the `x-request-id` header, which identifies the request
+/// when contacting DeepInfra support. `body` is the raw response body; [`ApiError::message`] extracts the human-readable
+/// message from the usual `{"error": {"message": ...}}` and `{"detail": ...}` shapes.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    /// The token is missing, invalid or lacks access (401, 403).
+    #[error("Unauthorized ({status}): {body}")]
+    Unauthorized {
+        status: u16,
+        request_id: Option<String>,
+        body: String,
+    },
+    /// Too many requests (429). `retry_after` comes from the `Retry-After` header.
+    #[error("Rate limited ({status}): {body}")]
+    RateLimited {
+        status: u16,
+        request_id: Option<String>,
+        retry_after: Option<Duration>,
+        body: String,
+    },
+    /// The requested model or resource does not exist (404).
+    #[error("Model not found ({status}): {body}")]
+    ModelNotFound {
+        status: u16,
+        request_id: Option<String>,
+        body: String,
+    },
+    /// The request was rejected as invalid (400, 422).
+    #[error("Invalid request ({status}): {body}")]
+    Validation {
+        status: u16,
+        request_id: Option<String>,
+        body: String,
+    },
+    /// The request or the generated output was rejected by a content policy.
+    /// `message` is the explanation given by the provider, fit to show to users.
+    #[error("Content rejected ({status}): {message}")]
+    ContentRejected {
+        status: u16,
+        request_id: Option<String>,
+        message: String,
+        body: String,
+    },
+    /// The API failed to process a valid request (5xx).
+    #[error("Server error ({status}): {body}")]
+    Server {
+        status: u16,
+        request_id: Option<String>,
+        body: String,
+    },
+    /// Any other non-success status.
+    #[error("Unexpected status ({status}): {body}")]
+    Other {
+        status: u16,
+        request_id: Option<String>,
+        body: String,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ErrorBody {
+    OpenAi { error: ErrorMessage },
+    Detail { detail: serde_json::Value },
+}
+
+#[derive(Deserialize)]
+struct ErrorMessage {
+    message: String,
+    #[serde(default, rename = "type")]
+    error_type: Option<String>,
+    #[serde(default)]
+    code: Option<serde_json::Value>,
+}
+
+/// Error codes and types that providers use for content policy rejections.
+const CONTENT_POLICY_CODES: [&str; 2] = ["content_policy_violation", "content_filter"];
+
+/// Extracts the message from the usual error body shapes, falling back to the body.
+fn error_message(body: &str) -> String {
+    match serde_json::from_str(body) {
+        Ok(ErrorBody::OpenAi { error }) => error.message,
+        Ok(ErrorBody::Detail {
+            detail: serde_json::Value::String(detail),
+        }) => detail,
+        Ok(ErrorBody::Detail { detail }) => detail.to_string(),
+        Err(_) => body.to_string(),
+    }
+}
+
+/// Whether `body` reports a content policy rejection, by error code or type, or
+/// failing that by message.
+fn is_content_rejection(body: &str) -> bool {
+    if let Ok(ErrorBody::OpenAi { error }) = serde_json::from_str(body) {
+        let code = error.code.as_ref().and_then(serde_json::Value::as_str);
+        if [code, error.error_type.as_deref()]
+            .into_iter()
+            .flatten()
+            .any(|code| CONTENT_POLICY_CODES.contains(&code))
+        {
+            return true;
+        }
+    }
+    let message = error_message(body).to_ascii_lowercase();
+    message.contains("content policy") || message.contains("content_policy")
+}
+
+impl ApiError {
+    /// Returns `response` unchanged if it succeeded, or reads its body into an
+    /// [`ApiError`] otherwise.
+    pub(crate) async fn check(response: Response) -> Result<Response, ApiError> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+        Err(ApiError::from_parts(status, &headers, body))
+    }
+
+    /// Classifies a non-success response from its status, headers and body.
+    pub(crate) fn from_parts(status: StatusCode, headers: &HeaderMap, body: String) -> ApiError {
+        let retry_after = headers
+            .get(http::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        let request_id = request_id(headers);
+
+        let code = status.as_u16();
+        let rejectable = matches!(
+            status,
+            StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN | StatusCode::UNPROCESSABLE_ENTITY
+        );
+        if rejectable && is_content_rejection(&body) {
+            return ApiError::ContentRejected {
+                status: code,
+                request_id,
+                message: error_message(&body),
+                body,
+            };
+        }
+
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => ApiError::Unauthorized {
+                status: code,
+                request_id,
+                body,
+            },
+            StatusCode::TOO_MANY_REQUESTS => ApiError::RateLimited {
+                status: code,
+                request_id,
+                retry_after,
+                body,
+            },
+            StatusCode::NOT_FOUND => ApiError::ModelNotFound {
+                status: code,
+                request_id,
+                body,
+            },
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => ApiError::Validation {
+                status: code,
+                request_id,
+                body,
+            },
+            status if status.is_server_error() => ApiError::Server {
+                status: code,
+                request_id,
+                body,
+            },
+            _ => ApiError::Other {
+                status: code,
+                request_id,
+                body,
+            },
+        }
+    }
+
+    /// The HTTP status code of the response.
+    pub fn status(&self) -> u16 {
+        match self {
+            ApiError::Unauthorized { status, .. }
+            | ApiError::RateLimited { status, .. }
+            | ApiError::ModelNotFound { status, .. }
+            | ApiError::Validation { status, .. }
+            | ApiError::ContentRejected { status, .. }
+            | ApiError::Server { status, .. }
+            | ApiError::Other { status, .. } => *status,
+        }
+    }
+
+    /// The request identifier reported by the API, if any.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            ApiError::Unauthorized { request_id, .. }
+            | ApiError::RateLimited { request_id, .. }
+            | ApiError::ModelNotFound { request_id, .. }
+            | ApiError::Validation { request_id, .. }
+            | ApiError::ContentRejected { request_id, .. }
+            | ApiError::Server { request_id, .. }
+            | ApiError::Other { request_id, .. } => request_id.as_deref(),
+        }
+    }
+
+    /// The raw response body.
+    pub fn body(&self) -> &str {
+        match self {
+            ApiError::Unauthorized { body, .. }
+            | ApiError::RateLimited { body, .. }
+            | ApiError::ModelNotFound { body, .. }
+            | ApiError::Validation { body, .. }
+            | ApiError::ContentRejected { body, .. }
+            | ApiError::Server { body, .. }
+            | ApiError::Other { body, .. } => body,
+        }
+    }
+
+    /// The error message reported by the API, falling back to the raw body.
+    pub fn message(&self) -> String {
+        match self {
+            ApiError::ContentRejected { message, .. } => message.clone(),
+            _ => error_message(self.body()),
+        }
+    }
+
+    /// The provider's explanation if the request was rejected by a content policy.
+    pub fn content_rejection(&self) -> Option<&str> {
+        match self {
+            ApiError::ContentRejected { message, .. } => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Whether retrying the same request later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ApiError::RateLimited { .. } | ApiError::Server { .. })
+    }
+}
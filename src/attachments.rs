@@ -0,0 +1,342 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use thiserror::Error;
+use tracing::instrument;
+
+#[cfg(feature = "audio_transcription")]
+use crate::audio_transcription::{AudioTranscriptionError, AudioTranscriptionRequest};
+use crate::chat_completition::{ContentPart, UserMessage};
+use crate::chunking::Chunker;
+use crate::client::DeepinfraClient;
+#[cfg(feature = "image")]
+use crate::image_input::{ImageInputError, ImageResize};
+
+#[derive(Debug, Error)]
+pub enum AttachmentError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[cfg(feature = "audio_transcription")]
+    #[error("Audio transcription error: {0}")]
+    AudioTranscriptionError(#[from] AudioTranscriptionError),
+    #[cfg(feature = "image")]
+    #[error("Image error: {0}")]
+    ImageInputError(#[from] ImageInputError),
+    /// The attachment was rejected by a preprocessor.
+    #[error("Attachment rejected: {0}")]
+    Rejected(String),
+}
+
+/// A file the user attached to a chat message.
+pub enum Attachment {
+    /// An image file, embedded as an image part for vision models.
+    Image(PathBuf),
+    /// An encoded image, embedded as an image part for vision models.
+    ImageBytes(Vec<u8>),
+    /// A text file, inlined into the message.
+    Text { name: String, content: String },
+    /// A recording, transcribed first and inlined into the message.
+    #[cfg(feature = "audio_transcription")]
+    Audio {
+        name: String,
+        request: AudioTranscriptionRequest,
+    },
+    /// A content part added to the message unchanged, e.g. by a preprocessor.
+    Part(ContentPart),
+}
+
+impl Attachment {
+    /// A text file read from `path` and named after its file name.
+    pub fn text_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        Ok(Attachment::Text {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            content: std::fs::read_to_string(path)?,
+        })
+    }
+}
+
+/// A step applied to every attachment before it is added to the message.
+///
+/// Any `Fn(Attachment) -> Result<Attachment, AttachmentError>` closure is a
+/// preprocessor, e.g. to reject files that are too large or to redact text files.
+pub trait AttachmentPreprocessor: Send + Sync {
+    fn preprocess(&self, attachment: Attachment) -> Result<Attachment, AttachmentError>;
+}
+
+impl<F> AttachmentPreprocessor for F
+where
+    F: Fn(Attachment) -> Result<Attachment, AttachmentError> + Send + Sync,
+{
+    fn preprocess(&self, attachment: Attachment) -> Result<Attachment, AttachmentError> {
+        self(attachment)
+    }
+}
+
+/// Scales attached images down before they are embedded.
+#[cfg(feature = "image")]
+impl AttachmentPreprocessor for ImageResize {
+    fn preprocess(&self, attachment: Attachment) -> Result<Attachment, AttachmentError> {
+        Ok(match attachment {
+            Attachment::Image(path) => {
+                Attachment::Part(ContentPart::image_file_resized(path, self)?)
+            }
+            Attachment::ImageBytes(bytes) => {
+                Attachment::Part(ContentPart::image_bytes_resized(&bytes, self)?)
+            }
+            attachment => attachment,
+        })
+    }
+}
+
+/// How attachments are turned into the parts of a user message.
+///
+/// Attachments run through the preprocessors in order. Text files and transcripts
+/// are inlined whole, or split with the chunker when one is set.
+///
+/// # Example
+///
+/// ```no_run
+/// # use deepinfra_client_rs::prelude::*;
+/// # use deepinfra_client_rs::chat_completition::*;
+/// use deepinfra_client_rs::attachments::*;
+/// use deepinfra_client_rs::chunking::Chunker;
+///
+/// # async fn run(client: DeepinfraClient) -> Result<(), Box<dyn std::error::Error>> {
+/// let pipeline = AttachmentPipeline::new()
+///     .with(|attachment| match attachment {
+///         Attachment::Text { content, .. } if content.len() > 1_000_000 => {
+///             Err(AttachmentError::Rejected("text file too large".to_string()))
+///         }
+///         attachment => Ok(attachment),
+///     })
+///     .chunker(Chunker::builder().max_tokens(2048).overlap_tokens(0).build());
+///
+/// let message = client
+///     .user_message_with_attachments(
+///         "What does this diagram say about the notes?",
+///         vec![
+///             Attachment::Image("diagram.png".into()),
+///             Attachment::text_file("notes.md")?,
+///         ],
+///         &pipeline,
+///     )
+///     .await?;
+///
+/// let request = ChatCompletionRequest::builder()
+///     .model("meta-llama/Llama-3.2-11B-Vision-Instruct".to_string())
+///     .messages(vec![Message::User(message)])
+///     .build();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct AttachmentPipeline {
+    preprocessors: Vec<Arc<dyn AttachmentPreprocessor>>,
+    chunker: Option<Chunker>,
+}
+
+impl AttachmentPipeline {
+    /// Creates a pipeline that inlines attachments as they are.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a preprocessor to the end of the pipeline.
+    pub fn with(mut self, preprocessor: impl AttachmentPreprocessor + 'static) -> Self {
+        self.preprocessors.push(Arc::new(preprocessor));
+        self
+    }
+
+    /// Splits inlined text into one part per chunk.
+    pub fn chunker(mut self, chunker: Chunker) -> Self {
+        self.chunker = Some(chunker);
+        self
+    }
+
+    fn preprocess(&self, attachment: Attachment) -> Result<Attachment, AttachmentError> {
+        self.preprocessors
+            .iter()
+            .try_fold(attachment, |attachment, preprocessor| {
+                preprocessor.preprocess(attachment)
+            })
+    }
+
+    /// Text parts inlining `content`, labelled with `name`.
+    fn text_parts(&self, name: &str, content: &str) -> Vec<ContentPart> {
+        let chunks = match &self.chunker {
+            Some(chunker) => chunker
+                .chunk(content)
+                .into_iter()
+                .map(|chunk| chunk.text)
+                .collect(),
+            None => vec![content.to_string()],
+        };
+        let count = chunks.len();
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| {
+                let label = match count {
+                    1 => name.to_string(),
+                    _ => format!("{name} (part {} of {count})", index + 1),
+                };
+                ContentPart::text(format!("Attached file {label}:\n\n{chunk}"))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for AttachmentPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AttachmentPipeline")
+            .field("preprocessors", &self.preprocessors.len())
+            .field("chunker", &self.chunker)
+            .finish()
+    }
+}
+
+impl DeepinfraClient {
+    /// Builds a user message from `text` and the files the user attached to it.
+    ///
+    /// Images become image parts, text files are inlined, and recordings are
+    /// transcribed and inlined, after running through the preprocessors of
+    /// `pipeline`.
+    #[instrument(skip(self, text, attachments, pipeline))]
+    pub async fn user_message_with_attachments(
+        &self,
+        text: impl Into<String>,
+        attachments: Vec<Attachment>,
+        pipeline: &AttachmentPipeline,
+    ) -> Result<UserMessage, AttachmentError> {
+        let mut parts = vec![ContentPart::text(text)];
+        for attachment in attachments {
+            match pipeline.preprocess(attachment)? {
+                Attachment::Image(path) => parts.push(ContentPart::image_file(path)?),
+                Attachment::ImageBytes(bytes) => {
+                    let mime_type = crate::data_url::image_mime_type(&bytes).unwrap_or("image/png");
+                    parts.push(ContentPart::image_bytes(&bytes, mime_type));
+                }
+                Attachment::Text { name, content } => {
+                    parts.extend(pipeline.text_parts(&name, &content));
+                }
+                #[cfg(feature = "audio_transcription")]
+                Attachment::Audio { name, request } => {
+                    let transcript = self.audio_transcription(request).await?;
+                    let name = format!("{name} (transcript)");
+                    parts.extend(pipeline.text_parts(&name, &transcript.text));
+                }
+                Attachment::Part(part) => parts.push(part),
+            }
+        }
+        Ok(UserMessage::builder().content(parts).build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> DeepinfraClient {
+        DeepinfraClient::builder().token("token").build().unwrap()
+    }
+
+    async fn parts(
+        attachments: Vec<Attachment>,
+        pipeline: &AttachmentPipeline,
+    ) -> Vec<serde_json::Value> {
+        let message = client()
+            .user_message_with_attachments("Look at these.", attachments, pipeline)
+            .await
+            .unwrap();
+        let serde_json::Value::Array(parts) =
+            serde_json::to_value(message).unwrap()["content"].take()
+        else {
+            panic!("message without parts");
+        };
+        parts
+    }
+
+    #[tokio::test]
+    async fn turns_attachments_into_parts() {
+        let parts = parts(
+            vec![
+                Attachment::ImageBytes(b"\xff\xd8\xff\xe0".to_vec()),
+                Attachment::Text {
+                    name: "notes.txt".to_string(),
+                    content: "Buy milk.".to_string(),
+                },
+                Attachment::Part(ContentPart::image_url("https://example.com/cat.png")),
+            ],
+            &AttachmentPipeline::new(),
+        )
+        .await;
+
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[0]["text"], "Look at these.");
+        assert!(parts[1]["image_url"]["url"]
+            .as_str()
+            .unwrap()
+            .starts_with("data:image/jpeg;base64,"));
+        assert_eq!(parts[2]["text"], "Attached file notes.txt:\n\nBuy milk.");
+        assert_eq!(parts[3]["image_url"]["url"], "https://example.com/cat.png");
+    }
+
+    #[tokio::test]
+    async fn chunks_long_text_files() {
+        let pipeline = AttachmentPipeline::new()
+            .chunker(Chunker::builder().max_tokens(4).overlap_tokens(0).build());
+        let parts = parts(
+            vec![Attachment::Text {
+                name: "log.txt".to_string(),
+                content: "one two three four five six".to_string(),
+            }],
+            &pipeline,
+        )
+        .await;
+
+        assert_eq!(parts.len(), 3);
+        assert!(parts[1]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with("Attached file log.txt (part 1 of 2):"));
+    }
+
+    #[tokio::test]
+    async fn runs_preprocessors_in_order() {
+        let pipeline = AttachmentPipeline::new()
+            .with(|attachment| match attachment {
+                Attachment::Text { name, content } => Ok(Attachment::Text {
+                    name,
+                    content: content.replace("secret", "[redacted]"),
+                }),
+                attachment => Ok(attachment),
+            })
+            .with(|attachment| match attachment {
+                Attachment::Image(_) => Err(AttachmentError::Rejected("no images".to_string())),
+                attachment => Ok(attachment),
+            });
+
+        let parts = parts(
+            vec![Attachment::Text {
+                name: "a.txt".to_string(),
+                content: "the secret word".to_string(),
+            }],
+            &pipeline,
+        )
+        .await;
+        assert_eq!(
+            parts[1]["text"],
+            "Attached file a.txt:\n\nthe [redacted] word"
+        );
+
+        let rejected = client()
+            .user_message_with_attachments("", vec![Attachment::Image("cat.png".into())], &pipeline)
+            .await;
+        assert!(matches!(rejected, Err(AttachmentError::Rejected(_))));
+    }
+}
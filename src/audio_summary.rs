@@ -0,0 +1,406 @@
+use crate::audio_transcription::{
+    AudioTranscriptionError, AudioTranscriptionRequest, AudioTranscriptionResponse, FileSource,
+};
+use crate::client::DeepinfraClient;
+use crate::summarization::{SummarizeError, SummarizeOptions, Summary};
+use bon::Builder;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::path::Path;
+use std::time::Duration;
+use tracing::instrument;
+
+const MEETING_NOTES_INSTRUCTIONS: &str = "The user provides the transcript of a recording. \
+    Write concise notes: a short overview, the key points discussed, decisions taken and \
+    action items with owners when mentioned. Reply with the notes only.";
+
+/// Options for [`DeepinfraClient::transcribe_and_summarize`].
+#[derive(Clone, Debug, Builder)]
+pub struct AudioSummaryOptions {
+    /// Longest piece of a WAV recording sent in one transcription request.
+    #[builder(default = Duration::from_secs(10 * 60))]
+    pub max_chunk_duration: Duration,
+    /// Largest piece of a WAV or MP3 recording sent in one transcription request.
+    #[builder(default = 20 * 1024 * 1024)]
+    pub max_chunk_bytes: usize,
+    /// Maximum number of transcription requests in flight.
+    #[builder(default = 4)]
+    pub concurrency: usize,
+    /// Price of the transcription model in USD per minute of audio, used to estimate
+    /// the transcription cost.
+    pub price_per_minute: Option<f64>,
+    /// Options of the summarization step.
+    #[builder(default)]
+    pub summarize: SummarizeOptions,
+}
+
+impl Default for AudioSummaryOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// The audio transcribed for an [`AudioSummary`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TranscriptionUsage {
+    /// Number of transcription requests made, one per piece of the recording.
+    pub requests: usize,
+    /// Seconds of audio transcribed, when reported for every piece. Transcription is
+    /// billed by it.
+    pub audio_seconds: Option<f64>,
+    /// Cost in USD, when a price per minute was given and the duration is known.
+    pub estimated_cost: Option<f64>,
+}
+
+/// A transcript together with its summary.
+#[derive(Debug)]
+pub struct AudioSummary {
+    /// The full transcription, joined from the transcripts of every piece.
+    pub transcript: AudioTranscriptionResponse,
+    /// The summary of the transcript, including the chat usage it consumed.
+    pub summary: Summary,
+    /// The audio transcribed to produce the transcript.
+    pub transcription_usage: TranscriptionUsage,
+    /// Transcription and summarization cost in USD; `None` when neither is known.
+    pub estimated_cost: Option<f64>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AudioSummaryError {
+    #[error("Transcription failed: {0}")]
+    TranscriptionError(#[from] AudioTranscriptionError),
+    #[error("Summarization failed: {0}")]
+    SummarizationError(#[from] SummarizeError),
+}
+
+impl DeepinfraClient {
+    /// Transcribes an audio file and summarizes the transcript.
+    ///
+    /// Recordings too long for one transcription request are split into pieces that
+    /// are transcribed concurrently and joined. WAV files are split by duration and
+    /// size, MP3 files by size at frame boundaries; other formats are sent whole.
+    ///
+    /// Long transcripts are summarized with [`DeepinfraClient::summarize_long`], so they
+    /// are chunked to fit the summarization model. When `options` carries no custom
+    /// instructions, the summary is written as meeting notes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::audio_summary::*;
+    /// # use deepinfra_client_rs::audio_transcription::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), AudioSummaryError> {
+    /// let request = AudioTranscriptionRequest::builder()
+    ///     .source(FileSource::Filepath(std::path::Path::new("/path/to/meeting.mp3").into()))
+    ///     .build();
+    /// let options = AudioSummaryOptions::builder().price_per_minute(0.0002).build();
+    ///
+    /// let notes = client.transcribe_and_summarize(request, &options).await?;
+    /// println!("{}", notes.summary.summary);
+    /// println!("Cost: {:?} USD", notes.estimated_cost);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request))]
+    pub async fn transcribe_and_summarize(
+        &self,
+        mut request: AudioTranscriptionRequest,
+        options: &AudioSummaryOptions,
+    ) -> Result<AudioSummary, AudioSummaryError> {
+        let pieces = split_source(request.take_source(), options).await?;
+        let requests = pieces.len();
+        let responses: Vec<AudioTranscriptionResponse> = stream::iter(pieces)
+            .map(|piece| self.audio_transcription(request.with_source(piece)))
+            .buffered(options.concurrency.max(1))
+            .try_collect()
+            .await?;
+        let transcript = join_transcripts(responses);
+
+        let summary = if options.summarize.instructions.is_some() {
+            self.summarize_long(&transcript.text, &options.summarize)
+                .await?
+        } else {
+            let summarize = SummarizeOptions {
+                instructions: Some(MEETING_NOTES_INSTRUCTIONS.to_string()),
+                ..options.summarize.clone()
+            };
+            self.summarize_long(&transcript.text, &summarize).await?
+        };
+
+        let transcription_usage = TranscriptionUsage {
+            requests,
+            audio_seconds: transcript.duration,
+            estimated_cost: transcript
+                .duration
+                .zip(options.price_per_minute)
+                .map(|(seconds, price)| seconds / 60.0 * price),
+        };
+        let summary_cost = summary
+            .usage
+            .as_ref()
+            .and_then(|usage| usage.estimated_cost);
+        let estimated_cost = match (transcription_usage.estimated_cost, summary_cost) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        };
+
+        Ok(AudioSummary {
+            transcript,
+            summary,
+            transcription_usage,
+            estimated_cost,
+        })
+    }
+}
+
+/// Splits `source` into pieces small enough for one transcription request each.
+async fn split_source(
+    source: FileSource,
+    options: &AudioSummaryOptions,
+) -> Result<Vec<FileSource>, AudioTranscriptionError> {
+    let (buffer, file_name) = match source {
+        FileSource::Filepath(path) => {
+            if !path.exists() {
+                return Err(AudioTranscriptionError::FileNotFoundError(
+                    path.to_string_lossy().into_owned(),
+                ));
+            }
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            (tokio::fs::read(&path).await?, file_name)
+        }
+        FileSource::Bytes { buffer, file_name } => (buffer, file_name),
+    };
+
+    let pieces = split_wav(&buffer, options.max_chunk_duration, options.max_chunk_bytes)
+        .or_else(|| is_mp3(&file_name).then(|| split_mp3(&buffer, options.max_chunk_bytes)))
+        .filter(|pieces| pieces.len() > 1);
+    let Some(pieces) = pieces else {
+        return Ok(vec![FileSource::Bytes { buffer, file_name }]);
+    };
+
+    let path = Path::new(&file_name);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = path.extension().unwrap_or_default().to_string_lossy();
+    Ok(pieces
+        .into_iter()
+        .enumerate()
+        .map(|(index, buffer)| FileSource::Bytes {
+            buffer,
+            file_name: format!("{stem}-{index}.{extension}"),
+        })
+        .collect())
+}
+
+/// Joins the transcripts of consecutive pieces, shifting timestamps by the duration
+/// of the pieces before them.
+fn join_transcripts(responses: Vec<AudioTranscriptionResponse>) -> AudioTranscriptionResponse {
+    let mut responses = responses.into_iter();
+    let Some(mut joined) = responses.next() else {
+        return AudioTranscriptionResponse {
+            text: String::new(),
+            language: None,
+            duration: None,
+            segments: Vec::new(),
+            words: Vec::new(),
+            metadata: Default::default(),
+        };
+    };
+
+    let mut offset = joined.duration;
+    for response in responses {
+        let shift = offset.unwrap_or_default();
+        let text = response.text.trim();
+        if !text.is_empty() {
+            if !joined.text.is_empty() {
+                joined.text.push(' ');
+            }
+            joined.text.push_str(text);
+        }
+        joined.language = joined.language.or(response.language);
+        joined
+            .segments
+            .extend(response.segments.into_iter().map(|mut segment| {
+                segment.start += shift;
+                segment.end += shift;
+                segment
+            }));
+        joined
+            .words
+            .extend(response.words.into_iter().map(|mut word| {
+                word.start += shift;
+                word.end += shift;
+                word
+            }));
+        offset = offset.zip(response.duration).map(|(a, b)| a + b);
+        joined.metadata = response.metadata;
+    }
+    joined.duration = offset;
+    joined
+}
+
+/// Splits a PCM WAV file into valid WAV files of at most `max_duration` and
+/// `max_bytes` each. Returns `None` if `buffer` is not a WAV file.
+fn split_wav(buffer: &[u8], max_duration: Duration, max_bytes: usize) -> Option<Vec<Vec<u8>>> {
+    if buffer.len() < 12 || &buffer[0..4] != b"RIFF" || &buffer[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut fmt = None;
+    let mut data = None;
+    let mut position = 12;
+    while position + 8 <= buffer.len() {
+        let id = &buffer[position..position + 4];
+        let size = u32::from_le_bytes(buffer[position + 4..position + 8].try_into().ok()?);
+        let start = position + 8;
+        let end = start.checked_add(size as usize)?.min(buffer.len());
+        match id {
+            b"fmt " => fmt = Some(&buffer[position..end]),
+            b"data" => data = Some(&buffer[start..end]),
+            _ => {}
+        }
+        // Chunks are padded to an even size.
+        position = end + (size as usize & 1);
+    }
+    let (fmt, data) = (fmt?, data?);
+    if fmt.len() < 24 {
+        return None;
+    }
+    let byte_rate = u32::from_le_bytes(fmt[16..20].try_into().ok()?) as usize;
+    let block_align = usize::from(u16::from_le_bytes(fmt[20..22].try_into().ok()?)).max(1);
+
+    let header_len = 12 + fmt.len() + 8;
+    let by_duration = (max_duration.as_secs_f64() * byte_rate as f64) as usize;
+    let by_size = max_bytes.saturating_sub(header_len);
+    let piece_len = (by_duration.min(by_size) / block_align * block_align).max(block_align);
+
+    Some(
+        data.chunks(piece_len)
+            .map(|samples| {
+                let mut piece = Vec::with_capacity(header_len + samples.len());
+                piece.extend_from_slice(b"RIFF");
+                piece.extend_from_slice(&((header_len - 8 + samples.len()) as u32).to_le_bytes());
+                piece.extend_from_slice(b"WAVE");
+                piece.extend_from_slice(fmt);
+                piece.extend_from_slice(b"data");
+                piece.extend_from_slice(&(samples.len() as u32).to_le_bytes());
+                piece.extend_from_slice(samples);
+                piece
+            })
+            .collect(),
+    )
+}
+
+fn is_mp3(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("mp3"))
+}
+
+/// Splits an MP3 file into pieces of at most `max_bytes`, each starting at a frame
+/// header so it decodes on its own.
+fn split_mp3(buffer: &[u8], max_bytes: usize) -> Vec<Vec<u8>> {
+    let max_bytes = max_bytes.max(1);
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    while buffer.len() - start > max_bytes {
+        let limit = start + max_bytes;
+        // The last frame sync at or before the limit, past the start of the piece.
+        let cut = (start + 1..limit)
+            .rev()
+            .find(|&index| buffer[index] == 0xFF && buffer[index + 1] & 0xE0 == 0xE0)
+            .unwrap_or(limit);
+        pieces.push(buffer[start..cut].to_vec());
+        start = cut;
+    }
+    pieces.push(buffer[start..].to_vec());
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(samples: &[u8], byte_rate: u32, block_align: u16) -> Vec<u8> {
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&(byte_rate / u32::from(block_align)).to_le_bytes());
+        fmt.extend_from_slice(&byte_rate.to_le_bytes());
+        fmt.extend_from_slice(&block_align.to_le_bytes());
+        fmt.extend_from_slice(&(block_align * 8).to_le_bytes());
+
+        let mut wav = b"RIFF".to_vec();
+        wav.extend_from_slice(&((4 + 24 + 8 + samples.len()) as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&fmt);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(samples.len() as u32).to_le_bytes());
+        wav.extend_from_slice(samples);
+        wav
+    }
+
+    #[test]
+    fn splits_wav_files_by_duration_into_valid_files() {
+        let samples: Vec<u8> = (0..250).collect();
+        let pieces = split_wav(&wav(&samples, 100, 2), Duration::from_secs(1), usize::MAX).unwrap();
+
+        assert_eq!(pieces.len(), 3);
+        let mut rejoined = Vec::new();
+        for piece in &pieces {
+            let reparsed = split_wav(piece, Duration::from_secs(10), usize::MAX).unwrap();
+            assert_eq!(reparsed.len(), 1, "each piece is a complete WAV file");
+            assert_eq!(&reparsed[0], piece);
+            rejoined.extend_from_slice(&piece[44..]);
+        }
+        assert_eq!(rejoined, samples);
+    }
+
+    #[test]
+    fn splits_wav_files_by_size_on_sample_boundaries() {
+        let pieces = split_wav(&wav(&[0; 100], 100, 4), Duration::from_secs(60), 44 + 30).unwrap();
+        let lengths: Vec<usize> = pieces.iter().map(|piece| piece.len() - 44).collect();
+        assert_eq!(lengths, [28, 28, 28, 16]);
+    }
+
+    #[test]
+    fn does_not_treat_other_formats_as_wav() {
+        assert!(split_wav(b"ID3\x04rest of an mp3", Duration::from_secs(1), 10).is_none());
+    }
+
+    #[test]
+    fn splits_mp3_files_at_frame_headers() {
+        let frame = [0xFF, 0xFB, 0x90, 0x00, 1, 2, 3, 4];
+        let buffer = frame.repeat(4);
+        let pieces = split_mp3(&buffer, 20);
+
+        let lengths: Vec<usize> = pieces.iter().map(Vec::len).collect();
+        assert_eq!(lengths, [16, 16]);
+        assert!(pieces.iter().all(|piece| piece.starts_with(&[0xFF, 0xFB])));
+        assert_eq!(pieces.concat(), buffer);
+    }
+
+    #[test]
+    fn joins_transcripts_and_shifts_timestamps() {
+        let response = |text: &str, duration, start| {
+            serde_json::from_value::<AudioTranscriptionResponse>(serde_json::json!({
+                "text": text,
+                "duration": duration,
+                "segments": [{ "start": start, "end": start + 1.0, "text": text }],
+            }))
+            .unwrap()
+        };
+        let joined = join_transcripts(vec![
+            response("Hello.", 60.0, 2.0),
+            response(" World.", 30.0, 5.0),
+        ]);
+
+        assert_eq!(joined.text, "Hello. World.");
+        assert_eq!(joined.duration, Some(90.0));
+        assert_eq!(joined.segments[1].start, 65.0);
+    }
+}
@@ -1,24 +1,75 @@
-use crate::client::DeepinfraClient;
+use crate::api_error::{read_json, ApiError, DecodeError};
+use crate::client::{with_timeout, DeepinfraClient};
+use crate::endpoints::Endpoint;
+use crate::language::Language;
+use crate::metadata::ResponseMetadata;
+use crate::raw::RawResponse;
+use crate::shutdown::ShutdownError;
 use bon::Builder;
+use http::Method;
 use reqwest::multipart;
 use serde::Deserialize;
 use std::path::Path;
+use std::time::{Duration, Instant};
 use tracing::instrument;
 
-const AUDIO_TRANSCRIPTION_API_URL: &str =
-    "https://api.deepinfra.com/v1/openai/audio/transcriptions";
+pub(crate) const AUDIO_TRANSCRIPTION_PATH: &str = Endpoint::AudioTranscriptions.path();
 
 #[derive(Debug, Deserialize)]
 pub struct AudioTranscriptionResponse {
     pub text: String,
+    /// The language detected in (or forced for) the audio, when reported.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Length of the audio in seconds, when reported. Transcription is billed by it.
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// Timed segments of the transcript, returned for the `verbose_json` format.
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+    /// Timed words, returned for the `verbose_json` format when `word` is one of the
+    /// `timestamp_granularities`.
+    #[serde(default)]
+    pub words: Vec<Word>,
+    /// Status and headers of the HTTP response, including DeepInfra runtime headers.
+    #[serde(skip)]
+    pub metadata: ResponseMetadata,
+}
+
+/// A timed piece of a transcript, returned for the `verbose_json` format.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Segment {
+    /// Start time in seconds.
+    pub start: f64,
+    /// End time in seconds.
+    pub end: f64,
+    pub text: String,
+    /// Probability that the segment is transcribed correctly, when reported.
+    #[serde(default, alias = "probability")]
+    pub confidence: Option<f64>,
+}
+
+/// A timed word, returned for the `verbose_json` format with `word` timestamp
+/// granularity.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Word {
+    /// Start time in seconds.
+    pub start: f64,
+    /// End time in seconds.
+    pub end: f64,
+    #[serde(alias = "word")]
+    pub text: String,
+    /// Probability that the word is transcribed correctly, when reported.
+    #[serde(default, alias = "probability")]
+    pub confidence: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ErrorDetail {
-    loc: Vec<String>,
-    msg: String,
+    pub loc: Vec<String>,
+    pub msg: String,
     #[serde(rename = "type")]
-    error_type: String,
+    pub error_type: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -31,51 +82,79 @@ pub enum ErrorResponse {
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum AudioTranscriptionApiResponse {
-    TranscriptionResponse(AudioTranscriptionResponse),
+    TranscriptionResponse(Box<AudioTranscriptionResponse>),
     ErrorResponse(ErrorResponse),
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum AudioTranscriptionError {
     #[error("Request error: {0}")]
-    ReqwestError(#[from] reqwest::Error),
+    Transport(#[from] reqwest::Error),
     #[error("File not found: {0}")]
     FileNotFoundError(String),
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
     #[error("Error response: {0}")]
     ErrorResponse(String),
+    #[error("Client shutdown: {0}")]
+    ShutdownError(#[from] ShutdownError),
+    #[error("API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Could not decode response: {0}")]
+    DecodeError(#[from] DecodeError),
+}
+
+/// A subtitle response format of the transcription endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    /// SubRip (`.srt`).
+    Srt,
+    /// WebVTT (`.vtt`).
+    Vtt,
 }
 
-#[derive(Debug)]
+impl SubtitleFormat {
+    /// The `response_format` value sent to the API.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FileSource {
     Filepath(Box<Path>),
     Bytes { buffer: Vec<u8>, file_name: String },
 }
 
-#[derive(Builder)]
+#[derive(Builder, Deserialize)]
 /// Represents a request to transcribe an audio file.
 ///
 /// # Fields
-/// - `language`: Optional language of the input audio (ISO-639-1 format).
+/// - `language`: Optional language of the input audio.
 /// - `model`: The transcription model to use (default: "openai/whisper-large-v3-turbo").
 /// - `prompt`: Optional prompt to guide the transcription output.
 /// - `response_format`: The desired format of the transcription response (e.g., "json", "text").
 /// - `source`: The audio source; can be either a file path or a byte buffer.
 /// - `temperature`: Optional sampling temperature (between 0 and 1).
 /// - `timestamp_granularities`: Optional list specifying timestamp granularities.
+#[serde(deny_unknown_fields)]
 pub struct AudioTranscriptionRequest {
-    /// Optional language of the input audio in ISO-639-1 format.
-    #[builder(into)]
-    language: Option<String>,
+    /// Optional language of the input audio.
+    language: Option<Language>,
     /// The transcription model to use (default: "openai/whisper-large-v3-turbo").
-    #[builder(default = "openai/whisper-large-v3-turbo".to_string())]
+    #[serde(default = "default_model")]
+    #[builder(default = default_model())]
     model: String,
     /// Optional prompt to guide the transcription style.
     #[builder(into)]
     prompt: Option<String>,
     /// The desired response format (default: "json").
-    #[builder(default = "json".to_string())]
+    #[serde(default = "default_response_format")]
+    #[builder(default = default_response_format())]
     response_format: String,
     /// The audio source: either a file path or a buffer with file name.
     #[builder(into)]
@@ -86,6 +165,51 @@ pub struct AudioTranscriptionRequest {
     /// Optional timestamp granularities for transcription.
     #[builder(into)]
     timestamp_granularities: Option<Vec<String>>,
+    /// Overrides the client's timeout, e.g. for long recordings.
+    #[serde(skip)]
+    timeout: Option<Duration>,
+}
+
+#[cfg(feature = "chat_completition")]
+impl AudioTranscriptionRequest {
+    /// Takes the audio source out of the request, leaving an empty buffer.
+    pub(crate) fn take_source(&mut self) -> FileSource {
+        let empty = FileSource::Bytes {
+            buffer: Vec::new(),
+            file_name: String::new(),
+        };
+        std::mem::replace(&mut self.source, empty)
+    }
+
+    /// A copy of the request transcribing `source` instead.
+    pub(crate) fn with_source(&self, source: FileSource) -> Self {
+        Self {
+            language: self.language.clone(),
+            model: self.model.clone(),
+            prompt: self.prompt.clone(),
+            response_format: self.response_format.clone(),
+            source,
+            temperature: self.temperature,
+            timestamp_granularities: self.timestamp_granularities.clone(),
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// A multipart audio form ready to be sent.
+pub(crate) struct AudioForm {
+    pub(crate) form: multipart::Form,
+    /// The resolved model name.
+    pub(crate) model: String,
+    pub(crate) timeout: Option<Duration>,
+}
+
+pub(crate) fn default_model() -> String {
+    "openai/whisper-large-v3-turbo".to_string()
+}
+
+pub(crate) fn default_response_format() -> String {
+    "json".to_string()
 }
 
 impl DeepinfraClient {
@@ -104,35 +228,101 @@ impl DeepinfraClient {
     ///
     /// Returns an `AudioTranscriptionResponse` with the transcribed text if successful,
     /// or an `AudioTranscriptionError` in case of a failure.
+    ///
+    /// The `srt` and `vtt` response formats are not JSON; use
+    /// [`DeepinfraClient::audio_transcription_subtitles`] for them.
     #[instrument(skip(self, request))]
     pub async fn audio_transcription(
         &self,
         request: AudioTranscriptionRequest,
     ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
-        let mut form = multipart::Form::new()
-            .text("model", request.model.to_string())
-            .text("response_format", request.response_format.to_string());
+        self.lifecycle
+            .track(self.send_audio_transcription(request))
+            .await?
+    }
 
-        match request.source {
-            FileSource::Filepath(file_path) => {
-                let file_path = file_path.as_ref();
+    async fn send_audio_transcription(
+        &self,
+        request: AudioTranscriptionRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
+        let form = self.transcription_form(request).await?;
+        self.send_audio_form(AUDIO_TRANSCRIPTION_PATH, form).await
+    }
 
-                if !file_path.exists() {
-                    return Err(AudioTranscriptionError::FileNotFoundError(
-                        file_path.to_string_lossy().into_owned(),
-                    ));
-                }
+    /// Transcribes an audio file and returns the response before deserialization.
+    #[instrument(skip(self, request))]
+    pub async fn audio_transcription_raw(
+        &self,
+        request: AudioTranscriptionRequest,
+    ) -> Result<RawResponse, AudioTranscriptionError> {
+        self.lifecycle
+            .track(async {
+                let form = self.transcription_form(request).await?;
+                self.send_audio_form_raw(AUDIO_TRANSCRIPTION_PATH, form)
+                    .await
+            })
+            .await?
+    }
 
-                form = form.file("file", file_path).await?;
-            }
-            FileSource::Bytes { buffer, file_name } => {
-                let part = multipart::Part::bytes(buffer).file_name(file_name);
-                form = form.part("file", part);
-            }
-        }
+    /// Transcribes an audio file directly into a subtitle file.
+    ///
+    /// The response format of `request` is replaced by `format`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::audio_transcription::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = AudioTranscriptionRequest::builder()
+    ///     .source(FileSource::Filepath(std::path::Path::new("talk.mp3").into()))
+    ///     .build();
+    ///
+    /// let subtitles = client
+    ///     .audio_transcription_subtitles(request, SubtitleFormat::Srt)
+    ///     .await?;
+    /// std::fs::write("talk.srt", subtitles)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request))]
+    pub async fn audio_transcription_subtitles(
+        &self,
+        mut request: AudioTranscriptionRequest,
+        format: SubtitleFormat,
+    ) -> Result<String, AudioTranscriptionError> {
+        request.response_format = format.as_str().to_string();
+        self.lifecycle
+            .track(async {
+                let AudioForm {
+                    form,
+                    model,
+                    timeout,
+                } = self.transcription_form(request).await?;
+                let _permit = self.throttle(&model).await;
+                let request = self.request(Method::POST, AUDIO_TRANSCRIPTION_PATH);
+                let response = self
+                    .send(with_timeout(request, timeout).multipart(form))
+                    .await?;
+                Ok(ApiError::check(response).await?.text().await?)
+            })
+            .await?
+    }
+
+    /// Builds the multipart form for `request`.
+    pub(crate) async fn transcription_form(
+        &self,
+        mut request: AudioTranscriptionRequest,
+    ) -> Result<AudioForm, AudioTranscriptionError> {
+        request.model = self.resolve_model(&request.model);
+        let mut form = multipart::Form::new()
+            .text("model", request.model.clone())
+            .text("response_format", request.response_format.to_string());
+
+        form = attach_source(form, request.source).await?;
 
         if let Some(language) = request.language {
-            form = form.text("language", language.to_string());
+            form = form.text("language", language.code().to_string());
         }
         if let Some(prompt) = request.prompt {
             form = form.text("prompt", prompt.to_string());
@@ -146,17 +336,34 @@ impl DeepinfraClient {
             }
         }
 
-        let response = self
-            .client
-            .post(AUDIO_TRANSCRIPTION_API_URL)
-            .multipart(form)
-            .send()
-            .await?
-            .json::<AudioTranscriptionApiResponse>()
-            .await?;
+        Ok(AudioForm {
+            form,
+            model: request.model,
+            timeout: request.timeout,
+        })
+    }
+
+    /// Sends a transcription or translation form to `path` and parses the response.
+    pub(crate) async fn send_audio_form(
+        &self,
+        path: &str,
+        form: AudioForm,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
+        let _permit = self.throttle(&form.model).await;
+        let started = Instant::now();
+        let request = with_timeout(self.request(Method::POST, path), form.timeout);
+        let http_response = self.send(request.multipart(form.form)).await?;
+        let http_response = ApiError::check(http_response).await?;
+        let metadata = self.response_metadata(&http_response, started);
+
+        // Error bodies can still arrive with a success status.
+        let response: AudioTranscriptionApiResponse = read_json(http_response).await??;
 
         match response {
-            AudioTranscriptionApiResponse::TranscriptionResponse(response) => Ok(response),
+            AudioTranscriptionApiResponse::TranscriptionResponse(mut response) => {
+                response.metadata = metadata.finish(started);
+                Ok(*response)
+            }
             AudioTranscriptionApiResponse::ErrorResponse(error) => match error {
                 ErrorResponse::Simple { detail } => {
                     Err(AudioTranscriptionError::ErrorResponse(detail))
@@ -173,4 +380,39 @@ impl DeepinfraClient {
             },
         }
     }
+
+    /// Sends a transcription or translation form to `path` without parsing the response.
+    pub(crate) async fn send_audio_form_raw(
+        &self,
+        path: &str,
+        form: AudioForm,
+    ) -> Result<RawResponse, AudioTranscriptionError> {
+        let _permit = self.throttle(&form.model).await;
+        let request = with_timeout(self.request(Method::POST, path), form.timeout);
+        Ok(self.send_raw(request.multipart(form.form)).await?)
+    }
+}
+
+/// Adds the audio file of `source` to `form`.
+pub(crate) async fn attach_source(
+    form: multipart::Form,
+    source: FileSource,
+) -> Result<multipart::Form, AudioTranscriptionError> {
+    match source {
+        FileSource::Filepath(file_path) => {
+            let file_path = file_path.as_ref();
+
+            if !file_path.exists() {
+                return Err(AudioTranscriptionError::FileNotFoundError(
+                    file_path.to_string_lossy().into_owned(),
+                ));
+            }
+
+            Ok(form.file("file", file_path).await?)
+        }
+        FileSource::Bytes { buffer, file_name } => {
+            let part = multipart::Part::bytes(buffer).file_name(file_name);
+            Ok(form.part("file", part))
+        }
+    }
 }
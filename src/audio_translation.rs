@@ -0,0 +1,120 @@
+use crate::audio_transcription::{
+    attach_source, default_model, default_response_format, AudioForm, AudioTranscriptionError,
+    AudioTranscriptionResponse, FileSource,
+};
+use crate::client::DeepinfraClient;
+use crate::endpoints::Endpoint;
+use crate::raw::RawResponse;
+use bon::Builder;
+use reqwest::multipart;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::instrument;
+
+const AUDIO_TRANSLATION_PATH: &str = Endpoint::AudioTranslations.path();
+
+/// A request to translate speech into English text.
+///
+/// Mirrors [`AudioTranscriptionRequest`](crate::audio_transcription::AudioTranscriptionRequest)
+/// without the language, which is detected, and the timestamp granularities.
+#[derive(Builder, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AudioTranslationRequest {
+    /// The translation model to use (default: "openai/whisper-large-v3-turbo").
+    #[serde(default = "default_model")]
+    #[builder(default = default_model())]
+    model: String,
+    /// Optional English text to guide the translation style.
+    #[builder(into)]
+    prompt: Option<String>,
+    /// The desired response format (default: "json").
+    #[serde(default = "default_response_format")]
+    #[builder(default = default_response_format())]
+    response_format: String,
+    /// The audio source: either a file path or a buffer with file name.
+    #[builder(into)]
+    source: FileSource,
+    /// Optional temperature controlling sampling; must be between 0 and 1.
+    #[builder(into)]
+    temperature: Option<f32>,
+    /// Overrides the client's timeout, e.g. for long recordings.
+    #[serde(skip)]
+    timeout: Option<Duration>,
+}
+
+impl DeepinfraClient {
+    /// Translates the speech of an audio file into English.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::audio_transcription::*;
+    /// # use deepinfra_client_rs::audio_translation::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), AudioTranscriptionError> {
+    /// let request = AudioTranslationRequest::builder()
+    ///     .source(FileSource::Filepath(std::path::Path::new("intervista.mp3").into()))
+    ///     .build();
+    ///
+    /// let response = client.audio_translation(request).await?;
+    /// println!("{}", response.text);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request))]
+    pub async fn audio_translation(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
+        self.lifecycle
+            .track(self.send_audio_translation(request))
+            .await?
+    }
+
+    /// Translates an audio file and returns the response before deserialization.
+    #[instrument(skip(self, request))]
+    pub async fn audio_translation_raw(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<RawResponse, AudioTranscriptionError> {
+        self.lifecycle
+            .track(async {
+                let form = self.translation_form(request).await?;
+                self.send_audio_form_raw(AUDIO_TRANSLATION_PATH, form).await
+            })
+            .await?
+    }
+
+    async fn send_audio_translation(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
+        let form = self.translation_form(request).await?;
+        self.send_audio_form(AUDIO_TRANSLATION_PATH, form).await
+    }
+
+    /// Builds the multipart form for `request`.
+    async fn translation_form(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<AudioForm, AudioTranscriptionError> {
+        let model = self.resolve_model(&request.model);
+        let mut form = multipart::Form::new()
+            .text("model", model.clone())
+            .text("response_format", request.response_format);
+        form = attach_source(form, request.source).await?;
+
+        if let Some(prompt) = request.prompt {
+            form = form.text("prompt", prompt);
+        }
+        if let Some(temperature) = request.temperature {
+            form = form.text("temperature", temperature.to_string());
+        }
+
+        Ok(AudioForm {
+            form,
+            model,
+            timeout: request.timeout,
+        })
+    }
+}
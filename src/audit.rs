@@ -0,0 +1,195 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::chat_completition::{Message, Usage};
+
+/// What happened at one step of an audited conversation.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// A message of the conversation as it was sent to the model.
+    Message { message: Message },
+    /// A response of the model.
+    Response {
+        model: Option<String>,
+        request_id: Option<String>,
+        usage: Option<Usage>,
+        message: Message,
+    },
+    /// A tool call about to be executed.
+    ToolCall {
+        id: String,
+        name: String,
+        arguments: String,
+    },
+    /// The output of a tool call, as sent back to the model.
+    ToolResult {
+        id: String,
+        name: String,
+        content: String,
+    },
+}
+
+/// One structured entry of an audit trail.
+///
+/// Entries serialize to flat JSON objects, e.g.
+/// `{"timestamp_ms": 1700000000000, "iteration": 1, "event": "tool_call", ...}`.
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditEntry {
+    /// Milliseconds since the Unix epoch when the entry was recorded.
+    pub timestamp_ms: u64,
+    /// The model round trip the entry belongs to, starting at 1.
+    pub iteration: usize,
+    #[serde(flatten)]
+    pub event: AuditEvent,
+}
+
+/// Receives the entries of an audit trail, e.g. to append them to a retained log.
+///
+/// Any `Fn(&AuditEntry)` closure is an audit log.
+pub trait AuditLog: Send + Sync {
+    fn record(&self, entry: &AuditEntry);
+}
+
+impl<F> AuditLog for F
+where
+    F: Fn(&AuditEntry) + Send + Sync,
+{
+    fn record(&self, entry: &AuditEntry) {
+        self(entry)
+    }
+}
+
+/// Rewrites entries before they reach the audit logs, e.g. to mask personal data.
+///
+/// Any `Fn(&mut AuditEntry)` closure is a redactor.
+pub trait AuditRedactor: Send + Sync {
+    fn redact(&self, entry: &mut AuditEntry);
+}
+
+impl<F> AuditRedactor for F
+where
+    F: Fn(&mut AuditEntry) + Send + Sync,
+{
+    fn redact(&self, entry: &mut AuditEntry) {
+        self(entry)
+    }
+}
+
+/// Audit logs fed with every step of a conversation, after the redactors ran.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::audit::{AuditEntry, AuditEvent, AuditTrail};
+/// use deepinfra_client_rs::tool_runner::ToolRunner;
+///
+/// let trail = AuditTrail::new()
+///     .redact(|entry: &mut AuditEntry| {
+///         if let AuditEvent::ToolResult { content, .. } = &mut entry.event {
+///             *content = content.replace("4111-1111-1111-1111", "[card]");
+///         }
+///     })
+///     .log(|entry: &AuditEntry| {
+///         eprintln!("{}", serde_json::to_string(entry).unwrap());
+///     });
+///
+/// let runner = ToolRunner::new().audit(trail);
+/// ```
+#[derive(Clone, Default)]
+pub struct AuditTrail {
+    logs: Vec<Arc<dyn AuditLog>>,
+    redactors: Vec<Arc<dyn AuditRedactor>>,
+}
+
+impl AuditTrail {
+    /// Creates a trail without logs, which records nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends entries to `log` as well.
+    pub fn log(mut self, log: impl AuditLog + 'static) -> Self {
+        self.logs.push(Arc::new(log));
+        self
+    }
+
+    /// Appends a redactor, run in order on every entry before it is logged.
+    pub fn redact(mut self, redactor: impl AuditRedactor + 'static) -> Self {
+        self.redactors.push(Arc::new(redactor));
+        self
+    }
+
+    /// Records `event`, building it only if a log will receive it.
+    pub(crate) fn record(&self, iteration: usize, event: impl FnOnce() -> AuditEvent) {
+        if self.logs.is_empty() {
+            return;
+        }
+        let mut entry = AuditEntry {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            iteration,
+            event: event(),
+        };
+        for redactor in &self.redactors {
+            redactor.redact(&mut entry);
+        }
+        for log in &self.logs {
+            log.record(&entry);
+        }
+    }
+}
+
+impl fmt::Debug for AuditTrail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditTrail")
+            .field("logs", &self.logs.len())
+            .field("redactors", &self.redactors.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn redacts_entries_before_logging_them() {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&entries);
+        let trail = AuditTrail::new()
+            .redact(|entry: &mut AuditEntry| {
+                if let AuditEvent::ToolCall { arguments, .. } = &mut entry.event {
+                    *arguments = "[redacted]".to_string();
+                }
+            })
+            .log(move |entry: &AuditEntry| {
+                seen.lock()
+                    .unwrap()
+                    .push(serde_json::to_value(entry).unwrap());
+            });
+
+        trail.record(2, || AuditEvent::ToolCall {
+            id: "call_1".to_string(),
+            name: "lookup".to_string(),
+            arguments: r#"{"ssn": "078-05-1120"}"#.to_string(),
+        });
+
+        let entries = entries.lock().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["event"], "tool_call");
+        assert_eq!(entries[0]["iteration"], 2);
+        assert_eq!(entries[0]["name"], "lookup");
+        assert_eq!(entries[0]["arguments"], "[redacted]");
+    }
+
+    #[test]
+    fn builds_no_entries_without_logs() {
+        AuditTrail::new().record(1, || unreachable!("event built without a log"));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::canonical::fnv1a;
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse,
+};
+use crate::client::DeepinfraClient;
+#[cfg(feature = "rate_limit")]
+use crate::pacing::Pacer;
+use bon::Builder;
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::fs::{self, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tracing::{info, instrument};
+
+/// A single unit of work in a batch.
+#[derive(Clone, Debug)]
+pub struct BatchItem {
+    /// Identifier recorded in the checkpoint file. Must be unique within the batch
+    /// and must not contain line breaks.
+    pub id: String,
+    pub request: ChatCompletionRequest,
+}
+
+/// Options for [`DeepinfraClient::run_batch`].
+#[derive(Clone, Debug, Builder)]
+pub struct BatchOptions {
+    /// File recording the IDs of completed items, one per line. Items listed in it
+    /// are skipped, so rerunning the same batch resumes where it stopped.
+    #[builder(into)]
+    pub checkpoint_path: PathBuf,
+    /// Maximum number of requests in flight.
+    #[builder(default = 8)]
+    pub concurrency: usize,
+    /// Maximum number of requests started per second; must be positive. Requires the
+    /// `rate_limit` feature.
+    #[cfg(feature = "rate_limit")]
+    pub max_requests_per_second: Option<f64>,
+    /// Index of the shard processed by this runner, in `0..shard_count`.
+    #[builder(default = 0)]
+    pub shard_index: u64,
+    /// Number of runners sharing the batch. Items are assigned to shards by hashing
+    /// their ID, so every runner must receive the same items.
+    #[builder(default = 1)]
+    pub shard_count: u64,
+    /// How often progress is logged.
+    #[builder(default = Duration::from_secs(30))]
+    pub progress_interval: Duration,
+}
+
+/// Counts of a finished batch run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchReport {
+    /// Items completed during this run.
+    pub completed: usize,
+    /// Items that failed during this run. They are not checkpointed and will be
+    /// retried on the next run.
+    pub failed: usize,
+    /// Items skipped because they were already checkpointed.
+    pub skipped: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BatchError {
+    #[error("Checkpoint error: {0}")]
+    CheckpointError(#[from] std::io::Error),
+    #[cfg(feature = "rate_limit")]
+    #[error("Invalid rate of {0} requests per second, expected a positive number")]
+    InvalidRate(f64),
+    #[error("Shard index {index} is out of range for {count} shards")]
+    InvalidShard { index: u64, count: u64 },
+}
+
+impl DeepinfraClient {
+    /// Runs a large batch of chat completions with checkpointing.
+    ///
+    /// `on_result` is called with every outcome before the item is checkpointed, so
+    /// results persisted there survive a crash. Failed items are passed to
+    /// `on_result` too but are not checkpointed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::batch::*;
+    /// # async fn run(client: DeepinfraClient, items: Vec<BatchItem>) -> Result<(), BatchError> {
+    /// let options = BatchOptions::builder()
+    ///     .checkpoint_path("batch.checkpoint")
+    ///     .concurrency(16)
+    ///     .build();
+    ///
+    /// let report = client
+    ///     .run_batch(items, &options, |id, result| {
+    ///         if let Ok(response) = result {
+    ///             println!("{id}: {}", response.content().unwrap_or_default());
+    ///         }
+    ///     })
+    ///     .await?;
+    /// println!("{} completed, {} failed", report.completed, report.failed);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, items, on_result))]
+    pub async fn run_batch<I, F>(
+        &self,
+        items: I,
+        options: &BatchOptions,
+        mut on_result: F,
+    ) -> Result<BatchReport, BatchError>
+    where
+        I: IntoIterator<Item = BatchItem>,
+        F: FnMut(&str, &Result<ChatCompletionResponse, ChatCompletionError>),
+    {
+        let shard_count = options.shard_count.max(1);
+        if options.shard_index >= shard_count {
+            return Err(BatchError::InvalidShard {
+                index: options.shard_index,
+                count: options.shard_count,
+            });
+        }
+        #[cfg(feature = "rate_limit")]
+        let pacer = match options.max_requests_per_second {
+            Some(rate) => Some(Pacer::per_second(rate).ok_or(BatchError::InvalidRate(rate))?),
+            None => None,
+        };
+
+        let done = read_checkpoint(options).await?;
+        let mut checkpoint = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&options.checkpoint_path)
+            .await?;
+
+        let mut skipped = 0;
+        let pending: Vec<BatchItem> = items
+            .into_iter()
+            .filter(|item| fnv1a(item.id.as_bytes()) % shard_count == options.shard_index)
+            .filter(|item| {
+                let skip = done.contains(&item.id);
+                skipped += usize::from(skip);
+                !skip
+            })
+            .collect();
+
+        #[cfg(feature = "rate_limit")]
+        let pacer = &pacer;
+        let mut results = stream::iter(pending)
+            .map(|item| async move {
+                #[cfg(feature = "rate_limit")]
+                if let Some(pacer) = pacer {
+                    pacer.until_ready().await;
+                }
+                (item.id, self.chat_completition(item.request).await)
+            })
+            .buffer_unordered(options.concurrency.max(1));
+
+        let mut report = BatchReport {
+            skipped,
+            ..BatchReport::default()
+        };
+        let mut last_progress = Instant::now();
+        while let Some((id, result)) = results.next().await {
+            on_result(&id, &result);
+            match result {
+                Ok(_) => {
+                    checkpoint.write_all(format!("{id}\n").as_bytes()).await?;
+                    checkpoint.flush().await?;
+                    report.completed += 1;
+                }
+                Err(_) => report.failed += 1,
+            }
+
+            if last_progress.elapsed() >= options.progress_interval {
+                info!(
+                    completed = report.completed,
+                    failed = report.failed,
+                    "Batch progress"
+                );
+                last_progress = Instant::now();
+            }
+        }
+
+        info!(
+            completed = report.completed,
+            failed = report.failed,
+            skipped = report.skipped,
+            "Batch finished"
+        );
+        Ok(report)
+    }
+
+    /// Sends chat completions with at most `concurrency` in flight and returns the
+    /// results in the order of `requests`.
+    ///
+    /// Unlike [`run_batch`](Self::run_batch), nothing is checkpointed; use it for
+    /// batches small enough to simply rerun.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # async fn run(client: DeepinfraClient, requests: Vec<ChatCompletionRequest>) {
+    /// let results = client.chat_completion_batch(requests, 8).await;
+    /// for result in results {
+    ///     match result {
+    ///         Ok(response) => println!("{}", response.content().unwrap_or_default()),
+    ///         Err(error) => eprintln!("{error}"),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[instrument(skip(self, requests))]
+    pub async fn chat_completion_batch<I>(
+        &self,
+        requests: I,
+        concurrency: usize,
+    ) -> Vec<Result<ChatCompletionResponse, ChatCompletionError>>
+    where
+        I: IntoIterator<Item = ChatCompletionRequest>,
+    {
+        self.chat_completion_batch_stream(requests, concurrency)
+            .collect()
+            .await
+    }
+
+    /// Like [`chat_completion_batch`](Self::chat_completion_batch), but yields each
+    /// result as soon as it and every result before it are available.
+    pub fn chat_completion_batch_stream<'a, I>(
+        &'a self,
+        requests: I,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<ChatCompletionResponse, ChatCompletionError>> + 'a
+    where
+        I: IntoIterator<Item = ChatCompletionRequest>,
+        I::IntoIter: 'a,
+    {
+        stream::iter(requests)
+            .map(move |request| self.chat_completition(request))
+            .buffered(concurrency.max(1))
+    }
+}
+
+/// IDs already recorded in the checkpoint file, if it exists.
+async fn read_checkpoint(options: &BatchOptions) -> Result<HashSet<String>, std::io::Error> {
+    match fs::read_to_string(&options.checkpoint_path).await {
+        Ok(contents) => Ok(contents.lines().map(str::to_string).collect()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(HashSet::new()),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> DeepinfraClient {
+        DeepinfraClient::builder().token("token").build().unwrap()
+    }
+
+    fn options() -> BatchOptionsBuilder<batch_options_builder::SetCheckpointPath> {
+        let path = std::env::temp_dir().join(format!("batch-{}.checkpoint", std::process::id()));
+        BatchOptions::builder().checkpoint_path(path)
+    }
+
+    #[tokio::test]
+    async fn rejects_an_out_of_range_shard() {
+        let options = options().shard_index(2).shard_count(2).build();
+        let error = client()
+            .run_batch(Vec::new(), &options, |_, _| {})
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            BatchError::InvalidShard { index: 2, count: 2 }
+        ));
+    }
+
+    #[cfg(feature = "rate_limit")]
+    #[tokio::test]
+    async fn rejects_a_non_positive_rate() {
+        for rate in [0.0, -1.0] {
+            let options = options().max_requests_per_second(rate).build();
+            let error = client()
+                .run_batch(Vec::new(), &options, |_, _| {})
+                .await
+                .unwrap_err();
+            assert!(matches!(error, BatchError::InvalidRate(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn skips_checkpointed_items() {
+        let path =
+            std::env::temp_dir().join(format!("batch-skip-{}.checkpoint", std::process::id()));
+        fs::write(&path, "a\nb\n").await.unwrap();
+        let options = BatchOptions::builder().checkpoint_path(&path).build();
+
+        let items = ["a", "b"].map(|id| BatchItem {
+            id: id.to_string(),
+            request: ChatCompletionRequest::builder()
+                .messages(Vec::new())
+                .build(),
+        });
+        let report = client()
+            .run_batch(items, &options, |_, _| {})
+            .await
+            .unwrap();
+        fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(
+            report,
+            BatchReport {
+                completed: 0,
+                failed: 0,
+                skipped: 2
+            }
+        );
+    }
+}
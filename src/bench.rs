@@ -0,0 +1,291 @@
+use crate::chat_completition::{ChatCompletionError, ChatCompletionRequest, Usage};
+use crate::client::DeepinfraClient;
+use crate::experiment::ExperimentArm;
+use crate::judge::{render_conversation, JudgeError};
+use bon::Builder;
+use futures::stream::{self, StreamExt};
+use std::time::Duration;
+use tracing::instrument;
+
+/// Options for [`DeepinfraClient::bench`].
+#[derive(Clone, Debug, Builder)]
+pub struct BenchOptions {
+    /// How often every prompt is sent to every configuration.
+    #[builder(default = 1)]
+    pub repetitions: usize,
+    /// Maximum number of requests in flight, judge requests included.
+    #[builder(default = 4)]
+    pub concurrency: usize,
+    /// Model that scores every answer from 0 to 10. Answers are not scored when unset.
+    #[builder(into)]
+    pub judge_model: Option<String>,
+    /// What the judge should value, e.g. "factual accuracy and brevity".
+    #[builder(into)]
+    pub criteria: Option<String>,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// A successful completion measured during a benchmark.
+#[derive(Debug)]
+pub struct BenchSample {
+    /// Content of the first choice.
+    pub content: String,
+    /// Wall-clock time of the request.
+    pub latency: Duration,
+    pub usage: Option<Usage>,
+    /// The judge's score, or the error that prevented scoring. `None` without a
+    /// judge model.
+    pub score: Option<Result<f64, JudgeError>>,
+}
+
+/// One request of a benchmark.
+#[derive(Debug)]
+pub struct BenchRun {
+    /// Index of the prompt in the prompt set.
+    pub prompt: usize,
+    pub repetition: usize,
+    pub result: Result<BenchSample, ChatCompletionError>,
+}
+
+/// Nearest-rank latency percentiles.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::bench::LatencyPercentiles;
+/// use std::time::Duration;
+///
+/// let latencies: Vec<Duration> = (1..=100).map(Duration::from_millis).collect();
+/// let percentiles = LatencyPercentiles::from_samples(&latencies).unwrap();
+///
+/// assert_eq!(percentiles.p50, Duration::from_millis(50));
+/// assert_eq!(percentiles.p90, Duration::from_millis(90));
+/// assert_eq!(percentiles.max, Duration::from_millis(100));
+/// assert!(LatencyPercentiles::from_samples(&[]).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencyPercentiles {
+    /// Computes the percentiles of `samples`, or `None` when there are none.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let max = *sorted.last()?;
+        let percentile = |p: f64| {
+            let rank = (p * sorted.len() as f64).ceil() as usize;
+            sorted[rank.max(1) - 1]
+        };
+
+        Some(Self {
+            p50: percentile(0.5),
+            p90: percentile(0.9),
+            p99: percentile(0.99),
+            max,
+        })
+    }
+}
+
+/// The measurements of a single configuration.
+#[derive(Debug)]
+pub struct ConfigurationReport {
+    /// Name of the [`ExperimentArm`] under test.
+    pub name: String,
+    /// Every request, ordered by prompt then repetition.
+    pub runs: Vec<BenchRun>,
+    /// Number of requests that failed.
+    pub failures: usize,
+    /// Latency of the successful requests, `None` when all of them failed.
+    pub latency: Option<LatencyPercentiles>,
+    /// Completion tokens generated per second of request latency.
+    pub tokens_per_second: Option<f64>,
+    /// Sum of the costs estimated by DeepInfra, `None` when none were reported.
+    pub cost: Option<f64>,
+    /// Mean judge score of the scored answers.
+    pub mean_score: Option<f64>,
+}
+
+impl ConfigurationReport {
+    fn new(name: String, runs: Vec<BenchRun>) -> Self {
+        let samples: Vec<&BenchSample> = runs
+            .iter()
+            .filter_map(|run| run.result.as_ref().ok())
+            .collect();
+
+        let latencies: Vec<Duration> = samples.iter().map(|sample| sample.latency).collect();
+        let elapsed: Duration = latencies.iter().sum();
+        let tokens: i32 = samples
+            .iter()
+            .filter_map(|sample| sample.usage.as_ref())
+            .map(|usage| usage.completion_tokens)
+            .sum();
+        let cost = samples
+            .iter()
+            .filter_map(|sample| sample.usage.as_ref()?.estimated_cost)
+            .reduce(|a, b| a + b);
+        let scores: Vec<f64> = samples
+            .iter()
+            .filter_map(|sample| sample.score.as_ref()?.as_ref().ok().copied())
+            .collect();
+
+        Self {
+            name,
+            failures: runs.len() - samples.len(),
+            latency: LatencyPercentiles::from_samples(&latencies),
+            tokens_per_second: (!elapsed.is_zero())
+                .then(|| f64::from(tokens) / elapsed.as_secs_f64()),
+            cost,
+            mean_score: (!scores.is_empty())
+                .then(|| scores.iter().sum::<f64>() / scores.len() as f64),
+            runs,
+        }
+    }
+}
+
+/// The outcome of [`DeepinfraClient::bench`], one entry per configuration.
+#[derive(Debug)]
+pub struct BenchReport {
+    /// Reports in the order the configurations were given.
+    pub configurations: Vec<ConfigurationReport>,
+}
+
+impl BenchReport {
+    /// The report of the configuration called `name`.
+    pub fn configuration(&self, name: &str) -> Option<&ConfigurationReport> {
+        self.configurations
+            .iter()
+            .find(|configuration| configuration.name == name)
+    }
+}
+
+impl DeepinfraClient {
+    /// Sends every prompt to every configuration and reports latency percentiles,
+    /// token throughput, cost and, with a judge model, quality scores.
+    ///
+    /// Each configuration's overrides are applied to the prompts as in an
+    /// [`Experiment`](crate::experiment::Experiment). Requests run with at most
+    /// `options.concurrency` in flight, so latencies include any queueing the
+    /// client's rate limits add.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::bench::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # use deepinfra_client_rs::experiment::ExperimentArm;
+    /// # async fn run(client: DeepinfraClient, prompts: Vec<ChatCompletionRequest>) {
+    /// let configurations = [
+    ///     ExperimentArm::builder()
+    ///         .name("llama-70b")
+    ///         .model("meta-llama/Meta-Llama-3.1-70B-Instruct")
+    ///         .build(),
+    ///     ExperimentArm::builder()
+    ///         .name("deepseek")
+    ///         .model("deepseek-ai/DeepSeek-V3")
+    ///         .build(),
+    /// ];
+    /// let options = BenchOptions::builder()
+    ///     .repetitions(3)
+    ///     .judge_model("deepseek-ai/DeepSeek-R1")
+    ///     .build();
+    ///
+    /// let report = client.bench(&prompts, &configurations, &options).await;
+    /// for configuration in &report.configurations {
+    ///     println!(
+    ///         "{}: p50 {:?}, score {:?}",
+    ///         configuration.name,
+    ///         configuration.latency.map(|latency| latency.p50),
+    ///         configuration.mean_score,
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    #[instrument(skip(self, prompts, configurations, options))]
+    pub async fn bench(
+        &self,
+        prompts: &[ChatCompletionRequest],
+        configurations: &[ExperimentArm],
+        options: &BenchOptions,
+    ) -> BenchReport {
+        let runs_per_configuration = prompts.len() * options.repetitions;
+        let jobs = configurations.iter().flat_map(|configuration| {
+            prompts
+                .iter()
+                .enumerate()
+                .flat_map(move |(prompt, request)| {
+                    (0..options.repetitions)
+                        .map(move |repetition| (configuration, prompt, request, repetition))
+                })
+        });
+
+        let mut runs = stream::iter(jobs)
+            .map(|(configuration, prompt, request, repetition)| async move {
+                let mut request = request.clone();
+                configuration.apply(&mut request);
+                BenchRun {
+                    prompt,
+                    repetition,
+                    result: self.bench_sample(request, options).await,
+                }
+            })
+            .buffered(options.concurrency.max(1))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter();
+
+        let configurations = configurations
+            .iter()
+            .map(|configuration| {
+                let runs = runs.by_ref().take(runs_per_configuration).collect();
+                ConfigurationReport::new(configuration.name.clone(), runs)
+            })
+            .collect();
+
+        BenchReport { configurations }
+    }
+
+    async fn bench_sample(
+        &self,
+        request: ChatCompletionRequest,
+        options: &BenchOptions,
+    ) -> Result<BenchSample, ChatCompletionError> {
+        let conversation = options
+            .judge_model
+            .is_some()
+            .then(|| render_conversation(&request.messages));
+        let response = self.chat_completition(request).await?;
+        let content = response.content().unwrap_or_default().to_string();
+
+        let score = match (&options.judge_model, conversation) {
+            (Some(judge_model), Some(conversation)) => Some(
+                self.judge_candidates(
+                    &conversation,
+                    vec![(0, content.clone())],
+                    judge_model,
+                    options.criteria.as_deref(),
+                )
+                .await
+                .map(|candidates| candidates[0].score),
+            ),
+            _ => None,
+        };
+
+        Ok(BenchSample {
+            content,
+            latency: response.metadata.elapsed,
+            usage: response.usage,
+            score,
+        })
+    }
+}
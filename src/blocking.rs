@@ -0,0 +1,573 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::client;
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+use futures::{Stream, StreamExt};
+use tokio::runtime::Runtime;
+
+#[cfg(feature = "audio_transcription")]
+use crate::audio_transcription::{
+    AudioTranscriptionError, AudioTranscriptionRequest, AudioTranscriptionResponse, SubtitleFormat,
+};
+#[cfg(feature = "audio_transcription")]
+use crate::audio_translation::AudioTranslationRequest;
+#[cfg(feature = "chat_completition")]
+use crate::batch::{BatchError, BatchItem, BatchOptions, BatchReport};
+#[cfg(feature = "chat_completition")]
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse, Usage,
+};
+#[cfg(feature = "chat_completition")]
+use crate::chat_stream::{ChatCompletionChunk, StreamMetrics};
+#[cfg(feature = "deploy")]
+use crate::deploy::{DeployError, Deployment, DeploymentRequest};
+use crate::download::{DownloadError, DownloadOptions};
+#[cfg(feature = "image_generation")]
+use crate::image_generation::{
+    ImageGenerationError, ImageGenerationRequest, ImageGenerationResponse,
+};
+#[cfg(feature = "inference")]
+use crate::inference::{InferenceError, InferenceResponse};
+#[cfg(feature = "inference")]
+use crate::jobs::{Job, JobError, JobPollOptions};
+#[cfg(feature = "models")]
+use crate::models::{Model, ModelFilter, ModelsError};
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "image_generation",
+    feature = "inference",
+    feature = "models"
+))]
+use crate::raw::RawResponse;
+#[cfg(feature = "inference")]
+use crate::rerank::{RerankError, RerankRequest, RerankResponse};
+#[cfg(all(feature = "chat_completition", feature = "schemars"))]
+use crate::structured::StructuredOutputError;
+#[cfg(feature = "audio_transcription")]
+use crate::transcription_stream::TranscriptionEvent;
+
+/// A synchronous client for scripts and command line tools without an async
+/// runtime.
+///
+/// The client owns a small Tokio runtime whose worker thread drives connections,
+/// timers and background tasks such as stream read-ahead. Each call polls the async
+/// [`DeepinfraClient`](client::DeepinfraClient) future on the calling thread until it
+/// completes, so calls also work from inside another runtime, where they block that
+/// runtime's thread like any other blocking call. Requests and responses are the same
+/// types, and rate limits, model aliases and timeouts behave exactly as they do for
+/// the async client.
+///
+/// Helpers without a blocking method can be run with [`block_on`](Self::block_on).
+///
+/// # Example
+///
+/// ```no_run
+/// use deepinfra_client_rs::blocking;
+/// use deepinfra_client_rs::chat_completition::*;
+/// use deepinfra_client_rs::client::DeepinfraClient;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let client = DeepinfraClient::builder().token("your_api_token").build()?;
+///     let client = blocking::DeepinfraClient::new(client)?;
+///
+///     let request = ChatCompletionRequest::builder()
+///         .messages(vec![Message::User(UserMessage::builder().content("Hello!").build())])
+///         .build();
+///     let response = client.chat_completition(request)?;
+///     println!("{}", response.content().unwrap_or_default());
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DeepinfraClient {
+    inner: client::DeepinfraClient,
+    driver: Arc<Driver>,
+}
+
+/// The runtime behind a blocking client and its streams.
+#[derive(Debug)]
+struct Driver {
+    runtime: Option<Runtime>,
+}
+
+impl Driver {
+    fn new() -> std::io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("deepinfra-blocking")
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            runtime: Some(runtime),
+        })
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let runtime = self
+            .runtime
+            .as_ref()
+            .expect("runtime is only taken on drop");
+        // Tasks spawned by the future, such as connections, run on the worker thread.
+        let _context = runtime.enter();
+        // The caller may be a task of another runtime whose cooperative budget would
+        // otherwise starve the future.
+        futures::executor::block_on(tokio::task::unconstrained(future))
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        // Waiting for the worker would panic when dropped inside another runtime.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl DeepinfraClient {
+    /// Wraps an async client, starting the runtime that drives its requests.
+    pub fn new(client: client::DeepinfraClient) -> std::io::Result<Self> {
+        Ok(Self {
+            inner: client,
+            driver: Arc::new(Driver::new()?),
+        })
+    }
+
+    /// The async client, for settings and helpers without a blocking equivalent.
+    pub fn inner(&self) -> &client::DeepinfraClient {
+        &self.inner
+    }
+
+    /// Runs `future` to completion on this client's runtime, e.g. an async helper
+    /// of [`inner`](Self::inner) without a blocking method.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::blocking;
+    /// # fn run(client: blocking::DeepinfraClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let bytes = client.block_on(client.inner().download("https://example.com/image.png"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.driver.block_on(future)
+    }
+
+    /// See [`client::DeepinfraClient::chat_completition`].
+    #[cfg(feature = "chat_completition")]
+    pub fn chat_completition(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, ChatCompletionError> {
+        self.block_on(self.inner.chat_completition(request))
+    }
+
+    /// See [`client::DeepinfraClient::chat_completion_raw`].
+    #[cfg(feature = "chat_completition")]
+    pub fn chat_completion_raw(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<RawResponse, ChatCompletionError> {
+        self.block_on(self.inner.chat_completion_raw(request))
+    }
+
+    /// Streams a chat completion, yielding chunks as they arrive.
+    ///
+    /// The runtime keeps reading the response between calls to `next`, so a
+    /// [`StreamBuffer`](crate::chat_stream::StreamBuffer) fills while the caller is
+    /// busy with the previous chunk.
+    #[cfg(feature = "chat_completition")]
+    pub fn chat_completion_stream(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionStream, ChatCompletionError> {
+        let inner = self.block_on(self.inner.chat_completion_stream(request))?;
+        Ok(ChatCompletionStream {
+            inner: BlockingStream::new(inner, &self.driver),
+        })
+    }
+
+    /// See [`client::DeepinfraClient::chat_completion_structured`].
+    #[cfg(all(feature = "chat_completition", feature = "schemars"))]
+    pub fn chat_completion_structured<T>(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<T, StructuredOutputError>
+    where
+        T: serde::de::DeserializeOwned + schemars::JsonSchema,
+    {
+        self.block_on(self.inner.chat_completion_structured(request))
+    }
+
+    /// See [`client::DeepinfraClient::chat_completion_batch`].
+    #[cfg(feature = "chat_completition")]
+    pub fn chat_completion_batch<I>(
+        &self,
+        requests: I,
+        concurrency: usize,
+    ) -> Vec<Result<ChatCompletionResponse, ChatCompletionError>>
+    where
+        I: IntoIterator<Item = ChatCompletionRequest>,
+    {
+        self.block_on(self.inner.chat_completion_batch(requests, concurrency))
+    }
+
+    /// See [`client::DeepinfraClient::run_batch`].
+    #[cfg(feature = "chat_completition")]
+    pub fn run_batch<I, F>(
+        &self,
+        items: I,
+        options: &BatchOptions,
+        on_result: F,
+    ) -> Result<BatchReport, BatchError>
+    where
+        I: IntoIterator<Item = BatchItem>,
+        F: FnMut(&str, &Result<ChatCompletionResponse, ChatCompletionError>),
+    {
+        self.block_on(self.inner.run_batch(items, options, on_result))
+    }
+
+    /// See [`client::DeepinfraClient::audio_transcription`].
+    #[cfg(feature = "audio_transcription")]
+    pub fn audio_transcription(
+        &self,
+        request: AudioTranscriptionRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
+        self.block_on(self.inner.audio_transcription(request))
+    }
+
+    /// See [`client::DeepinfraClient::audio_transcription_raw`].
+    #[cfg(feature = "audio_transcription")]
+    pub fn audio_transcription_raw(
+        &self,
+        request: AudioTranscriptionRequest,
+    ) -> Result<RawResponse, AudioTranscriptionError> {
+        self.block_on(self.inner.audio_transcription_raw(request))
+    }
+
+    /// See [`client::DeepinfraClient::audio_transcription_subtitles`].
+    #[cfg(feature = "audio_transcription")]
+    pub fn audio_transcription_subtitles(
+        &self,
+        request: AudioTranscriptionRequest,
+        format: SubtitleFormat,
+    ) -> Result<String, AudioTranscriptionError> {
+        self.block_on(self.inner.audio_transcription_subtitles(request, format))
+    }
+
+    /// Transcribes an audio file, yielding transcript events as they arrive.
+    #[cfg(feature = "audio_transcription")]
+    pub fn audio_transcription_stream(
+        &self,
+        request: AudioTranscriptionRequest,
+    ) -> Result<TranscriptionStream, AudioTranscriptionError> {
+        let inner = self.block_on(self.inner.audio_transcription_stream(request))?;
+        Ok(TranscriptionStream {
+            inner: BlockingStream::new(inner, &self.driver),
+        })
+    }
+
+    /// See [`client::DeepinfraClient::audio_translation`].
+    #[cfg(feature = "audio_transcription")]
+    pub fn audio_translation(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<AudioTranscriptionResponse, AudioTranscriptionError> {
+        self.block_on(self.inner.audio_translation(request))
+    }
+
+    /// See [`client::DeepinfraClient::audio_translation_raw`].
+    #[cfg(feature = "audio_transcription")]
+    pub fn audio_translation_raw(
+        &self,
+        request: AudioTranslationRequest,
+    ) -> Result<RawResponse, AudioTranscriptionError> {
+        self.block_on(self.inner.audio_translation_raw(request))
+    }
+
+    /// See [`client::DeepinfraClient::image_generation`].
+    #[cfg(feature = "image_generation")]
+    pub fn image_generation(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, ImageGenerationError> {
+        self.block_on(self.inner.image_generation(request))
+    }
+
+    /// See [`client::DeepinfraClient::image_generation_raw`].
+    #[cfg(feature = "image_generation")]
+    pub fn image_generation_raw(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<RawResponse, ImageGenerationError> {
+        self.block_on(self.inner.image_generation_raw(request))
+    }
+
+    /// See [`client::DeepinfraClient::inference`].
+    #[cfg(feature = "inference")]
+    pub fn inference<I, O>(
+        &self,
+        model: &str,
+        input: &I,
+    ) -> Result<InferenceResponse<O>, InferenceError>
+    where
+        I: serde::Serialize,
+        O: serde::de::DeserializeOwned,
+    {
+        self.block_on(self.inner.inference(model, input))
+    }
+
+    /// See [`client::DeepinfraClient::inference_raw`].
+    #[cfg(feature = "inference")]
+    pub fn inference_raw<I: serde::Serialize>(
+        &self,
+        model: &str,
+        input: &I,
+    ) -> Result<RawResponse, InferenceError> {
+        self.block_on(self.inner.inference_raw(model, input))
+    }
+
+    /// See [`client::DeepinfraClient::rerank`].
+    #[cfg(feature = "inference")]
+    pub fn rerank(&self, request: RerankRequest) -> Result<RerankResponse, RerankError> {
+        self.block_on(self.inner.rerank(request))
+    }
+
+    /// See [`client::DeepinfraClient::submit_job`].
+    #[cfg(feature = "inference")]
+    pub fn submit_job<I: serde::Serialize>(
+        &self,
+        model: &str,
+        input: &I,
+        webhook: Option<&str>,
+    ) -> Result<Job, JobError> {
+        self.block_on(self.inner.submit_job(model, input, webhook))
+    }
+
+    /// See [`client::DeepinfraClient::poll_job`].
+    #[cfg(feature = "inference")]
+    pub fn poll_job(&self, job: &mut Job) -> Result<InferenceResponse, JobError> {
+        self.block_on(self.inner.poll_job(job))
+    }
+
+    /// See [`client::DeepinfraClient::wait_for_job`].
+    #[cfg(feature = "inference")]
+    pub fn wait_for_job<O: serde::de::DeserializeOwned>(
+        &self,
+        job: &mut Job,
+        options: &JobPollOptions,
+    ) -> Result<InferenceResponse<O>, JobError> {
+        self.block_on(self.inner.wait_for_job(job, options))
+    }
+
+    /// See [`client::DeepinfraClient::list_models`].
+    #[cfg(feature = "models")]
+    pub fn list_models(&self) -> Result<Vec<Model>, ModelsError> {
+        self.block_on(self.inner.list_models())
+    }
+
+    /// See [`client::DeepinfraClient::list_models_raw`].
+    #[cfg(feature = "models")]
+    pub fn list_models_raw(&self) -> Result<RawResponse, ModelsError> {
+        self.block_on(self.inner.list_models_raw())
+    }
+
+    /// See [`client::DeepinfraClient::find_models`].
+    #[cfg(feature = "models")]
+    pub fn find_models(&self, filter: &ModelFilter) -> Result<Vec<Model>, ModelsError> {
+        self.block_on(self.inner.find_models(filter))
+    }
+
+    /// See [`client::DeepinfraClient::model`].
+    #[cfg(feature = "models")]
+    pub fn model(&self, id: &str) -> Result<Model, ModelsError> {
+        self.block_on(self.inner.model(id))
+    }
+
+    /// See [`client::DeepinfraClient::create_deployment`].
+    #[cfg(feature = "deploy")]
+    pub fn create_deployment(&self, request: DeploymentRequest) -> Result<String, DeployError> {
+        self.block_on(self.inner.create_deployment(request))
+    }
+
+    /// See [`client::DeepinfraClient::list_deployments`].
+    #[cfg(feature = "deploy")]
+    pub fn list_deployments(&self) -> Result<Vec<Deployment>, DeployError> {
+        self.block_on(self.inner.list_deployments())
+    }
+
+    /// See [`client::DeepinfraClient::deployment`].
+    #[cfg(feature = "deploy")]
+    pub fn deployment(&self, deploy_id: &str) -> Result<Deployment, DeployError> {
+        self.block_on(self.inner.deployment(deploy_id))
+    }
+
+    /// See [`client::DeepinfraClient::delete_deployment`].
+    #[cfg(feature = "deploy")]
+    pub fn delete_deployment(&self, deploy_id: &str) -> Result<(), DeployError> {
+        self.block_on(self.inner.delete_deployment(deploy_id))
+    }
+
+    /// See [`client::DeepinfraClient::download_with`].
+    pub fn download(&self, url: &str, options: &DownloadOptions) -> Result<Vec<u8>, DownloadError> {
+        self.block_on(self.inner.download_with(url, options))
+    }
+
+    /// See [`client::DeepinfraClient::download_to`].
+    pub fn download_to(
+        &self,
+        url: &str,
+        options: &DownloadOptions,
+        writer: &mut (impl std::io::Write + Send),
+    ) -> Result<u64, DownloadError> {
+        self.block_on(self.inner.download_to(url, options, writer))
+    }
+}
+
+/// An async stream read by blocking on the runtime that created it.
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+struct BlockingStream<S> {
+    inner: S,
+    driver: Arc<Driver>,
+}
+
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+impl<S: Stream + Unpin> BlockingStream<S> {
+    fn new(inner: S, driver: &Arc<Driver>) -> Self {
+        Self {
+            inner,
+            driver: Arc::clone(driver),
+        }
+    }
+
+    fn next(&mut self) -> Option<S::Item> {
+        self.driver.block_on(self.inner.next())
+    }
+}
+
+/// A streamed chat completion read by iterating over its chunks.
+#[cfg(feature = "chat_completition")]
+pub struct ChatCompletionStream {
+    inner: BlockingStream<crate::chat_stream::ChatCompletionStream>,
+}
+
+#[cfg(feature = "chat_completition")]
+impl ChatCompletionStream {
+    /// See [`crate::chat_stream::ChatCompletionStream::metrics`].
+    pub fn metrics(&self) -> StreamMetrics {
+        self.inner.inner.metrics()
+    }
+
+    /// See [`crate::chat_stream::ChatCompletionStream::usage`].
+    pub fn usage(&self) -> Option<&Usage> {
+        self.inner.inner.usage()
+    }
+
+    /// See [`crate::chat_stream::ChatCompletionStream::cancel`].
+    pub fn cancel(self) {
+        let BlockingStream { inner, driver } = self.inner;
+        driver.block_on(inner.cancel())
+    }
+
+    /// Reads the rest of the stream and assembles it into a complete response.
+    pub fn into_response(self) -> Result<ChatCompletionResponse, ChatCompletionError> {
+        let BlockingStream { inner, driver } = self.inner;
+        driver.block_on(inner.into_response())
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl Iterator for ChatCompletionStream {
+    type Item = Result<ChatCompletionChunk, ChatCompletionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// A streamed transcription read by iterating over its events.
+#[cfg(feature = "audio_transcription")]
+pub struct TranscriptionStream {
+    inner: BlockingStream<crate::transcription_stream::TranscriptionStream>,
+}
+
+#[cfg(feature = "audio_transcription")]
+impl Iterator for TranscriptionStream {
+    type Item = Result<TranscriptionEvent, AudioTranscriptionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    /// Serves `body` once over HTTP and returns the server's URL.
+    fn serve_once(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut connection, _) = listener.accept().unwrap();
+            let mut request = [0; 4096];
+            let _ = connection.read(&mut request).unwrap();
+            write!(
+                connection,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        format!("http://{address}")
+    }
+
+    fn client(base_url: &str) -> DeepinfraClient {
+        let client = client::DeepinfraClient::builder()
+            .token("token")
+            .base_url(base_url)
+            .build()
+            .unwrap();
+        DeepinfraClient::new(client).unwrap()
+    }
+
+    #[test]
+    fn sends_requests_without_a_runtime() {
+        let url = serve_once("hello");
+        let client = client(&url);
+        let body = client
+            .download(&format!("{url}/asset"), &DownloadOptions::default())
+            .unwrap();
+        assert_eq!(body, b"hello");
+    }
+
+    #[tokio::test]
+    async fn sends_requests_and_drops_inside_another_runtime() {
+        let url = serve_once("hello");
+        let client = client(&url);
+        let body = client
+            .download(&format!("{url}/asset"), &DownloadOptions::default())
+            .unwrap();
+        assert_eq!(body, b"hello");
+        drop(client);
+    }
+
+    #[test]
+    fn spawned_tasks_progress_between_calls() {
+        let client = client(client::DEFAULT_BASE_URL);
+        let (sender, receiver) = mpsc::channel();
+        client.block_on(async move {
+            tokio::spawn(async move { sender.send(()).unwrap() });
+        });
+        receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("spawned task did not run");
+    }
+}
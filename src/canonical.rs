@@ -0,0 +1,61 @@
+use crate::chat_completition::ChatCompletionRequest;
+
+/// Request fields that do not influence the generated output.
+const IGNORED_FIELDS: &[&str] = &["stream", "stream_options", "user"];
+
+impl ChatCompletionRequest {
+    /// Serializes the request into a stable canonical form.
+    ///
+    /// Object keys are sorted, unset optional fields are dropped and fields that do
+    /// not influence the output (`stream`, `stream_options`, `user`) are ignored, so two requests with
+    /// the same canonical form are expected to produce equivalent completions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deepinfra_client_rs::chat_completition::*;
+    ///
+    /// let messages = vec![Message::User(UserMessage::builder().content("Hi").build())];
+    /// let a = ChatCompletionRequest::builder().messages(messages.clone()).build();
+    /// let b = ChatCompletionRequest::builder()
+    ///     .messages(messages)
+    ///     .user("someone".to_string())
+    ///     .build();
+    ///
+    /// assert_eq!(a.canonical_json(), b.canonical_json());
+    /// assert_eq!(a.canonical_hash(), b.canonical_hash());
+    /// ```
+    pub fn canonical_json(&self) -> String {
+        self.canonical_value().to_string()
+    }
+
+    /// The canonical form as a JSON value.
+    pub(crate) fn canonical_value(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = value.as_object_mut() {
+            fields.retain(|key, value| !value.is_null() && !IGNORED_FIELDS.contains(&&**key));
+        }
+        // Key order is not guaranteed if `serde_json/preserve_order` is enabled.
+        value.sort_all_objects();
+        value
+    }
+
+    /// A 64-bit hash of [`canonical_json`](Self::canonical_json), stable across
+    /// processes and crate builds.
+    pub fn canonical_hash(&self) -> u64 {
+        fnv1a(self.canonical_json().as_bytes())
+    }
+
+    /// The canonical hash as a 16 character hex string, suitable as a cache,
+    /// deduplication or idempotency key.
+    pub fn canonical_key(&self) -> String {
+        format!("{:016x}", self.canonical_hash())
+    }
+}
+
+/// 64-bit FNV-1a, used because it is stable across processes and Rust versions.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
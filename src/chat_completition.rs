@@ -1,12 +1,28 @@
-use crate::client::DeepinfraClient;
+use crate::api_error::{read_json, ApiError, DecodeError};
+use crate::chat_stream::{BufferOverflowError, StreamBuffer};
+use crate::client::{with_timeout, DeepinfraClient};
+use crate::end_user::EndUser;
+use crate::endpoints::Endpoint;
+use crate::metadata::ResponseMetadata;
+use crate::post_processing::PostProcessError;
+use crate::raw::RawResponse;
+use crate::shutdown::ShutdownError;
+use base64::Engine;
 use bon::Builder;
+use reqwest::RequestBuilder;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json;
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::instrument;
 
-const CHAT_COMPLETIONS_API_URL: &str = "https://api.deepinfra.com/v1/openai/chat/completions";
+pub(crate) const CHAT_COMPLETIONS_PATH: &str = Endpoint::ChatCompletions.path();
 
-#[derive(Debug, Deserialize, Serialize, Builder)]
+#[derive(Clone, Debug, Deserialize, Serialize, Builder)]
 pub struct SystemMessage {
     #[builder(into)]
     content: String,
@@ -14,30 +30,202 @@ pub struct SystemMessage {
     name: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Builder)]
+#[derive(Clone, Debug, Deserialize, Serialize, Builder)]
 pub struct UserMessage {
     #[builder(into)]
-    content: String,
+    content: MessageContent,
     #[builder(into)]
     name: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Builder)]
+#[derive(Clone, Debug, Deserialize, Serialize, Builder)]
 pub struct AssistantMessage {
+    /// Empty when the model refused or only called tools.
+    #[serde(default, deserialize_with = "null_as_default")]
     #[builder(into)]
     pub content: String,
     name: Option<String>,
     tool_calls: Option<Vec<ToolCall>>,
+    /// The model's explanation when it declines to answer, sent instead of content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(into)]
+    refusal: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Builder)]
+impl AssistantMessage {
+    /// The tool calls requested by the model, if any.
+    pub fn tool_calls(&self) -> &[ToolCall] {
+        self.tool_calls.as_deref().unwrap_or_default()
+    }
+
+    /// The refusal message, if the model declined to answer.
+    pub fn refusal(&self) -> Option<&str> {
+        self.refusal.as_deref()
+    }
+}
+
+/// Deserializes `null` as the default value, for fields the API may send as null.
+fn null_as_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Builder)]
 pub struct ToolMessage {
     #[builder(into)]
     content: String,
     tool_call_id: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// The content of a user message: plain text, or a list of parts mixing text and
+/// images for vision models.
+///
+/// # Example
+///
+/// ```no_run
+/// use deepinfra_client_rs::chat_completition::*;
+///
+/// let message = UserMessage::builder()
+///     .content(vec![
+///         ContentPart::text("What is in this picture?"),
+///         ContentPart::image_file("photo.jpg")?,
+///     ])
+///     .build();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+impl MessageContent {
+    /// Returns the text of the content, joining the text parts with newlines.
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            MessageContent::Text(text) => Cow::Borrowed(text),
+            MessageContent::Parts(parts) => {
+                let texts: Vec<&str> = parts
+                    .iter()
+                    .filter_map(|part| match part {
+                        ContentPart::Text { text } => Some(text.as_str()),
+                        ContentPart::ImageUrl { .. } => None,
+                    })
+                    .collect();
+                match texts.as_slice() {
+                    [text] => Cow::Borrowed(text),
+                    texts => Cow::Owned(texts.join("\n")),
+                }
+            }
+        }
+    }
+
+    /// Returns the text of plain text content, or `None` for multi-part content.
+    pub(crate) fn text_mut(&mut self) -> Option<&mut String> {
+        match self {
+            MessageContent::Text(text) => Some(text),
+            MessageContent::Parts(_) => None,
+        }
+    }
+}
+
+impl From<String> for MessageContent {
+    fn from(text: String) -> Self {
+        MessageContent::Text(text)
+    }
+}
+
+impl From<&str> for MessageContent {
+    fn from(text: &str) -> Self {
+        MessageContent::Text(text.to_string())
+    }
+}
+
+impl From<Vec<ContentPart>> for MessageContent {
+    fn from(parts: Vec<ContentPart>) -> Self {
+        MessageContent::Parts(parts)
+    }
+}
+
+/// A part of a multi-part [`MessageContent`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+impl ContentPart {
+    /// A text part.
+    pub fn text(text: impl Into<String>) -> Self {
+        ContentPart::Text { text: text.into() }
+    }
+
+    /// An image part referencing a hosted image or a `data:` URI.
+    pub fn image_url(url: impl Into<String>) -> Self {
+        ContentPart::ImageUrl {
+            image_url: ImageUrl {
+                url: url.into(),
+                detail: None,
+            },
+        }
+    }
+
+    /// An image part embedding `bytes` as a base64 data URI.
+    pub fn image_bytes(bytes: &[u8], mime_type: &str) -> Self {
+        let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+        Self::image_url(format!("data:{mime_type};base64,{data}"))
+    }
+
+    /// An image part embedding the file at `path`.
+    ///
+    /// The MIME type is detected from the content, then guessed from the file
+    /// extension, and defaults to `image/png`. With the `image` feature,
+    /// `image_file_resized` also scales large photos down.
+    pub fn image_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        if let Some(mime_type) = crate::data_url::image_mime_type(&bytes) {
+            return Ok(Self::image_bytes(&bytes, mime_type));
+        }
+        let mime_type = match path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("jpg" | "jpeg") => "image/jpeg",
+            Some("gif") => "image/gif",
+            Some("webp") => "image/webp",
+            _ => "image/png",
+        };
+        Ok(Self::image_bytes(&bytes, mime_type))
+    }
+}
+
+/// The image of a [`ContentPart::ImageUrl`] part.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ImageUrl {
+    /// A URL or a base64 `data:` URI.
+    pub url: String,
+    /// Resolution at which the model looks at the image, for models that support it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<ImageDetail>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageDetail {
+    Auto,
+    Low,
+    High,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(tag = "role", rename_all = "snake_case")]
 pub enum Message {
     System(SystemMessage),
@@ -46,193 +234,1215 @@ pub enum Message {
     Tool(ToolMessage),
 }
 
+impl Message {
+    /// Returns the text content of the message, without any images.
+    pub fn content(&self) -> Cow<'_, str> {
+        match self {
+            Message::System(message) => Cow::Borrowed(&message.content),
+            Message::User(message) => message.content.text(),
+            Message::Assistant(message) => Cow::Borrowed(&message.content),
+            Message::Tool(message) => Cow::Borrowed(&message.content),
+        }
+    }
+
+    /// Returns the text content for editing, or `None` for multi-part content.
+    pub(crate) fn content_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Message::System(message) => Some(&mut message.content),
+            Message::User(message) => message.content.text_mut(),
+            Message::Assistant(message) => Some(&mut message.content),
+            Message::Tool(message) => Some(&mut message.content),
+        }
+    }
+}
+
 /// Represents a request for generating chat completions.
 /// Includes all parameters as per the OpenAPI schema.
-#[derive(Debug, Serialize, Deserialize, Builder)]
+///
+/// Deserializing applies the same defaults as the builder, so a configuration file
+/// only needs the parameters it overrides.
+#[derive(Clone, Debug, Serialize, Deserialize, Builder)]
+#[serde(deny_unknown_fields)]
 pub struct ChatCompletionRequest {
+    /// Whether the chat template should open a new assistant turn after the
+    /// messages. Must be disabled when continuing the final message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    add_generation_prompt: Option<bool>,
+
+    /// Whether the model continues a trailing assistant message instead of starting
+    /// a new one. Supported by vLLM-based models.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[builder(default = false)]
+    continue_final_message: bool,
+
     /// Penalizes new tokens based on their frequency in the text so far.
     /// Increases the model's likelihood to talk about new topics.
     /// Range: -2 to 2
+    #[serde(default)]
     #[builder(default = 0.0)]
     frequency_penalty: f64,
 
     /// Maximum number of tokens to generate in the chat completion.
     /// Total length is limited by the model's context length.
-    #[builder(default = 100000)]
-    max_tokens: u32,
+    #[serde(default = "default_max_tokens")]
+    #[builder(default = default_max_tokens())]
+    pub(crate) max_tokens: u32,
 
     /// Conversation messages including user, assistant, and system messages.
     /// Must include one system message anywhere.
-    messages: Vec<Message>,
+    /// May be omitted when deserializing parameters from a configuration file.
+    #[serde(default)]
+    pub(crate) messages: Vec<Message>,
+
+    /// Whether to return the log probability of each output token.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[builder(default = false)]
+    logprobs: bool,
+
+    /// Number of most likely alternatives to return for each output token, between
+    /// 0 and 20. Requires `logprobs`.
+    top_logprobs: Option<u32>,
 
     /// Minimum probability for a token to be considered, relative to the most likely token.
     /// Must be between 0 and 1. Set to 0 to disable.
+    #[serde(default)]
     #[builder(default = 0.0)]
     min_p: f64,
 
     /// Model name to use for the chat completion.
     /// Example: "meta-llama/Llama-2-70b-chat-hf"
-    #[builder(default = "deepseek-ai/DeepSeek-V3".to_string())]
-    model: String,
+    #[serde(default = "default_model")]
+    #[builder(default = default_model())]
+    pub(crate) model: String,
 
     /// Number of sequences to return.
     /// Minimum: 1, Maximum: 4
-    #[builder(default = 1)]
-    n: u32,
+    #[serde(default = "default_n")]
+    #[builder(default = default_n())]
+    pub(crate) n: u32,
 
     /// Penalizes new tokens based on whether they appear in the text so far.
     /// Increases the model's likelihood to talk about new topics.
     /// Range: -2 to 2
+    #[serde(default)]
     #[builder(default = 0.0)]
     presence_penalty: f64,
 
     /// Penalty for repetition. Values >1 penalize, <1 encourage repetition.
     /// Range: 0.01 to 5
+    #[serde(default = "default_one")]
     #[builder(default = 1.0)]
     repetition_penalty: f64,
 
-    /// The format of the response. Currently, only "text" or "json_object" are supported.
-    response_format: Option<ResponseFormat>,
+    /// The format of the response: "text", "json_object" or "json_schema".
+    pub(crate) response_format: Option<ResponseFormat>,
+
+    /// How much effort reasoning models should spend thinking before answering.
+    /// Checked against [`ReasoningSupport`] before sending.
+    reasoning_effort: Option<ReasoningEffort>,
+
+    /// Maximum number of tokens reasoning models spend thinking. Reasoning counts
+    /// towards `max_tokens`, so the budget must be smaller to leave room for the
+    /// answer. Checked against [`ReasoningSupport`] before sending.
+    ///
+    /// Sent as the `max_reasoning_tokens` field of the OpenAI-compatible request
+    /// body, which DeepInfra applies as the thinking budget of the Qwen3 hybrid
+    /// models and QwQ. Models without a budget reject it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_reasoning_tokens: Option<u32>,
 
     /// Seed for the random number generator.
     /// If not provided, a random seed is used. Determinism is not guaranteed.
-    seed: Option<u64>,
+    pub(crate) seed: Option<u64>,
 
     /// Up to 16 sequences where the API will stop generating further tokens.
-    stop: Option<Vec<String>>,
+    #[builder(into)]
+    pub(crate) stop: Option<StopSequences>,
+
+    /// Also enforces `stop` on the client when streaming: content is cut at the first
+    /// stop sequence and the stream ends once every choice has stopped, for backends
+    /// that apply `stop` only loosely in streaming mode. Not sent to the API.
+    #[serde(skip)]
+    #[builder(default = false)]
+    pub(crate) enforce_stop: bool,
 
     /// Whether to stream the output via SSE or return the full response.
+    /// Set automatically by `chat_completion_stream`.
+    #[serde(default)]
     #[builder(default = false)]
-    stream: bool,
+    pub(crate) stream: bool,
+
+    /// Options for streamed responses. `chat_completion_stream` requests usage by
+    /// default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) stream_options: Option<StreamOptions>,
+
+    /// How many chunks a stream reads ahead of its consumer. Not sent to the API.
+    #[serde(skip)]
+    pub(crate) stream_buffer: Option<StreamBuffer>,
 
     /// Sampling temperature to use, between 0 and 2.
     /// Higher values make the output more random.
+    #[serde(default = "default_one")]
     #[builder(default = 1.0)]
-    temperature: f64,
+    pub(crate) temperature: f64,
+
+    /// Overrides the client's timeout for this request. Not sent to the API.
+    #[serde(skip)]
+    pub(crate) timeout: Option<Duration>,
 
     /// Controls which (if any) function is called by the model.
-    /// "none" means the model will not call a function.
-    /// "auto" means the model can choose to call a function or not.
-    tool_choice: Option<String>,
+    tool_choice: Option<ToolChoice>,
 
     /// A list of tools the model may call. Currently, only functions are supported.
     tools: Option<Vec<ChatTool>>,
 
     /// Sample from the top_k number of tokens. 0 means off.
+    #[serde(default)]
     #[builder(default = 0)]
     top_k: u32,
 
     /// Nucleus sampling parameter between 0 and 1.
     /// The model considers tokens with top_p probability mass.
+    #[serde(default = "default_one")]
     #[builder(default = 1.0)]
     top_p: f64,
 
     /// A unique identifier representing your end-user.
-    /// Helps monitor and detect abuse. Avoid sending identifying information.
-    user: Option<String>,
+    /// Helps monitor and detect abuse. Avoid sending identifying information;
+    /// [`UserHasher`](crate::end_user::UserHasher) derives a pseudonymous one.
+    #[builder(into)]
+    user: Option<EndUser>,
+}
+
+impl ChatCompletionRequest {
+    /// Prefills the reply: the model continues `prefix` instead of starting its
+    /// answer from scratch, e.g. to force JSON output to start with `{`.
+    ///
+    /// The response contains only the continuation, without `prefix`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deepinfra_client_rs::chat_completition::*;
+    ///
+    /// let request = ChatCompletionRequest::builder()
+    ///     .messages(vec![Message::User(
+    ///         UserMessage::builder().content("List three colors as JSON.").build(),
+    ///     )])
+    ///     .build()
+    ///     .prefill("{\"colors\": [");
+    ///
+    /// let json = serde_json::to_value(&request)?;
+    /// assert_eq!(json["messages"][1]["role"], "assistant");
+    /// assert_eq!(json["continue_final_message"], true);
+    /// assert_eq!(json["add_generation_prompt"], false);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn prefill(mut self, prefix: impl Into<String>) -> Self {
+        let prefill = AssistantMessage::builder().content(prefix).build();
+        self.messages.push(Message::Assistant(prefill));
+        self.continue_final_message = true;
+        self.add_generation_prompt = Some(false);
+        self
+    }
+
+    /// Rejects parameters the API is known to refuse, before sending the request.
+    /// `model` is the resolved model the request is sent to.
+    pub(crate) fn validate(&self, model: &str) -> Result<()> {
+        if let Some(stop) = &self.stop {
+            if stop.len() > MAX_STOP_SEQUENCES {
+                return Err(ChatCompletionError::TooManyStopSequences(stop.len()));
+            }
+        }
+        if let Some(budget) = self.max_reasoning_tokens {
+            if budget == 0 || budget >= self.max_tokens {
+                return Err(ChatCompletionError::InvalidReasoningBudget {
+                    budget,
+                    max_tokens: self.max_tokens,
+                });
+            }
+        }
+
+        let Some(support) = ReasoningSupport::of(model) else {
+            return Ok(());
+        };
+        let unsupported = if self.reasoning_effort.is_some() && !support.effort {
+            Some("reasoning_effort")
+        } else if self.max_reasoning_tokens.is_some() && !support.token_budget {
+            Some("max_reasoning_tokens")
+        } else {
+            None
+        };
+        match unsupported {
+            Some(parameter) => Err(ChatCompletionError::UnsupportedReasoningParameter {
+                model: model.to_string(),
+                parameter,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Options for streamed chat completions.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Builder)]
+pub struct StreamOptions {
+    /// Whether a last chunk without choices reports the usage of the request.
+    #[serde(default)]
+    #[builder(default)]
+    pub include_usage: bool,
+}
+
+/// The maximum number of stop sequences accepted by the API.
+pub const MAX_STOP_SEQUENCES: usize = 16;
+
+/// One or more sequences where the model stops generating.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::*;
+///
+/// let request = ChatCompletionRequest::builder()
+///     .messages(Vec::new())
+///     .stop("\n\n")
+///     .build();
+/// let json = serde_json::to_value(&request)?;
+/// assert_eq!(json["stop"], "\n\n");
+///
+/// let stop = StopSequences::from(["END", "STOP"]);
+/// assert_eq!(serde_json::to_value(&stop)?, serde_json::json!(["END", "STOP"]));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StopSequences {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl StopSequences {
+    /// The number of sequences.
+    pub fn len(&self) -> usize {
+        match self {
+            StopSequences::Single(_) => 1,
+            StopSequences::Multiple(sequences) => sequences.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The sequences, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        let sequences = match self {
+            StopSequences::Single(sequence) => std::slice::from_ref(sequence),
+            StopSequences::Multiple(sequences) => sequences.as_slice(),
+        };
+        sequences.iter().map(String::as_str)
+    }
+}
+
+impl From<String> for StopSequences {
+    fn from(sequence: String) -> Self {
+        StopSequences::Single(sequence)
+    }
+}
+
+impl From<&str> for StopSequences {
+    fn from(sequence: &str) -> Self {
+        StopSequences::Single(sequence.to_string())
+    }
+}
+
+impl From<Vec<String>> for StopSequences {
+    fn from(sequences: Vec<String>) -> Self {
+        StopSequences::Multiple(sequences)
+    }
+}
+
+impl<const N: usize> From<[&str; N]> for StopSequences {
+    fn from(sequences: [&str; N]) -> Self {
+        StopSequences::Multiple(sequences.map(str::to_string).into())
+    }
 }
 
 /// Represents a tool that the model may call during chat completion.
 /// Currently supports functions as tools.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Builder)]
 pub struct ChatTool {
     /// Type of the tool. Defaults to "function".
     #[serde(default = "default_tool_type", rename = "type")]
+    #[builder(default = default_tool_type(), into)]
     type_: String,
 
     /// The function definition of the tool.
     function: FunctionDefinition,
 }
 
+impl ChatTool {
+    /// Wraps a function definition into a tool.
+    pub fn function(function: FunctionDefinition) -> Self {
+        ChatTool::builder().function(function).build()
+    }
+
+    /// The function the tool calls.
+    pub fn definition(&self) -> &FunctionDefinition {
+        &self.function
+    }
+
+    /// Builds a function tool whose parameters are the JSON schema of `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deepinfra_client_rs::chat_completition::ChatTool;
+    /// use schemars::JsonSchema;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, JsonSchema)]
+    /// struct WeatherArgs {
+    ///     /// City name, e.g. "Rome".
+    ///     city: String,
+    /// }
+    ///
+    /// let tool = ChatTool::from_schema::<WeatherArgs>("get_weather", "Current weather in a city");
+    /// ```
+    #[cfg(feature = "schemars")]
+    pub fn from_schema<T: schemars::JsonSchema>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        ChatTool::function(FunctionDefinition::new(
+            name,
+            description,
+            ToolParameters::from_schema::<T>(),
+        ))
+    }
+}
+
+impl From<FunctionDefinition> for ChatTool {
+    fn from(function: FunctionDefinition) -> Self {
+        ChatTool::function(function)
+    }
+}
+
+fn default_max_tokens() -> u32 {
+    100000
+}
+
+fn default_model() -> String {
+    "deepseek-ai/DeepSeek-V3".to_string()
+}
+
+fn default_n() -> u32 {
+    1
+}
+
+fn default_one() -> f64 {
+    1.0
+}
+
 fn default_tool_type() -> String {
     "function".to_string()
 }
 
 /// Definition of a function that can be called as a tool.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, Builder)]
 pub struct FunctionDefinition {
     /// The name of the function.
+    #[builder(into)]
     name: String,
 
     /// A description of what the function does.
+    #[builder(into)]
     description: String,
 
     /// Parameters for the function.
-    parameters: serde_json::Value,
+    #[builder(into)]
+    parameters: ToolParameters,
+
+    /// Whether the model must produce arguments that match `parameters` exactly.
+    /// Only honoured by models that support strict function calling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    strict: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+impl FunctionDefinition {
+    /// Defines a function from its name, description and parameter schema.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deepinfra_client_rs::chat_completition::*;
+    ///
+    /// let tool: ChatTool = FunctionDefinition::new(
+    ///     "get_weather",
+    ///     "Returns the current weather in a city",
+    ///     serde_json::json!({
+    ///         "type": "object",
+    ///         "properties": { "city": { "type": "string" } },
+    ///         "required": ["city"],
+    ///     }),
+    /// )
+    /// .into();
+    ///
+    /// assert_eq!(tool.definition().name(), "get_weather");
+    /// ```
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: impl Into<ToolParameters>,
+    ) -> Self {
+        FunctionDefinition::builder()
+            .name(name)
+            .description(description)
+            .parameters(parameters)
+            .build()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn parameters(&self) -> &ToolParameters {
+        &self.parameters
+    }
+
+    /// Requires arguments that match the parameter schema exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deepinfra_client_rs::chat_completition::*;
+    ///
+    /// let function = FunctionDefinition::new(
+    ///     "get_weather",
+    ///     "Returns the current weather in a city",
+    ///     serde_json::json!({
+    ///         "type": "object",
+    ///         "properties": { "city": { "type": "string" } },
+    ///         "required": ["city"],
+    ///         "additionalProperties": false,
+    ///     }),
+    /// )
+    /// .strict();
+    ///
+    /// assert_eq!(serde_json::to_value(&function)?["strict"], true);
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn strict(mut self) -> Self {
+        self.strict = Some(true);
+        self
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict.unwrap_or(false)
+    }
+}
+
+/// The JSON schema of a function's parameters, serialized once.
+///
+/// The schema is kept as raw JSON behind an `Arc`, so cloning a tool is cheap and
+/// every request embeds the same bytes without serializing the schema again.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::*;
+///
+/// let parameters = ToolParameters::from_json(
+///     r#"{"type": "object", "properties": {"city": {"type": "string"}}}"#,
+/// )
+/// .unwrap();
+///
+/// let tool = ChatTool::builder()
+///     .function(
+///         FunctionDefinition::builder()
+///             .name("get_weather")
+///             .description("Returns the current weather in a city")
+///             .parameters(parameters)
+///             .build(),
+///     )
+///     .build();
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ToolParameters(Arc<RawValue>);
+
+impl ToolParameters {
+    /// Wraps pre-rendered JSON, checking that it is valid.
+    pub fn from_json(json: impl Into<String>) -> std::result::Result<Self, serde_json::Error> {
+        Ok(Self(RawValue::from_string(json.into())?.into()))
+    }
+
+    /// Serializes a schema value once.
+    pub fn from_value(schema: &impl Serialize) -> std::result::Result<Self, serde_json::Error> {
+        Ok(Self(serde_json::value::to_raw_value(schema)?.into()))
+    }
+
+    /// Generates the JSON schema of `T`.
+    #[cfg(feature = "schemars")]
+    pub fn from_schema<T: schemars::JsonSchema>() -> Self {
+        let mut schema = schemars::schema_for!(T);
+        // The meta-schema URI is noise to the model and rejected by some backends.
+        schema.remove("$schema");
+        // A schema is a JSON value, which always serializes.
+        Self::from_value(&schema).expect("JSON schema serializes")
+    }
+
+    /// The schema as JSON text.
+    pub fn get(&self) -> &str {
+        self.0.get()
+    }
+}
+
+impl From<serde_json::Value> for ToolParameters {
+    fn from(schema: serde_json::Value) -> Self {
+        // A JSON value always serializes.
+        Self::from_value(&schema).expect("JSON value serializes")
+    }
+}
+
+#[derive(Clone, Debug)]
 pub enum ResponseFormatType {
     Text,
     JsonObject,
+    /// Output constrained to a JSON schema.
+    JsonSchema {
+        /// Name of the schema, shown to the model.
+        name: String,
+        schema: serde_json::Value,
+        /// Whether the model must follow the schema exactly.
+        strict: Option<bool>,
+    },
 }
 
 /// Specifies the format of the response.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "WireResponseFormat", into = "WireResponseFormat")]
 pub struct ResponseFormat {
-    /// Response type, such as "text" or "json_object".
-    #[serde(default = "default_response_format_type", rename = "type")]
+    /// Response type, such as "text", "json_object" or "json_schema".
     pub response_type: ResponseFormatType,
 }
 
-fn default_response_format_type() -> ResponseFormatType {
-    ResponseFormatType::Text
+/// The API representation of a [`ResponseFormat`], which nests the schema of the
+/// `json_schema` type next to the type tag.
+#[derive(Serialize, Deserialize)]
+struct WireResponseFormat {
+    #[serde(default = "default_response_format_kind", rename = "type")]
+    kind: ResponseFormatKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_schema: Option<JsonSchemaFormat>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ResponseFormatKind {
+    Text,
+    JsonObject,
+    JsonSchema,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonSchemaFormat {
+    name: String,
+    #[serde(default)]
+    schema: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strict: Option<bool>,
+}
+
+fn default_response_format_kind() -> ResponseFormatKind {
+    ResponseFormatKind::Text
+}
+
+impl From<WireResponseFormat> for ResponseFormat {
+    fn from(format: WireResponseFormat) -> Self {
+        let response_type = match (format.kind, format.json_schema) {
+            (ResponseFormatKind::JsonSchema, Some(json_schema)) => ResponseFormatType::JsonSchema {
+                name: json_schema.name,
+                schema: json_schema.schema,
+                strict: json_schema.strict,
+            },
+            // A schema type without a schema only constrains the output to JSON.
+            (ResponseFormatKind::JsonSchema | ResponseFormatKind::JsonObject, _) => {
+                ResponseFormatType::JsonObject
+            }
+            (ResponseFormatKind::Text, _) => ResponseFormatType::Text,
+        };
+        ResponseFormat { response_type }
+    }
+}
+
+impl From<ResponseFormat> for WireResponseFormat {
+    fn from(format: ResponseFormat) -> Self {
+        match format.response_type {
+            ResponseFormatType::Text => WireResponseFormat {
+                kind: ResponseFormatKind::Text,
+                json_schema: None,
+            },
+            ResponseFormatType::JsonObject => WireResponseFormat {
+                kind: ResponseFormatKind::JsonObject,
+                json_schema: None,
+            },
+            ResponseFormatType::JsonSchema {
+                name,
+                schema,
+                strict,
+            } => WireResponseFormat {
+                kind: ResponseFormatKind::JsonSchema,
+                json_schema: Some(JsonSchemaFormat {
+                    name,
+                    schema,
+                    strict,
+                }),
+            },
+        }
+    }
+}
+
+/// Controls which (if any) tool the model calls.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::ToolChoice;
+///
+/// let json = serde_json::to_value(ToolChoice::function("get_weather"))?;
+/// assert_eq!(
+///     json,
+///     serde_json::json!({ "type": "function", "function": { "name": "get_weather" } }),
+/// );
+/// assert_eq!(serde_json::to_value(ToolChoice::Required)?, "required");
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "WireToolChoice", into = "WireToolChoice")]
+pub enum ToolChoice {
+    /// The model does not call any tool.
+    None,
+    /// The model decides whether to call tools.
+    Auto,
+    /// The model calls at least one tool.
+    Required,
+    /// The model calls the function `name`.
+    Function { name: String },
+}
+
+impl ToolChoice {
+    /// Forces the model to call the function `name`.
+    pub fn function(name: impl Into<String>) -> Self {
+        ToolChoice::Function { name: name.into() }
+    }
+}
+
+/// The API representation of a [`ToolChoice`]: a mode string, or an object naming
+/// the function to call.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum WireToolChoice {
+    Mode(ToolChoiceMode),
+    Function {
+        #[serde(rename = "type")]
+        kind: String,
+        function: ToolChoiceFunction,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ToolChoiceMode {
+    None,
+    Auto,
+    Required,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ToolChoiceFunction {
+    name: String,
+}
+
+impl From<WireToolChoice> for ToolChoice {
+    fn from(choice: WireToolChoice) -> Self {
+        match choice {
+            WireToolChoice::Mode(ToolChoiceMode::None) => ToolChoice::None,
+            WireToolChoice::Mode(ToolChoiceMode::Auto) => ToolChoice::Auto,
+            WireToolChoice::Mode(ToolChoiceMode::Required) => ToolChoice::Required,
+            WireToolChoice::Function { function, .. } => ToolChoice::Function {
+                name: function.name,
+            },
+        }
+    }
+}
+
+impl From<ToolChoice> for WireToolChoice {
+    fn from(choice: ToolChoice) -> Self {
+        match choice {
+            ToolChoice::None => WireToolChoice::Mode(ToolChoiceMode::None),
+            ToolChoice::Auto => WireToolChoice::Mode(ToolChoiceMode::Auto),
+            ToolChoice::Required => WireToolChoice::Mode(ToolChoiceMode::Required),
+            ToolChoice::Function { name } => WireToolChoice::Function {
+                kind: "function".to_string(),
+                function: ToolChoiceFunction { name },
+            },
+        }
+    }
+}
+
+/// Reasoning effort accepted by reasoning models such as gpt-oss.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+/// The reasoning parameters a model family accepts.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::ReasoningSupport;
+///
+/// let support = ReasoningSupport::of("openai/gpt-oss-120b").unwrap();
+/// assert!(support.effort && !support.token_budget);
+/// assert_eq!(ReasoningSupport::of("my-org/custom-model"), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReasoningSupport {
+    /// Accepts `reasoning_effort`.
+    pub effort: bool,
+    /// Accepts `max_reasoning_tokens`.
+    pub token_budget: bool,
+}
+
+/// How an entry of [`REASONING_MODELS`] matches DeepInfra model IDs.
+#[derive(Clone, Copy, Debug)]
+enum ModelId {
+    /// The one model with this ID.
+    Exact(&'static str),
+    /// Every model whose ID starts with this prefix.
+    Prefix(&'static str),
+}
+
+impl ModelId {
+    /// Whether `model` is matched, ignoring ASCII case.
+    fn matches(self, model: &str) -> bool {
+        match self {
+            ModelId::Exact(id) => model.eq_ignore_ascii_case(id),
+            ModelId::Prefix(prefix) => model
+                .get(..prefix.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(prefix)),
+        }
+    }
+}
+
+/// Models with known reasoning parameters, by their ID in the DeepInfra model list.
+/// Qwen3 models are listed one by one, since the instruct and coder releases of the
+/// family do not think.
+const REASONING_MODELS: [(ModelId, ReasoningSupport); 11] = [
+    (
+        ModelId::Prefix("openai/gpt-oss-"),
+        ReasoningSupport::new(true, false),
+    ),
+    // Hybrid models, thinking within the budget.
+    (
+        ModelId::Exact("Qwen/Qwen3-235B-A22B"),
+        ReasoningSupport::new(false, true),
+    ),
+    (
+        ModelId::Exact("Qwen/Qwen3-32B"),
+        ReasoningSupport::new(false, true),
+    ),
+    (
+        ModelId::Exact("Qwen/Qwen3-30B-A3B"),
+        ReasoningSupport::new(false, true),
+    ),
+    (
+        ModelId::Exact("Qwen/Qwen3-14B"),
+        ReasoningSupport::new(false, true),
+    ),
+    (
+        ModelId::Exact("Qwen/QwQ-32B"),
+        ReasoningSupport::new(false, true),
+    ),
+    // Qwen3 releases that do not reason.
+    (
+        ModelId::Exact("Qwen/Qwen3-235B-A22B-Instruct-2507"),
+        ReasoningSupport::new(false, false),
+    ),
+    (
+        ModelId::Prefix("Qwen/Qwen3-Coder-"),
+        ReasoningSupport::new(false, false),
+    ),
+    // Always reasons, without a way to tune it; includes the distilled models.
+    (
+        ModelId::Exact("deepseek-ai/DeepSeek-R1"),
+        ReasoningSupport::new(false, false),
+    ),
+    (
+        ModelId::Prefix("deepseek-ai/DeepSeek-R1-"),
+        ReasoningSupport::new(false, false),
+    ),
+    // Does not reason.
+    (
+        ModelId::Prefix("meta-llama/"),
+        ReasoningSupport::new(false, false),
+    ),
+];
+
+impl ReasoningSupport {
+    const fn new(effort: bool, token_budget: bool) -> Self {
+        Self {
+            effort,
+            token_budget,
+        }
+    }
+
+    /// The reasoning parameters `model` accepts, or `None` for models this crate
+    /// does not know, whose parameters are sent unchecked.
+    pub fn of(model: &str) -> Option<Self> {
+        REASONING_MODELS
+            .iter()
+            .find(|(id, _)| id.matches(model))
+            .map(|(_, support)| *support)
+    }
 }
 
 /// Details of a tool call made by the model.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ToolCall {
     /// The ID of the tool call.
-    id: String,
+    pub(crate) id: String,
 
     /// The type of the tool call. Only "function" is supported currently.
     #[serde(rename = "type")]
-    type_: String,
+    pub(crate) type_: String,
 
     /// The function that the model called.
-    function: FunctionCall,
+    pub(crate) function: FunctionCall,
 }
 
 /// Represents a function call made by the model.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FunctionCall {
     /// The name of the function to call.
-    name: String,
+    pub(crate) name: String,
 
     /// The function arguments in JSON format.
     /// The model may not always generate valid JSON.
-    arguments: String,
+    pub(crate) arguments: String,
+}
+
+impl ToolCall {
+    /// The ID to reply to with a [`ToolMessage`].
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn function(&self) -> &FunctionCall {
+        &self.function
+    }
+}
+
+impl FunctionCall {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The arguments as generated by the model, which may not be valid JSON.
+    pub fn arguments(&self) -> &str {
+        &self.arguments
+    }
+
+    /// Deserializes the arguments into `T`.
+    pub fn parse_arguments<T: DeserializeOwned>(
+        &self,
+    ) -> std::result::Result<T, serde_json::Error> {
+        serde_json::from_str(&self.arguments)
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Choice {
-    index: i32,
+    pub(crate) index: i32,
     pub message: Message,
-    finish_reason: String,
+    pub(crate) finish_reason: FinishReason,
+    /// Token log probabilities, if requested with `logprobs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<ChoiceLogprobs>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Log probabilities of the tokens of a choice.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ChoiceLogprobs {
+    /// One entry per output token, in order.
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub content: Vec<LogprobsContent>,
+}
+
+/// An output token with its log probability and the most likely alternatives.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LogprobsContent {
+    pub token: String,
+    pub logprob: f64,
+    /// The UTF-8 bytes of the token, for tokens that are not valid text on their own.
+    pub bytes: Option<Vec<u8>>,
+    /// Up to `top_logprobs` alternatives, most likely first.
+    #[serde(default)]
+    pub top_logprobs: Vec<TopLogprob>,
+}
+
+/// An alternative token considered at a position.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TopLogprob {
+    pub token: String,
+    pub logprob: f64,
+    pub bytes: Option<Vec<u8>>,
+}
+
+impl LogprobsContent {
+    /// The probability of the token, between 0 and 1.
+    pub fn probability(&self) -> f64 {
+        self.logprob.exp()
+    }
+}
+
+/// Why the model stopped generating a choice.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::FinishReason;
+///
+/// let reason: FinishReason = serde_json::from_str(r#""tool_calls""#)?;
+/// assert_eq!(reason, FinishReason::ToolCalls);
+///
+/// let reason: FinishReason = serde_json::from_str(r#""eos""#)?;
+/// assert_eq!(reason, FinishReason::Other("eos".to_string()));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum FinishReason {
+    /// The model finished its answer or hit a stop sequence.
+    Stop,
+    /// The answer was cut at `max_tokens` or the context length.
+    Length,
+    /// The model called tools.
+    ToolCalls,
+    /// The output was cut or withheld by a content filter.
+    ContentFilter,
+    /// A reason this crate does not know about, kept verbatim.
+    Other(String),
+}
+
+impl FinishReason {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FinishReason::Stop => "stop",
+            FinishReason::Length => "length",
+            FinishReason::ToolCalls => "tool_calls",
+            FinishReason::ContentFilter => "content_filter",
+            FinishReason::Other(reason) => reason,
+        }
+    }
+}
+
+impl From<String> for FinishReason {
+    fn from(reason: String) -> Self {
+        match reason.as_str() {
+            "stop" => FinishReason::Stop,
+            "length" => FinishReason::Length,
+            "tool_calls" => FinishReason::ToolCalls,
+            "content_filter" => FinishReason::ContentFilter,
+            _ => FinishReason::Other(reason),
+        }
+    }
+}
+
+impl From<FinishReason> for String {
+    fn from(reason: FinishReason) -> Self {
+        match reason {
+            FinishReason::Other(reason) => reason,
+            reason => reason.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for FinishReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Token counts and cost of a completion.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::Usage;
+///
+/// let usage: Usage = serde_json::from_str(
+///     r#"{"prompt_tokens": 12, "completion_tokens": 30, "total_tokens": 42,
+///         "estimated_cost": 0.000021, "prompt_tokens_details": {"cached_tokens": 8}}"#,
+/// )?;
+/// assert_eq!(usage.estimated_cost, Some(0.000021));
+/// assert_eq!(usage.extra["prompt_tokens_details"]["cached_tokens"], 8);
+///
+/// let total = usage.clone() + usage;
+/// assert_eq!(total.total_tokens, 84);
+/// assert_eq!(total.extra["prompt_tokens_details"]["cached_tokens"], 16);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Usage {
-    prompt_tokens: i32,
-    total_tokens: i32,
-    completion_tokens: i32,
+    pub prompt_tokens: i32,
+    pub total_tokens: i32,
+    pub completion_tokens: i32,
+    /// The cost of the request in USD, as estimated by DeepInfra.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_cost: Option<f64>,
+    /// Usage fields without a dedicated field, such as token details.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl std::ops::Add for Usage {
+    type Output = Usage;
+
+    /// Sums the counts and costs. Numbers in `extra` are summed as well; other
+    /// values are taken from `other`.
+    fn add(self, other: Usage) -> Usage {
+        let mut extra = self.extra;
+        add_usage_fields(&mut extra, other.extra);
+
+        Usage {
+            prompt_tokens: self.prompt_tokens + other.prompt_tokens,
+            total_tokens: self.total_tokens + other.total_tokens,
+            completion_tokens: self.completion_tokens + other.completion_tokens,
+            estimated_cost: match (self.estimated_cost, other.estimated_cost) {
+                (Some(a), Some(b)) => Some(a + b),
+                (a, b) => a.or(b),
+            },
+            extra,
+        }
+    }
+}
+
+/// Adds `value` to `total`, recursing into objects.
+fn add_usage_value(total: &mut serde_json::Value, value: serde_json::Value) {
+    use serde_json::Value;
+    match (total, value) {
+        (Value::Number(a), Value::Number(b)) => {
+            let sum = match (a.as_i64(), b.as_i64()) {
+                (Some(a), Some(b)) => Some(a.saturating_add(b).into()),
+                _ => serde_json::Number::from_f64(
+                    a.as_f64().unwrap_or_default() + b.as_f64().unwrap_or_default(),
+                ),
+            };
+            if let Some(sum) = sum {
+                *a = sum;
+            }
+        }
+        (Value::Object(total), Value::Object(fields)) => add_usage_fields(total, fields),
+        (total, value) => *total = value,
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Adds each of `fields` to the field of the same name in `total`.
+fn add_usage_fields(
+    total: &mut serde_json::Map<String, serde_json::Value>,
+    fields: serde_json::Map<String, serde_json::Value>,
+) {
+    for (key, value) in fields {
+        match total.get_mut(&key) {
+            Some(field) => add_usage_value(field, value),
+            None => {
+                total.insert(key, value);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ChatCompletionResponse {
-    id: Option<String>,
-    object: Option<String>,
-    created: Option<i64>,
-    model: Option<String>,
+    pub(crate) id: Option<String>,
+    pub(crate) object: Option<String>,
+    pub(crate) created: Option<i64>,
+    pub(crate) model: Option<String>,
     pub choices: Vec<Choice>,
-    usage: Option<Usage>,
+    pub(crate) usage: Option<Usage>,
+    /// Identifies the backend configuration that served the request.
+    /// Changes here explain output drift under a pinned seed.
+    pub system_fingerprint: Option<String>,
+    /// Status and headers of the HTTP response, including DeepInfra runtime headers.
+    #[serde(skip)]
+    pub metadata: ResponseMetadata,
+}
+
+impl ChatCompletionResponse {
+    /// Returns the assistant content of the first choice, if any.
+    pub fn content(&self) -> Option<&str> {
+        match &self.choices.first()?.message {
+            Message::Assistant(message) => Some(&message.content),
+            _ => None,
+        }
+    }
+
+    /// Returns the refusal of the first choice, if the model declined to answer.
+    pub fn refusal(&self) -> Option<&str> {
+        match &self.choices.first()?.message {
+            Message::Assistant(message) => message.refusal(),
+            _ => None,
+        }
+    }
+}
+
+impl Choice {
+    /// Why generation stopped.
+    pub fn finish_reason(&self) -> &FinishReason {
+        &self.finish_reason
+    }
+
+    /// Whether the output was cut or withheld by a content filter.
+    pub fn is_content_filtered(&self) -> bool {
+        self.finish_reason == FinishReason::ContentFilter
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum ChatCompletionError {
     #[error("Request errored {0}")]
-    ReqwestError(#[from] reqwest::Error),
+    Transport(#[from] reqwest::Error),
+    #[error("Post-processing failed {0}")]
+    PostProcessError(#[from] PostProcessError),
+    #[error("Client shutdown {0}")]
+    ShutdownError(#[from] ShutdownError),
+    #[error("Could not decode response {0}")]
+    DecodeError(#[from] DecodeError),
+    #[error("API error {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Stream buffer overflowed {0}")]
+    BufferOverflowError(#[from] BufferOverflowError),
+    #[error("At most {MAX_STOP_SEQUENCES} stop sequences are allowed, got {0}")]
+    TooManyStopSequences(usize),
+    #[error("{model} does not accept {parameter}")]
+    UnsupportedReasoningParameter {
+        model: String,
+        parameter: &'static str,
+    },
+    #[error(
+        "Reasoning budget of {budget} tokens must be positive and below max_tokens ({max_tokens})"
+    )]
+    InvalidReasoningBudget { budget: u32, max_tokens: u32 },
 }
 
 type Result<T> = std::result::Result<T, ChatCompletionError>;
@@ -241,28 +1451,233 @@ impl DeepinfraClient {
     /// Sends a chat completion request to DeepInfra, returning a structured response.
     ///
     /// # Usage
-    /// ```
-    /// let request = ChatCompletionRequestBuilder::default()
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), Box<dyn std::error::Error>> {
+    /// let request = ChatCompletionRequest::builder()
     ///     // Build your messages, model, temperature, etc.
+    ///     .messages(vec![Message::User(UserMessage::builder().content("Hello!").build())])
     ///     .build();
     ///
     /// let response = client.chat_completition(request).await?;
     /// println!("Received chat response: {:?}", response);
+    /// # Ok(())
+    /// # }
     /// ```
     #[instrument(skip(self))]
     pub async fn chat_completition(
         &self,
         body: ChatCompletionRequest,
     ) -> Result<ChatCompletionResponse> {
-        let response = self
-            .client
-            .post(CHAT_COMPLETIONS_API_URL)
-            .json(&body)
-            .send()
+        let mut response = self.chat_completion_unprocessed(&body).await?;
+        self.post_process(&mut response)?;
+        Ok(response)
+    }
+
+    /// Sends `body` like [`DeepinfraClient::chat_completition`], without applying the
+    /// post-processors.
+    pub(crate) async fn chat_completion_unprocessed(
+        &self,
+        body: &ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse> {
+        self.lifecycle
+            .track(self.send_chat_completion(body))
             .await?
-            .json()
-            .await?;
+    }
+
+    /// Applies the client's post-processors to the assistant content of `response`.
+    pub(crate) fn post_process(&self, response: &mut ChatCompletionResponse) -> Result<()> {
+        if !self.post_processing.is_empty() {
+            for choice in &mut response.choices {
+                if let Message::Assistant(message) = &mut choice.message {
+                    let content = std::mem::take(&mut message.content);
+                    message.content = self.post_processing.apply(content)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends `body` and returns the response before deserialization.
+    ///
+    /// The request is validated and its model alias resolved as for
+    /// [`DeepinfraClient::chat_completition`], but post-processors are not applied.
+    #[instrument(skip(self))]
+    pub async fn chat_completion_raw(&self, body: ChatCompletionRequest) -> Result<RawResponse> {
+        self.lifecycle
+            .track(async {
+                let (request, model) = self.chat_completion_request(&body)?;
+                let _permit = self.throttle(&model).await;
+                Ok(self.send_raw(request).await?)
+            })
+            .await?
+    }
+
+    async fn send_chat_completion(
+        &self,
+        body: &ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse> {
+        let (request, model) = self.chat_completion_request(body)?;
+        let _permit = self.throttle(&model).await;
+        let started = Instant::now();
+        let http_response = self.send(request).await?;
+        let http_response = ApiError::check(http_response).await?;
+        let metadata = self.response_metadata(&http_response, started);
+
+        let mut response: ChatCompletionResponse = read_json(http_response).await??;
+        response.metadata = metadata.finish(started);
 
         Ok(response)
     }
+
+    /// Validates `body`, resolves its model and builds the HTTP request. Returns the
+    /// request and the resolved model.
+    fn chat_completion_request(
+        &self,
+        body: &ChatCompletionRequest,
+    ) -> Result<(RequestBuilder, String)> {
+        let model = self.resolve_model(&body.model);
+        body.validate(&model)?;
+        let request = if model == body.model {
+            self.post_json(CHAT_COMPLETIONS_PATH, body)
+        } else {
+            let resolved = ChatCompletionRequest {
+                model: model.clone(),
+                ..body.clone()
+            };
+            self.post_json(CHAT_COMPLETIONS_PATH, &resolved)
+        };
+        Ok((with_timeout(request, body.timeout), model))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> ChatCompletionRequestBuilder<chat_completion_request_builder::SetMessages> {
+        ChatCompletionRequest::builder().messages(Vec::new())
+    }
+
+    #[test]
+    fn rejects_a_budget_without_room_for_the_answer() {
+        for budget in [0, 1_000] {
+            let request = request()
+                .max_tokens(1_000)
+                .max_reasoning_tokens(budget)
+                .build();
+            assert!(matches!(
+                request.validate("Qwen/Qwen3-32B"),
+                Err(ChatCompletionError::InvalidReasoningBudget { .. })
+            ));
+        }
+        let request = request()
+            .max_tokens(1_000)
+            .max_reasoning_tokens(999)
+            .build();
+        assert!(request.validate("Qwen/Qwen3-32B").is_ok());
+    }
+
+    #[test]
+    fn rejects_parameters_the_model_does_not_accept() {
+        let budget = request().max_reasoning_tokens(512).build();
+        assert!(matches!(
+            budget.validate("openai/gpt-oss-120b"),
+            Err(ChatCompletionError::UnsupportedReasoningParameter {
+                parameter: "max_reasoning_tokens",
+                ..
+            })
+        ));
+
+        let effort = request().reasoning_effort(ReasoningEffort::High).build();
+        assert!(effort.validate("openai/gpt-oss-120b").is_ok());
+        assert!(matches!(
+            effort.validate("deepseek-ai/DeepSeek-R1"),
+            Err(ChatCompletionError::UnsupportedReasoningParameter {
+                parameter: "reasoning_effort",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn sends_parameters_for_unknown_models_unchecked() {
+        let request = request()
+            .reasoning_effort(ReasoningEffort::Low)
+            .max_reasoning_tokens(512)
+            .build();
+        assert!(request.validate("my-org/custom-model").is_ok());
+    }
+
+    fn support(model: &str) -> Option<(bool, bool)> {
+        ReasoningSupport::of(model).map(|support| (support.effort, support.token_budget))
+    }
+
+    #[test]
+    fn knows_gpt_oss_takes_an_effort() {
+        for model in ["openai/gpt-oss-120b", "openai/gpt-oss-20b"] {
+            assert_eq!(support(model), Some((true, false)), "{model}");
+        }
+    }
+
+    #[test]
+    fn knows_which_qwen_models_take_a_budget() {
+        for model in [
+            "Qwen/Qwen3-235B-A22B",
+            "Qwen/Qwen3-32B",
+            "Qwen/Qwen3-30B-A3B",
+            "Qwen/Qwen3-14B",
+            "Qwen/QwQ-32B",
+            "qwen/qwen3-32b",
+        ] {
+            assert_eq!(support(model), Some((false, true)), "{model}");
+        }
+        for model in [
+            "Qwen/Qwen3-235B-A22B-Instruct-2507",
+            "Qwen/Qwen3-Coder-480B-A35B-Instruct",
+        ] {
+            assert_eq!(support(model), Some((false, false)), "{model}");
+        }
+    }
+
+    #[test]
+    fn knows_deepseek_r1_cannot_be_tuned() {
+        for model in [
+            "deepseek-ai/DeepSeek-R1",
+            "deepseek-ai/DeepSeek-R1-0528",
+            "deepseek-ai/DeepSeek-R1-Distill-Llama-70B",
+        ] {
+            assert_eq!(support(model), Some((false, false)), "{model}");
+        }
+    }
+
+    #[test]
+    fn knows_llama_does_not_reason() {
+        assert_eq!(
+            support("meta-llama/Meta-Llama-3.1-8B-Instruct"),
+            Some((false, false))
+        );
+    }
+
+    #[test]
+    fn leaves_models_merely_named_after_a_family_unchecked() {
+        for model in [
+            "my-org/qwen3-32b-finetune",
+            "Qwen/Qwen3-32B-AWQ",
+            "deepseek-ai/DeepSeek-R1x",
+            "my-org/meta-llama/Llama-3-8B",
+            "my-org/gpt-oss-120b",
+        ] {
+            assert_eq!(support(model), None, "{model}");
+        }
+    }
+
+    #[test]
+    fn omits_an_unset_budget() {
+        let body = serde_json::to_value(request().build()).unwrap();
+        assert!(body.get("max_reasoning_tokens").is_none());
+        let body = serde_json::to_value(request().max_reasoning_tokens(512).build()).unwrap();
+        assert_eq!(body["max_reasoning_tokens"], 512);
+    }
 }
@@ -0,0 +1,991 @@
+use crate::api_error::{decode, request_id, ApiError};
+use crate::chat_completition::{
+    AssistantMessage, ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse, Choice,
+    ChoiceLogprobs, FinishReason, FunctionCall, Message, StreamOptions, ToolCall, Usage,
+    CHAT_COMPLETIONS_PATH,
+};
+use crate::client::{with_timeout, DeepinfraClient};
+use crate::shutdown::ShutdownError;
+use crate::sse;
+use bon::Builder;
+use futures::future;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{debug, instrument};
+
+/// Author of a streamed message, sent in the first delta of each choice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    System,
+    User,
+    Assistant,
+    Tool,
+}
+
+/// One server-sent event of a streamed chat completion.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    pub id: Option<String>,
+    pub object: Option<String>,
+    pub created: Option<i64>,
+    pub model: Option<String>,
+    pub choices: Vec<ChunkChoice>,
+    pub usage: Option<Usage>,
+    pub system_fingerprint: Option<String>,
+}
+
+/// The incremental update for a single choice.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChunkChoice {
+    pub index: u32,
+    pub delta: Delta,
+    /// Set on the last chunk of the choice.
+    pub finish_reason: Option<FinishReason>,
+    /// Log probabilities of the tokens in this chunk, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logprobs: Option<ChoiceLogprobs>,
+}
+
+/// Fields of the assistant message added by a chunk.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Delta {
+    pub role: Option<Role>,
+    pub content: Option<String>,
+    /// A fragment of the refusal, when the model declines to answer.
+    pub refusal: Option<String>,
+    pub tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+/// A fragment of a tool call; fragments sharing an `index` belong to the same call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ToolCallDelta {
+    pub index: u32,
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub function: Option<FunctionCallDelta>,
+}
+
+/// A fragment of a function call. `arguments` must be concatenated across chunks.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct FunctionCallDelta {
+    pub name: Option<String>,
+    pub arguments: Option<String>,
+}
+
+/// Merges the chunks of a streamed chat completion into a complete response.
+///
+/// Content is concatenated per choice and tool call fragments are reassembled by
+/// their index, so partial tokens can be displayed while the final message is
+/// built up on the side.
+///
+/// # Example
+///
+/// ```no_run
+/// # use deepinfra_client_rs::prelude::*;
+/// # use deepinfra_client_rs::chat_completition::*;
+/// # use deepinfra_client_rs::chat_stream::*;
+/// # async fn run(client: DeepinfraClient, request: ChatCompletionRequest) -> Result<(), ChatCompletionError> {
+/// use futures::StreamExt;
+///
+/// let mut stream = client.chat_completion_stream(request).await?;
+/// let mut accumulator = StreamAccumulator::new();
+/// while let Some(chunk) = stream.next().await {
+///     let chunk = chunk?;
+///     for choice in &chunk.choices {
+///         print!("{}", choice.delta.content.as_deref().unwrap_or_default());
+///     }
+///     accumulator.push(&chunk);
+/// }
+/// let response = accumulator.finish();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StreamAccumulator {
+    id: Option<String>,
+    object: Option<String>,
+    created: Option<i64>,
+    model: Option<String>,
+    system_fingerprint: Option<String>,
+    usage: Option<Usage>,
+    choices: BTreeMap<u32, ChoiceAccumulator>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ChoiceAccumulator {
+    content: String,
+    refusal: Option<String>,
+    tool_calls: BTreeMap<u32, ToolCallAccumulator>,
+    finish_reason: Option<FinishReason>,
+    logprobs: Option<ChoiceLogprobs>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct ToolCallAccumulator {
+    id: String,
+    type_: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+impl StreamAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merges one chunk into the response built so far.
+    pub fn push(&mut self, chunk: &ChatCompletionChunk) {
+        // Identifiers repeat on every chunk; the first one seen wins.
+        self.id = self.id.take().or_else(|| chunk.id.clone());
+        self.object = self.object.take().or_else(|| chunk.object.clone());
+        self.created = self.created.or(chunk.created);
+        self.model = self.model.take().or_else(|| chunk.model.clone());
+        self.system_fingerprint = self
+            .system_fingerprint
+            .take()
+            .or_else(|| chunk.system_fingerprint.clone());
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage.clone();
+        }
+
+        for choice in &chunk.choices {
+            let accumulated = self.choices.entry(choice.index).or_default();
+            if let Some(content) = &choice.delta.content {
+                accumulated.content.push_str(content);
+            }
+            if let Some(refusal) = &choice.delta.refusal {
+                accumulated
+                    .refusal
+                    .get_or_insert_with(String::new)
+                    .push_str(refusal);
+            }
+            for fragment in choice.delta.tool_calls.iter().flatten() {
+                let call = accumulated.tool_calls.entry(fragment.index).or_default();
+                // Some providers repeat the ID and name on every fragment; only the
+                // arguments are split across them.
+                set_once(&mut call.id, &fragment.id);
+                if fragment.type_.is_some() {
+                    call.type_ = fragment.type_.clone();
+                }
+                if let Some(function) = &fragment.function {
+                    set_once(&mut call.name, &function.name);
+                    if let Some(arguments) = &function.arguments {
+                        call.arguments.push_str(arguments);
+                    }
+                }
+            }
+            if choice.finish_reason.is_some() {
+                accumulated.finish_reason = choice.finish_reason.clone();
+            }
+            if let Some(logprobs) = &choice.logprobs {
+                accumulated
+                    .logprobs
+                    .get_or_insert_with(ChoiceLogprobs::default)
+                    .content
+                    .extend(logprobs.content.iter().cloned());
+            }
+        }
+    }
+
+    /// Builds the complete response from the chunks pushed so far.
+    ///
+    /// The response carries no [`metadata`](ChatCompletionResponse::metadata), as the
+    /// chunks do not include the HTTP response.
+    pub fn finish(self) -> ChatCompletionResponse {
+        let choices = self
+            .choices
+            .into_iter()
+            .map(|(index, choice)| {
+                let tool_calls: Vec<ToolCall> = choice
+                    .tool_calls
+                    .into_values()
+                    .map(|call| ToolCall {
+                        id: call.id,
+                        type_: call.type_.unwrap_or_else(|| "function".to_string()),
+                        function: FunctionCall {
+                            name: call.name,
+                            arguments: call.arguments,
+                        },
+                    })
+                    .collect();
+                let message = AssistantMessage::builder()
+                    .content(choice.content)
+                    .maybe_refusal(choice.refusal)
+                    .maybe_tool_calls((!tool_calls.is_empty()).then_some(tool_calls))
+                    .build();
+                Choice {
+                    index: index as i32,
+                    message: Message::Assistant(message),
+                    // A stream cut short has no finish reason.
+                    finish_reason: choice
+                        .finish_reason
+                        .unwrap_or_else(|| FinishReason::Other(String::new())),
+                    logprobs: choice.logprobs,
+                }
+            })
+            .collect();
+
+        ChatCompletionResponse {
+            id: self.id,
+            object: self.object,
+            created: self.created,
+            model: self.model,
+            choices,
+            usage: self.usage,
+            system_fingerprint: self.system_fingerprint,
+            metadata: Default::default(),
+        }
+    }
+}
+
+/// Sets `field` to the first non-empty `value`.
+fn set_once(field: &mut String, value: &Option<String>) {
+    match value {
+        Some(value) if field.is_empty() => value.clone_into(field),
+        _ => {}
+    }
+}
+
+/// Cuts streamed content at client-side stop sequences.
+///
+/// Content that could be the start of a stop sequence is held back until the next
+/// chunk shows whether it is, so sequences split across chunks are caught too.
+#[derive(Debug)]
+struct StopEnforcer {
+    sequences: Vec<String>,
+    /// Number of choices requested; the stream ends once all of them have stopped.
+    choices: usize,
+    /// Held back content per choice, `None` once the choice has stopped.
+    pending: BTreeMap<u32, Option<String>>,
+}
+
+impl StopEnforcer {
+    fn new(sequences: Vec<String>, choices: usize) -> Self {
+        Self {
+            sequences: sequences.into_iter().filter(|s| !s.is_empty()).collect(),
+            choices: choices.max(1),
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Whether every choice has hit a stop sequence.
+    fn done(&self) -> bool {
+        self.pending
+            .values()
+            .filter(|pending| pending.is_none())
+            .count()
+            >= self.choices
+    }
+
+    /// Cuts the content of `chunk`, dropping choices that have already stopped.
+    /// Returns `None` if nothing of the chunk is left.
+    fn apply(&mut self, mut chunk: ChatCompletionChunk) -> Option<ChatCompletionChunk> {
+        let sequences = &self.sequences;
+        let had_choices = !chunk.choices.is_empty();
+        chunk.choices.retain_mut(|choice| {
+            let slot = self
+                .pending
+                .entry(choice.index)
+                .or_insert_with(|| Some(String::new()));
+            let Some(pending) = slot else {
+                return false;
+            };
+            let Some(delta) = choice.delta.content.take() else {
+                if choice.finish_reason.is_some() {
+                    choice.delta.content = Some(std::mem::take(pending));
+                }
+                return true;
+            };
+            pending.push_str(&delta);
+
+            let stop = sequences
+                .iter()
+                .filter_map(|sequence| pending.find(sequence.as_str()))
+                .min();
+            if let Some(position) = stop {
+                pending.truncate(position);
+                choice.delta.content = Some(std::mem::take(pending));
+                choice.finish_reason = Some(FinishReason::Stop);
+                *slot = None;
+                return true;
+            }
+
+            let keep = if choice.finish_reason.is_some() {
+                0
+            } else {
+                partial_stop(sequences, pending)
+            };
+            let held = pending.split_off(pending.len() - keep);
+            choice.delta.content = Some(std::mem::replace(pending, held));
+            true
+        });
+        (!had_choices || !chunk.choices.is_empty() || chunk.usage.is_some()).then_some(chunk)
+    }
+
+    /// A chunk releasing the content still held back when the stream ends early.
+    fn flush(&mut self) -> Option<ChatCompletionChunk> {
+        let choices: Vec<ChunkChoice> = self
+            .pending
+            .iter_mut()
+            .filter_map(|(index, pending)| {
+                let content = std::mem::take(pending.as_mut()?);
+                (!content.is_empty()).then(|| ChunkChoice {
+                    index: *index,
+                    delta: Delta {
+                        content: Some(content),
+                        ..Delta::default()
+                    },
+                    finish_reason: None,
+                    logprobs: None,
+                })
+            })
+            .collect();
+        (!choices.is_empty()).then_some(ChatCompletionChunk {
+            id: None,
+            object: None,
+            created: None,
+            model: None,
+            choices,
+            usage: None,
+            system_fingerprint: None,
+        })
+    }
+
+    /// Applies the enforcer to `chunks`, ending them once every choice has stopped.
+    fn enforce(
+        self,
+        chunks: BoxStream<'static, Result<ChatCompletionChunk, ChatCompletionError>>,
+    ) -> BoxStream<'static, Result<ChatCompletionChunk, ChatCompletionError>> {
+        stream::unfold(Some((chunks, self)), |state| async move {
+            let (mut chunks, mut enforcer) = state?;
+            loop {
+                if enforcer.done() {
+                    // Dropping the chunks closes the connection.
+                    return None;
+                }
+                match chunks.next().await {
+                    Some(Ok(chunk)) => {
+                        if let Some(chunk) = enforcer.apply(chunk) {
+                            return Some((Ok(chunk), Some((chunks, enforcer))));
+                        }
+                    }
+                    Some(Err(error)) => return Some((Err(error), Some((chunks, enforcer)))),
+                    None => return enforcer.flush().map(|chunk| (Ok(chunk), None)),
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+/// The length of the longest end of `text` that starts one of `sequences`.
+fn partial_stop(sequences: &[String], text: &str) -> usize {
+    sequences
+        .iter()
+        .flat_map(|sequence| {
+            sequence
+                .char_indices()
+                .skip(1)
+                .map(|(end, _)| &sequence[..end])
+                .filter(|prefix| text.ends_with(prefix))
+                .map(str::len)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Output rate metrics of a streamed chat completion, updated as chunks arrive.
+///
+/// Tokens are counted as chunks carrying generated text, as DeepInfra sends one
+/// token per chunk, and replaced by the exact count once the API reports usage.
+#[derive(Clone, Copy, Debug)]
+pub struct StreamMetrics {
+    started: Instant,
+    /// Time from sending the request to the first generated token.
+    pub time_to_first_token: Option<Duration>,
+    /// Time from sending the request to the last chunk received so far.
+    pub elapsed: Duration,
+    /// Tokens generated so far, summed over all choices.
+    pub tokens: u32,
+}
+
+impl StreamMetrics {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            time_to_first_token: None,
+            elapsed: Duration::ZERO,
+            tokens: 0,
+        }
+    }
+
+    fn record(&mut self, chunk: &ChatCompletionChunk) {
+        self.elapsed = self.started.elapsed();
+        let generated = chunk
+            .choices
+            .iter()
+            .filter(|choice| {
+                let delta = &choice.delta;
+                delta
+                    .content
+                    .as_deref()
+                    .is_some_and(|text| !text.is_empty())
+                    || delta.refusal.is_some()
+                    || delta.tool_calls.is_some()
+            })
+            .count() as u32;
+        if generated > 0 && self.time_to_first_token.is_none() {
+            self.time_to_first_token = Some(self.elapsed);
+        }
+        self.tokens += generated;
+        if let Some(usage) = &chunk.usage {
+            self.tokens = usage.completion_tokens.max(0) as u32;
+        }
+    }
+
+    /// Tokens generated per second after the first one, the usual measure of output
+    /// speed. `None` until at least two tokens have arrived.
+    pub fn tokens_per_second(&self) -> Option<f64> {
+        let generating = self.elapsed.checked_sub(self.time_to_first_token?)?;
+        (self.tokens > 1 && !generating.is_zero())
+            .then(|| f64::from(self.tokens - 1) / generating.as_secs_f64())
+    }
+}
+
+/// What a read-ahead buffer does when the consumer falls behind.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BufferOverflow {
+    /// Stops reading from the connection until the consumer catches up.
+    #[default]
+    Backpressure,
+    /// Ends the stream with a [`BufferOverflowError`] after the buffered chunks.
+    Fail,
+}
+
+/// A bounded buffer of chunks read from the connection ahead of the consumer.
+///
+/// Without a buffer, chunks are only read when the consumer asks for the next one.
+/// With one, a background task keeps reading until `capacity` chunks are waiting,
+/// which smooths out a bursty consumer, and applies `overflow` once it is full.
+/// [`BufferOverflow::Fail`] suits proxies whose clients may stall indefinitely.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::*;
+/// use deepinfra_client_rs::chat_stream::{BufferOverflow, StreamBuffer};
+///
+/// let request = ChatCompletionRequest::builder()
+///     .messages(vec![Message::User(UserMessage::builder().content("Hello!").build())])
+///     .stream_buffer(
+///         StreamBuffer::builder()
+///             .capacity(64)
+///             .overflow(BufferOverflow::Fail)
+///             .build(),
+///     )
+///     .build();
+/// ```
+#[derive(Clone, Copy, Debug, Builder)]
+pub struct StreamBuffer {
+    /// Maximum number of chunks waiting for the consumer; at least 1.
+    pub capacity: usize,
+    #[builder(default)]
+    pub overflow: BufferOverflow,
+}
+
+/// The consumer of a stream fell more than the buffer capacity behind.
+#[derive(Debug, thiserror::Error)]
+#[error("more than {capacity} chunks waiting for the consumer")]
+pub struct BufferOverflowError {
+    pub capacity: usize,
+}
+
+/// Aborts the read-ahead task when the stream is dropped.
+struct AbortOnDrop(JoinHandle<bool>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+impl StreamBuffer {
+    /// Reads `chunks` in a background task into the buffer. The returned receiver
+    /// closes once the task, and with it the request, has been dropped.
+    fn read_ahead(
+        self,
+        mut chunks: BoxStream<'static, Result<ChatCompletionChunk, ChatCompletionError>>,
+    ) -> (
+        BoxStream<'static, Result<ChatCompletionChunk, ChatCompletionError>>,
+        oneshot::Receiver<()>,
+    ) {
+        let capacity = self.capacity.max(1);
+        let (sender, receiver) = mpsc::channel(capacity);
+        let (released, on_released) = oneshot::channel();
+        // The task returns whether it stopped because the buffer was full.
+        let task = tokio::spawn(async move {
+            let _released = released;
+            while let Some(chunk) = chunks.next().await {
+                match self.overflow {
+                    BufferOverflow::Backpressure => {
+                        if sender.send(chunk).await.is_err() {
+                            return false;
+                        }
+                    }
+                    BufferOverflow::Fail => match sender.try_send(chunk) {
+                        Ok(()) => {}
+                        Err(TrySendError::Full(_)) => return true,
+                        Err(TrySendError::Closed(_)) => return false,
+                    },
+                }
+            }
+            false
+        });
+
+        let chunks = stream::unfold(
+            (receiver, Some(AbortOnDrop(task))),
+            move |(mut receiver, task)| async move {
+                if let Some(chunk) = receiver.recv().await {
+                    return Some((chunk, (receiver, task)));
+                }
+                let mut task = task?;
+                let overflowed = (&mut task.0).await.unwrap_or(false);
+                overflowed.then(|| {
+                    let error = BufferOverflowError { capacity }.into();
+                    (Err(error), (receiver, None))
+                })
+            },
+        )
+        .boxed();
+        (chunks, on_released)
+    }
+}
+
+/// A stream of [`ChatCompletionChunk`]s returned by
+/// [`DeepinfraClient::chat_completion_stream`].
+///
+/// When the stream ends, its [`metrics`](Self::metrics) are emitted as a `debug`
+/// tracing event.
+///
+/// # Cancellation
+///
+/// Dropping the stream closes the HTTP connection, which makes DeepInfra stop
+/// generating, and releases its rate limit permit. With a [`StreamBuffer`] the
+/// connection is held by a background task, which is aborted on drop and released
+/// shortly after; [`cancel`](Self::cancel) waits for that to happen.
+pub struct ChatCompletionStream {
+    inner: BoxStream<'static, Result<ChatCompletionChunk, ChatCompletionError>>,
+    /// Closes once the read-ahead task of a buffered stream has been dropped.
+    reader: Option<oneshot::Receiver<()>>,
+    metrics: StreamMetrics,
+    usage: Option<Usage>,
+    finished: bool,
+}
+
+impl Stream for ChatCompletionStream {
+    type Item = Result<ChatCompletionChunk, ChatCompletionError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.inner.poll_next_unpin(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(chunk))) => {
+                self.metrics.record(chunk);
+                if chunk.usage.is_some() {
+                    self.usage = chunk.usage.clone();
+                }
+            }
+            Poll::Ready(None) if !self.finished => {
+                self.finished = true;
+                let metrics = self.metrics;
+                debug!(
+                    time_to_first_token = ?metrics.time_to_first_token,
+                    elapsed = ?metrics.elapsed,
+                    tokens = metrics.tokens,
+                    tokens_per_second = metrics.tokens_per_second(),
+                    "Chat completion stream finished"
+                );
+            }
+            _ => {}
+        }
+        poll
+    }
+}
+
+impl ChatCompletionStream {
+    /// Wraps a stream of chunks produced without a request, e.g. by a mock
+    /// [`ChatCompletions`](crate::api::ChatCompletions) implementation.
+    pub fn from_chunks(
+        chunks: impl Stream<Item = Result<ChatCompletionChunk, ChatCompletionError>> + Send + 'static,
+    ) -> Self {
+        Self {
+            inner: chunks.boxed(),
+            reader: None,
+            metrics: StreamMetrics::new(Instant::now()),
+            usage: None,
+            finished: false,
+        }
+    }
+
+    /// Output rate metrics of the chunks received so far.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # async fn run(client: DeepinfraClient, request: ChatCompletionRequest) -> Result<(), ChatCompletionError> {
+    /// use futures::StreamExt;
+    ///
+    /// let mut stream = client.chat_completion_stream(request).await?;
+    /// while let Some(chunk) = stream.next().await {
+    ///     chunk?;
+    /// }
+    /// let metrics = stream.metrics();
+    /// println!(
+    ///     "first token after {:?}, {:.1} tokens/s",
+    ///     metrics.time_to_first_token,
+    ///     metrics.tokens_per_second().unwrap_or_default(),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn metrics(&self) -> StreamMetrics {
+        self.metrics
+    }
+
+    /// The usage of the request, once the chunk reporting it has been received.
+    pub fn usage(&self) -> Option<&Usage> {
+        self.usage.as_ref()
+    }
+
+    /// Stops the stream and waits until its connection and rate limit permit have
+    /// been released.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # async fn run(client: DeepinfraClient, request: ChatCompletionRequest) -> Result<(), ChatCompletionError> {
+    /// use futures::StreamExt;
+    ///
+    /// let mut stream = client.chat_completion_stream(request).await?;
+    /// let first = stream.next().await.transpose()?;
+    /// // Nothing more is generated, or billed, for this request.
+    /// stream.cancel().await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn cancel(self) {
+        let Self { inner, reader, .. } = self;
+        drop(inner);
+        if let Some(reader) = reader {
+            // The sender is never used; the receiver resolves once it is dropped.
+            let _ = reader.await;
+        }
+    }
+
+    /// Consumes the rest of the stream and merges it into a complete response.
+    pub async fn into_response(mut self) -> Result<ChatCompletionResponse, ChatCompletionError> {
+        let mut accumulator = StreamAccumulator::new();
+        while let Some(chunk) = self.next().await {
+            accumulator.push(&chunk?);
+        }
+        Ok(accumulator.finish())
+    }
+}
+
+impl fmt::Debug for ChatCompletionStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChatCompletionStream")
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeepinfraClient {
+    /// Sends a chat completion request and streams the response as it is generated.
+    ///
+    /// The `stream` flag of `body` is set automatically, and unless `body` sets
+    /// [`StreamOptions`], the last chunk reports the usage of the request. The stream ends after the
+    /// API's `[DONE]` event. Chunks are read as they are consumed unless the request
+    /// sets a [`StreamBuffer`]. Post-processors registered on the client are not applied
+    /// to streamed content. With `enforce_stop`, the `stop` sequences of `body` are
+    /// also applied on the client, ending the stream once every choice has stopped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), ChatCompletionError> {
+    /// use futures::StreamExt;
+    ///
+    /// let request = ChatCompletionRequest::builder()
+    ///     .messages(vec![Message::User(UserMessage::builder().content("Hello!").build())])
+    ///     .build();
+    ///
+    /// let mut stream = client.chat_completion_stream(request).await?;
+    /// while let Some(chunk) = stream.next().await {
+    ///     for choice in chunk?.choices {
+    ///         print!("{}", choice.delta.content.unwrap_or_default());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn chat_completion_stream(
+        &self,
+        mut body: ChatCompletionRequest,
+    ) -> Result<ChatCompletionStream, ChatCompletionError> {
+        body.model = self.resolve_model(&body.model);
+        body.validate(&body.model)?;
+        body.stream = true;
+        body.stream_options
+            .get_or_insert_with(|| StreamOptions::builder().include_usage(true).build());
+
+        // The registration lives as long as the stream, so shutdown drains it too.
+        // The request permit does as well, so open streams count towards the limits.
+        let mut in_flight = self.lifecycle.enter()?;
+        let permit = tokio::select! {
+            permit = self.throttle_stream(&body.model) => permit,
+            _ = in_flight.aborted() => return Err(ShutdownError::Aborted.into()),
+        };
+        let started = Instant::now();
+        let response = tokio::select! {
+            response = self.send(with_timeout(self.post_json(CHAT_COMPLETIONS_PATH, &body), body.timeout)) => response?,
+            _ = in_flight.aborted() => return Err(ShutdownError::Aborted.into()),
+        };
+        let response = ApiError::check(response).await?;
+        let request_id = request_id(response.headers());
+
+        let chunks = sse::data_events(response)
+            .take_while(|event| future::ready(!matches!(event, Ok(data) if data == "[DONE]")))
+            .map(move |event| {
+                let data = event?;
+                let chunk = decode::<ChatCompletionChunk>(data.as_bytes())
+                    .map_err(|error| error.with_request_id(request_id.clone()))?;
+                Ok(chunk)
+            })
+            .boxed();
+
+        let inner = stream::unfold(
+            (chunks, in_flight, permit, false),
+            |(mut chunks, mut in_flight, permit, aborted)| async move {
+                if aborted {
+                    return None;
+                }
+                tokio::select! {
+                    chunk = chunks.next() => chunk.map(|chunk| (chunk, (chunks, in_flight, permit, false))),
+                    _ = in_flight.aborted() => {
+                        let error = ShutdownError::Aborted.into();
+                        Some((Err(error), (chunks, in_flight, permit, true)))
+                    }
+                }
+            },
+        )
+        .boxed();
+        let inner = match &body.stop {
+            Some(stop) if body.enforce_stop => {
+                let sequences = stop.iter().map(str::to_string).collect();
+                StopEnforcer::new(sequences, body.n as usize).enforce(inner)
+            }
+            _ => inner,
+        };
+        let (inner, reader) = match body.stream_buffer {
+            Some(buffer) => {
+                let (inner, reader) = buffer.read_ahead(inner);
+                (inner, Some(reader))
+            }
+            None => (inner, None),
+        };
+
+        Ok(ChatCompletionStream {
+            inner,
+            reader,
+            metrics: StreamMetrics::new(started),
+            usage: None,
+            finished: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool_call_chunk(fragments: serde_json::Value) -> ChatCompletionChunk {
+        serde_json::from_value(json!({
+            "choices": [{
+                "index": 0,
+                "delta": { "tool_calls": fragments },
+                "finish_reason": null,
+            }],
+        }))
+        .unwrap()
+    }
+
+    fn tool_calls(chunks: &[ChatCompletionChunk]) -> Vec<(String, String, String)> {
+        let mut accumulator = StreamAccumulator::new();
+        for chunk in chunks {
+            accumulator.push(chunk);
+        }
+        let response = accumulator.finish();
+        let Message::Assistant(message) = &response.choices[0].message else {
+            panic!("not an assistant message");
+        };
+        message
+            .tool_calls()
+            .iter()
+            .map(|call| {
+                (
+                    call.id.clone(),
+                    call.function.name.clone(),
+                    call.function.arguments.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn concatenates_split_arguments() {
+        let calls = tool_calls(&[
+            tool_call_chunk(json!([{
+                "index": 0,
+                "id": "call_1",
+                "type": "function",
+                "function": { "name": "get_weather", "arguments": "{\"city\":" },
+            }])),
+            tool_call_chunk(json!([{ "index": 0, "function": { "arguments": "\"Rome\"}" } }])),
+        ]);
+        assert_eq!(
+            calls,
+            [(
+                "call_1".to_string(),
+                "get_weather".to_string(),
+                "{\"city\":\"Rome\"}".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn keeps_repeated_ids_and_names_once() {
+        let fragment = |arguments: &str| {
+            tool_call_chunk(json!([{
+                "index": 0,
+                "id": "call_1",
+                "function": { "name": "get_weather", "arguments": arguments },
+            }]))
+        };
+        let calls = tool_calls(&[fragment("{}"), fragment("")]);
+        assert_eq!(
+            calls,
+            [(
+                "call_1".to_string(),
+                "get_weather".to_string(),
+                "{}".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn separates_interleaved_indices() {
+        let calls = tool_calls(&[
+            tool_call_chunk(json!([
+                { "index": 0, "id": "call_a", "function": { "name": "a", "arguments": "[1" } },
+                { "index": 1, "id": "call_b", "function": { "name": "b", "arguments": "[2" } },
+            ])),
+            tool_call_chunk(json!([{ "index": 1, "function": { "arguments": "]" } }])),
+            tool_call_chunk(json!([{ "index": 0, "function": { "arguments": "]" } }])),
+        ]);
+        assert_eq!(
+            calls,
+            [
+                ("call_a".to_string(), "a".to_string(), "[1]".to_string()),
+                ("call_b".to_string(), "b".to_string(), "[2]".to_string()),
+            ]
+        );
+    }
+
+    fn content_chunk(content: &str, finish_reason: Option<&str>) -> ChatCompletionChunk {
+        serde_json::from_value(json!({
+            "choices": [{
+                "index": 0,
+                "delta": { "content": content },
+                "finish_reason": finish_reason,
+            }],
+        }))
+        .unwrap()
+    }
+
+    /// Runs `chunks` through a stop enforcer for `stop`, returning the content of
+    /// every chunk that came out and the last finish reason.
+    async fn enforce(
+        stop: &str,
+        chunks: Vec<ChatCompletionChunk>,
+    ) -> (Vec<String>, Option<FinishReason>) {
+        let chunks = stream::iter(chunks.into_iter().map(Ok)).boxed();
+        let enforced: Vec<ChatCompletionChunk> = StopEnforcer::new(vec![stop.to_string()], 1)
+            .enforce(chunks)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        let finish_reason = enforced
+            .iter()
+            .rev()
+            .find_map(|chunk| chunk.choices[0].finish_reason.clone());
+        let contents = enforced
+            .into_iter()
+            .map(|chunk| chunk.choices[0].delta.content.clone().unwrap_or_default())
+            .collect();
+        (contents, finish_reason)
+    }
+
+    #[tokio::test]
+    async fn cuts_at_a_stop_sequence_split_across_chunks() {
+        let (contents, finish_reason) = enforce(
+            "END",
+            vec![
+                content_chunk("Hello E", None),
+                content_chunk("ND world", None),
+                content_chunk("never read", None),
+            ],
+        )
+        .await;
+        assert_eq!(contents, ["Hello ", ""]);
+        assert_eq!(finish_reason, Some(FinishReason::Stop));
+    }
+
+    #[tokio::test]
+    async fn releases_held_back_content_that_is_not_a_stop_sequence() {
+        let (contents, finish_reason) = enforce(
+            "END",
+            vec![
+                content_chunk("Hello E", None),
+                content_chunk("very", None),
+                content_chunk(" bod", Some("length")),
+            ],
+        )
+        .await;
+        assert_eq!(contents.concat(), "Hello Every bod");
+        assert_eq!(contents[0], "Hello ");
+        assert_eq!(finish_reason, Some(FinishReason::Length));
+    }
+
+    #[tokio::test]
+    async fn flushes_held_back_content_when_the_stream_ends() {
+        let (contents, _) = enforce("\n\n", vec![content_chunk("Line\n", None)]).await;
+        assert_eq!(contents, ["Line", "\n"]);
+    }
+}
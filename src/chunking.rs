@@ -0,0 +1,331 @@
+use bon::Builder;
+
+/// Counts tokens in a piece of text.
+///
+/// Any `Fn(&str) -> usize` closure is a token counter, so an exact tokenizer can be
+/// plugged in when one is available.
+pub trait TokenCounter {
+    fn count(&self, text: &str) -> usize;
+}
+
+impl<F> TokenCounter for F
+where
+    F: Fn(&str) -> usize,
+{
+    fn count(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// Estimates roughly four characters per token, which holds well for English text on
+/// the BPE and WordPiece tokenizers used by DeepInfra-hosted models.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ApproximateTokenCounter;
+
+impl TokenCounter for ApproximateTokenCounter {
+    fn count(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+}
+
+/// Boundaries a [`Chunker`] prefers to split on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChunkingStrategy {
+    /// Pack words up to the token limit regardless of sentence structure.
+    #[default]
+    FixedSize,
+    /// Keep sentences whole whenever they fit.
+    Sentence,
+    /// Keep paragraphs (separated by blank lines) whole whenever they fit.
+    Paragraph,
+}
+
+/// A slice of the input text produced by a [`Chunker`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chunk {
+    /// The chunk text, copied verbatim from the input.
+    pub text: String,
+    /// Byte offset of the chunk start in the input.
+    pub start: usize,
+    /// Byte offset one past the chunk end in the input.
+    pub end: usize,
+    /// Token count of `text` according to the counter used.
+    pub tokens: usize,
+}
+
+/// Splits text into token-bounded chunks with optional overlap.
+///
+/// The defaults (512 tokens, 64 tokens overlap) fit the context window of the BGE and
+/// E5 embedding models hosted on DeepInfra. Units that are too large on their own are
+/// split into words; a single word longer than the limit becomes its own chunk.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chunking::{Chunker, ChunkingStrategy};
+///
+/// let chunker = Chunker::builder()
+///     .max_tokens(8)
+///     .overlap_tokens(0)
+///     .strategy(ChunkingStrategy::Sentence)
+///     .build();
+///
+/// let chunks = chunker.chunk("The first sentence is here. The second one follows it.");
+/// assert_eq!(chunks.len(), 2);
+/// assert_eq!(chunks[1].text, "The second one follows it.");
+/// ```
+#[derive(Clone, Debug, Builder)]
+pub struct Chunker {
+    /// Maximum number of tokens per chunk.
+    #[builder(default = 512)]
+    max_tokens: usize,
+    /// Number of tokens from the end of a chunk repeated at the start of the next one.
+    #[builder(default = 64)]
+    overlap_tokens: usize,
+    /// Preferred split boundaries.
+    #[builder(default)]
+    strategy: ChunkingStrategy,
+}
+
+impl Default for Chunker {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// A unit of text identified by its byte range and token count.
+#[derive(Clone, Copy)]
+struct Unit {
+    start: usize,
+    end: usize,
+    tokens: usize,
+}
+
+impl Chunker {
+    /// Chunks `text` using the [`ApproximateTokenCounter`].
+    pub fn chunk(&self, text: &str) -> Vec<Chunk> {
+        self.chunk_with(text, &ApproximateTokenCounter)
+    }
+
+    /// Chunks `text` using the given token counter.
+    pub fn chunk_with(&self, text: &str, counter: &impl TokenCounter) -> Vec<Chunk> {
+        let ranges = match self.strategy {
+            ChunkingStrategy::FixedSize => words(text, 0, text.len()),
+            ChunkingStrategy::Sentence => sentences(text),
+            ChunkingStrategy::Paragraph => paragraphs(text),
+        };
+
+        // Break up units that cannot fit into a single chunk.
+        let mut units = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            let tokens = counter.count(&text[start..end]);
+            if tokens > self.max_tokens && self.strategy != ChunkingStrategy::FixedSize {
+                units.extend(
+                    words(text, start, end)
+                        .into_iter()
+                        .map(|(start, end)| Unit {
+                            start,
+                            end,
+                            tokens: counter.count(&text[start..end]),
+                        }),
+                );
+            } else {
+                units.push(Unit { start, end, tokens });
+            }
+        }
+
+        let mut chunks = Vec::new();
+        let mut current: Vec<Unit> = Vec::new();
+        let mut current_tokens = 0;
+
+        for unit in units {
+            if !current.is_empty() && current_tokens + unit.tokens > self.max_tokens {
+                chunks.push(self.make_chunk(text, &current, counter));
+
+                // Carry the tail of the previous chunk over as overlap.
+                let mut overlap = 0;
+                let keep = current
+                    .iter()
+                    .rev()
+                    .take_while(|unit| {
+                        overlap += unit.tokens;
+                        overlap <= self.overlap_tokens
+                    })
+                    .count();
+                current.drain(..current.len() - keep);
+                current_tokens = current.iter().map(|unit| unit.tokens).sum();
+
+                while !current.is_empty() && current_tokens + unit.tokens > self.max_tokens {
+                    current_tokens -= current.remove(0).tokens;
+                }
+            }
+
+            current_tokens += unit.tokens;
+            current.push(unit);
+        }
+        if !current.is_empty() {
+            chunks.push(self.make_chunk(text, &current, counter));
+        }
+
+        chunks
+    }
+
+    fn make_chunk(&self, text: &str, units: &[Unit], counter: &impl TokenCounter) -> Chunk {
+        let start = units[0].start;
+        let end = units[units.len() - 1].end;
+        let text = text[start..end].to_string();
+        Chunk {
+            tokens: counter.count(&text),
+            text,
+            start,
+            end,
+        }
+    }
+}
+
+/// Byte ranges of the whitespace-separated words in `text[from..to]`.
+fn words(text: &str, from: usize, to: usize) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (offset, c) in text[from..to].char_indices() {
+        let index = from + offset;
+        match (c.is_whitespace(), start) {
+            (true, Some(word_start)) => {
+                ranges.push((word_start, index));
+                start = None;
+            }
+            (false, None) => start = Some(index),
+            _ => {}
+        }
+    }
+    if let Some(word_start) = start {
+        ranges.push((word_start, to));
+    }
+    ranges
+}
+
+/// Byte ranges of sentences, ending after `.`, `!` or `?` followed by whitespace.
+fn sentences(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let at_boundary = matches!(c, '.' | '!' | '?')
+            && chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        if at_boundary {
+            push_trimmed(text, start, index + c.len_utf8(), &mut ranges);
+            start = index + c.len_utf8();
+        }
+    }
+    push_trimmed(text, start, text.len(), &mut ranges);
+    ranges
+}
+
+/// Byte ranges of paragraphs, separated by one or more blank lines.
+fn paragraphs(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            push_trimmed(text, start, offset, &mut ranges);
+            start = offset + line.len();
+        }
+        offset += line.len();
+    }
+    push_trimmed(text, start, text.len(), &mut ranges);
+    ranges
+}
+
+fn push_trimmed(text: &str, start: usize, end: usize, ranges: &mut Vec<(usize, usize)>) {
+    let slice = &text[start..end];
+    let trimmed = slice.trim_start();
+    let start = start + (slice.len() - trimmed.len());
+    let end = start + trimmed.trim_end().len();
+    if start < end {
+        ranges.push((start, end));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunker(max_tokens: usize, overlap_tokens: usize, strategy: ChunkingStrategy) -> Chunker {
+        Chunker::builder()
+            .max_tokens(max_tokens)
+            .overlap_tokens(overlap_tokens)
+            .strategy(strategy)
+            .build()
+    }
+
+    fn words_counter(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    #[test]
+    fn returns_no_chunks_for_empty_input() {
+        for strategy in [
+            ChunkingStrategy::FixedSize,
+            ChunkingStrategy::Sentence,
+            ChunkingStrategy::Paragraph,
+        ] {
+            let chunker = chunker(8, 2, strategy);
+            assert!(chunker.chunk("").is_empty());
+            assert!(chunker.chunk(" \n\n \t").is_empty());
+        }
+    }
+
+    #[test]
+    fn overlaps_consecutive_chunks() {
+        let chunks =
+            chunker(4, 2, ChunkingStrategy::FixedSize).chunk_with("a b c d e f g", &words_counter);
+        let texts: Vec<_> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        assert_eq!(texts, ["a b c d", "c d e f", "e f g"]);
+    }
+
+    #[test]
+    fn advances_when_overlap_reaches_the_chunk_size() {
+        let text = "a b c d e f g h";
+        for overlap in [3, 4, 100] {
+            let chunks =
+                chunker(3, overlap, ChunkingStrategy::FixedSize).chunk_with(text, &words_counter);
+            assert!(chunks.iter().all(|chunk| chunk.tokens <= 3));
+            assert!(chunks.windows(2).all(|pair| pair[0].end < pair[1].end));
+            assert_eq!(chunks.last().unwrap().end, text.len());
+        }
+    }
+
+    #[test]
+    fn splits_on_multibyte_boundaries() {
+        let text = "Ça va? Très bien! Déjà vu… 日本語のテキスト。 Ünïcödé wörds everywhere.";
+        for strategy in [
+            ChunkingStrategy::FixedSize,
+            ChunkingStrategy::Sentence,
+            ChunkingStrategy::Paragraph,
+        ] {
+            let chunks = chunker(3, 1, strategy).chunk(text);
+            assert!(!chunks.is_empty());
+            for chunk in chunks {
+                assert_eq!(chunk.text, &text[chunk.start..chunk.end]);
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_sentences_whole_when_they_fit() {
+        let chunks = chunker(5, 0, ChunkingStrategy::Sentence).chunk_with(
+            "One two three. Four five! Six seven eight nine ten eleven.",
+            &words_counter,
+        );
+        let texts: Vec<_> = chunks.iter().map(|chunk| chunk.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "One two three. Four five!",
+                "Six seven eight nine ten",
+                "eleven."
+            ]
+        );
+    }
+}
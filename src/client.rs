@@ -1,19 +1,178 @@
 use std::env;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
+use crate::api_error::request_id;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models",
+    feature = "deploy"
+))]
+use crate::endpoints::EndpointUrls;
+use crate::interceptor::Interceptors;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models"
+))]
+use crate::metadata::ResponseMetadata;
+use crate::model_aliases::ModelAliases;
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+use crate::model_limits::StreamPermit;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference"
+))]
+use crate::model_limits::{ModelLimits, ModelPermit};
+#[cfg(feature = "chat_completition")]
+use crate::post_processing::PostProcessingPipeline;
+#[cfg(all(
+    feature = "rate_limit",
+    any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference"
+    )
+))]
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::redact::RedactPolicy;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "inference",
+    feature = "deploy"
+))]
+use crate::serialization::SharedSerializer;
+use crate::shutdown::Lifecycle;
 use bon::bon;
-use http::{HeaderMap, HeaderValue};
-use reqwest::Client;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "inference",
+    feature = "deploy"
+))]
+use futures::{future, stream};
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models",
+    feature = "deploy"
+))]
+use http::Method;
+use http::{header, HeaderMap, HeaderValue};
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "inference",
+    feature = "deploy"
+))]
+use reqwest::Body;
+use reqwest::{Client, RequestBuilder, Response};
+use secrecy::{ExposeSecret, SecretString};
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "inference",
+    feature = "deploy"
+))]
+use serde::Serialize;
 use thiserror::Error;
+use tracing::{debug, debug_span, field, Instrument};
+
+/// The DeepInfra API root that endpoint paths are resolved against by default.
+pub const DEFAULT_BASE_URL: &str = "https://api.deepinfra.com/v1";
 
 const APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
 /// A client for interacting with the DeepInfra API.
 ///
-/// This struct encapsulates an HTTP client with default settings and headers required for authentication.
+/// This struct encapsulates an HTTP client and the headers required for authentication.
 #[derive(Clone, Debug)]
 pub struct DeepinfraClient {
     /// The underlying HTTP client used for sending requests.
     pub(crate) client: Client,
+    /// Authorization and user agent headers added to every request.
+    pub(crate) headers: HeaderMap,
+    /// Headers hidden from `Debug` output.
+    pub(crate) redact: Arc<RedactPolicy>,
+    /// Base URL and request limits, which can be replaced while the client is in use.
+    pub(crate) settings: Arc<RwLock<Arc<Settings>>>,
+    /// Turns request bodies into bytes.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "inference",
+        feature = "deploy"
+    ))]
+    pub(crate) serializer: SharedSerializer,
+    /// Post-processors applied to assistant content before it is returned.
+    #[cfg(feature = "chat_completition")]
+    pub(crate) post_processing: PostProcessingPipeline,
+    /// Hooks called around every HTTP request.
+    pub(crate) interceptors: Interceptors,
+    /// In-flight request tracking used for graceful shutdown.
+    pub(crate) lifecycle: Arc<Lifecycle>,
+    /// Default timeout of each request, overridable per request.
+    pub(crate) timeout: Option<Duration>,
+    /// Request bodies larger than this many bytes are sent gzip-compressed.
+    #[cfg(all(
+        feature = "gzip",
+        any(
+            feature = "chat_completition",
+            feature = "inference",
+            feature = "deploy"
+        )
+    ))]
+    pub(crate) compress_requests_above: Option<usize>,
+}
+
+/// The part of the client configuration that can be reloaded at runtime.
+#[derive(Clone, Debug)]
+pub(crate) struct Settings {
+    /// The API root that endpoint paths are resolved against, without trailing slash.
+    pub(crate) base_url: String,
+    /// URLs of endpoints served elsewhere than the base URL.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models",
+        feature = "deploy"
+    ))]
+    pub(crate) endpoint_urls: EndpointUrls,
+    /// Per-model concurrency and throughput limits.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference"
+    ))]
+    pub(crate) model_limits: ModelLimits,
+    /// Logical model names and the models they stand for.
+    pub(crate) model_aliases: ModelAliases,
+    /// Client-wide concurrency and throughput limits.
+    #[cfg(all(
+        feature = "rate_limit",
+        any(
+            feature = "chat_completition",
+            feature = "audio_transcription",
+            feature = "inference"
+        )
+    ))]
+    pub(crate) rate_limiter: RateLimiter,
+}
+
+/// Slots held in the client's request limits while a request is in flight.
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference"
+))]
+pub(crate) struct RequestPermit {
+    _model: ModelPermit,
+    #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+    _stream: StreamPermit,
+    #[cfg(feature = "rate_limit")]
+    _client: Option<tokio::sync::OwnedSemaphorePermit>,
 }
 
 /// Errors that can occur when building a DeepinfraClient.
@@ -21,10 +180,13 @@ pub struct DeepinfraClient {
 pub enum DeepinfraClientBuilderError {
     /// Indicates that building the HTTP client failed.
     #[error("Could not build client {0}")]
-    ReqwestError(#[from] reqwest::Error),
+    Transport(#[from] reqwest::Error),
     /// Indicates that an invalid header value was provided.
     #[error("Invalid header value {0}")]
     InvalidHeaderValue(#[from] http::header::InvalidHeaderValue),
+    /// Indicates that the base URL is not a valid URL.
+    #[error("Invalid base URL {0}")]
+    InvalidBaseUrl(#[from] url::ParseError),
 }
 
 #[bon]
@@ -38,25 +200,395 @@ impl DeepinfraClient {
     ///
     /// fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let token = "your_api_token";
-    ///     let client = DeepinfraClient::new(token)?;
+    ///     let client = DeepinfraClient::builder().token(token).build()?;
     ///     // Use client for further API calls...
     ///     Ok(())
     /// }
     /// ```
+    ///
+    /// `http_client` lets the caller configure proxies, TLS roots, connection pooling
+    /// and timeouts on their own `reqwest::Client`; the authorization header and user
+    /// agent are then added to each request instead of to the client.
+    ///
+    /// `timeout` bounds every request from sending it until its response body has been
+    /// read; streamed responses included. Requests with their own `timeout`, such as
+    /// long transcriptions, override it.
+    ///
+    /// `interceptors` are called around every HTTP request, e.g. to add headers or
+    /// record metrics; see [`Interceptors`].
+    ///
+    /// `serializer` replaces the JSON serializer used for request bodies; see
+    /// [`BodySerializer`](crate::serialization::BodySerializer).
+    ///
+    /// The token is kept as a [`SecretString`] and only exposed to build the
+    /// authorization header, which is never printed by `Debug`. `redact` hides further
+    /// headers, request and response alike; see [`RedactPolicy`].
+    ///
+    /// `base_url` replaces the DeepInfra API root ([`DEFAULT_BASE_URL`]), e.g. to go
+    /// through a proxy, a mock server or an OpenAI-compatible gateway. Endpoint paths
+    /// such as `openai/chat/completions` are appended to it. `endpoint_urls` sends
+    /// individual endpoints to other URLs; see [`EndpointUrls`].
+    ///
+    /// With the `gzip` feature, `compress_requests_above` enables gzip compression of
+    /// request bodies larger than the given number of bytes. This mostly pays off for
+    /// long-context chat requests sent over slow uplinks. With the `rate_limit`
+    /// feature, `rate_limit` caps the request rate across all endpoints.
     #[builder]
-    pub fn new(token: &str) -> Result<Self, DeepinfraClientBuilderError> {
-        // Create headers with authorization token.
+    pub fn new(
+        #[builder(into)] token: SecretString,
+        #[builder(default = DEFAULT_BASE_URL)] base_url: &str,
+        #[cfg(any(
+            feature = "chat_completition",
+            feature = "audio_transcription",
+            feature = "inference",
+            feature = "models",
+            feature = "deploy"
+        ))]
+        #[builder(default)]
+        endpoint_urls: EndpointUrls,
+        http_client: Option<Client>,
+        timeout: Option<Duration>,
+        #[cfg(any(
+            feature = "chat_completition",
+            feature = "inference",
+            feature = "deploy"
+        ))]
+        #[builder(default, into)]
+        serializer: SharedSerializer,
+        #[cfg(feature = "chat_completition")]
+        #[builder(default)]
+        post_processing: PostProcessingPipeline,
+        #[builder(default)] interceptors: Interceptors,
+        #[cfg(any(
+            feature = "chat_completition",
+            feature = "audio_transcription",
+            feature = "inference"
+        ))]
+        #[builder(default)]
+        model_limits: ModelLimits,
+        #[builder(default)] model_aliases: ModelAliases,
+        #[builder(default)] redact: RedactPolicy,
+        #[cfg(all(
+            feature = "rate_limit",
+            any(
+                feature = "chat_completition",
+                feature = "audio_transcription",
+                feature = "inference"
+            )
+        ))]
+        rate_limit: Option<RateLimit>,
+        #[cfg(all(
+            feature = "gzip",
+            any(
+                feature = "chat_completition",
+                feature = "inference",
+                feature = "deploy"
+            )
+        ))]
+        compress_requests_above: Option<usize>,
+    ) -> Result<Self, DeepinfraClientBuilderError> {
+        let base_url = normalize_base_url(base_url)?;
+        #[cfg(any(
+            feature = "chat_completition",
+            feature = "audio_transcription",
+            feature = "inference",
+            feature = "models",
+            feature = "deploy"
+        ))]
+        let endpoint_urls = endpoint_urls.normalize()?;
+
+        // Create headers with authorization token and user agent.
         let mut headers = HeaderMap::new();
-        let bearer = format!("Bearer {token}");
-        headers.insert("Authorization", HeaderValue::from_str(&bearer)?);
+        headers.insert(header::AUTHORIZATION, bearer(&token)?);
+        headers.insert(header::USER_AGENT, HeaderValue::from_static(APP_USER_AGENT));
+        redact.apply(&mut headers);
 
-        // Create a client with default headers and user agent.
-        let client = Client::builder()
-            .default_headers(headers)
-            .user_agent(APP_USER_AGENT)
-            .build()?;
+        let client = match http_client {
+            Some(client) => client,
+            None => Client::builder().build()?,
+        };
 
         // Return the constructed DeepinfraClient.
-        Ok(DeepinfraClient { client })
+        Ok(DeepinfraClient {
+            client,
+            headers,
+            redact: Arc::new(redact),
+            settings: Arc::new(RwLock::new(Arc::new(Settings {
+                base_url,
+                #[cfg(any(
+                    feature = "chat_completition",
+                    feature = "audio_transcription",
+                    feature = "inference",
+                    feature = "models",
+                    feature = "deploy"
+                ))]
+                endpoint_urls,
+                #[cfg(any(
+                    feature = "chat_completition",
+                    feature = "audio_transcription",
+                    feature = "inference"
+                ))]
+                model_limits,
+                model_aliases,
+                #[cfg(all(
+                    feature = "rate_limit",
+                    any(
+                        feature = "chat_completition",
+                        feature = "audio_transcription",
+                        feature = "inference"
+                    )
+                ))]
+                rate_limiter: rate_limit.map(RateLimiter::from).unwrap_or_default(),
+            }))),
+            #[cfg(any(
+                feature = "chat_completition",
+                feature = "inference",
+                feature = "deploy"
+            ))]
+            serializer,
+            #[cfg(feature = "chat_completition")]
+            post_processing,
+            interceptors,
+            lifecycle: Arc::default(),
+            timeout,
+            #[cfg(all(
+                feature = "gzip",
+                any(
+                    feature = "chat_completition",
+                    feature = "inference",
+                    feature = "deploy"
+                )
+            ))]
+            compress_requests_above,
+        })
+    }
+
+    /// A copy of the client that authenticates with `token`, e.g. a key supplied by a
+    /// customer.
+    ///
+    /// The copy shares everything else with this client: its connection pool,
+    /// settings, request limits, interceptors and shutdown. Rate limits therefore
+    /// count the requests of both tokens together.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deepinfra_client_rs::client::DeepinfraClient;
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = DeepinfraClient::builder().token("your_api_token").build()?;
+    ///     let customer = client.with_token("customer_api_token")?;
+    ///     // Requests sent through `customer` are billed to the customer's account.
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_token(
+        &self,
+        token: impl Into<SecretString>,
+    ) -> Result<Self, DeepinfraClientBuilderError> {
+        let mut client = self.clone();
+        client
+            .headers
+            .insert(header::AUTHORIZATION, bearer(&token.into())?);
+        self.redact.apply(&mut client.headers);
+        Ok(client)
+    }
+
+    /// The current settings; a reload does not affect the returned snapshot.
+    pub(crate) fn settings(&self) -> Arc<Settings> {
+        // A writer cannot panic while holding the lock, so it is never poisoned.
+        Arc::clone(
+            &self
+                .settings
+                .read()
+                .unwrap_or_else(|error| error.into_inner()),
+        )
     }
+
+    /// Replaces the settings for every clone of the client.
+    #[cfg(feature = "hot_reload")]
+    pub(crate) fn replace_settings(&self, settings: Settings) {
+        *self
+            .settings
+            .write()
+            .unwrap_or_else(|error| error.into_inner()) = Arc::new(settings);
+    }
+
+    /// The model that `model` currently stands for, resolving
+    /// [aliases](crate::model_aliases::ModelAliases).
+    pub fn resolve_model(&self, model: &str) -> String {
+        self.settings().model_aliases.resolve(model).to_string()
+    }
+
+    /// Waits until a request to `model` fits in the client's limits.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference"
+    ))]
+    pub(crate) async fn throttle(&self, model: &str) -> RequestPermit {
+        let settings = self.settings();
+        RequestPermit {
+            _model: settings.model_limits.acquire(model).await,
+            #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+            _stream: None,
+            #[cfg(feature = "rate_limit")]
+            _client: settings.rate_limiter.acquire().await,
+        }
+    }
+
+    /// Waits until a stream to `model` fits in the client's limits, including the
+    /// per-model stream limits; the permit is meant to be held while the stream is
+    /// open.
+    #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+    pub(crate) async fn throttle_stream(&self, model: &str) -> RequestPermit {
+        // Queued streams wait for a stream slot before taking a request slot.
+        let stream = self.settings().model_limits.acquire_stream(model).await;
+        RequestPermit {
+            _stream: stream,
+            ..self.throttle(model).await
+        }
+    }
+
+    /// Resolves an endpoint path against its URL override or the base URL.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models",
+        feature = "deploy"
+    ))]
+    pub(crate) fn url(&self, path: &str) -> String {
+        let settings = self.settings();
+        settings
+            .endpoint_urls
+            .resolve(path)
+            .unwrap_or_else(|| format!("{}/{path}", settings.base_url))
+    }
+
+    /// Builds a request to `path` carrying the authorization header and the default
+    /// timeout.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models",
+        feature = "deploy"
+    ))]
+    pub(crate) fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, self.url(path))
+            .headers(self.headers.clone());
+        with_timeout(request, self.timeout)
+    }
+
+    /// Sends `request` through the interceptors.
+    ///
+    /// The exchange runs in an `http` tracing span, inside the span of the calling
+    /// endpoint method, that records the status and request ID of the response.
+    pub(crate) async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let started = Instant::now();
+        let (client, request) = request.build_split();
+        let result = match request {
+            Ok(mut request) => {
+                self.interceptors.on_request(&mut request);
+                let span = debug_span!(
+                    "http",
+                    method = %request.method(),
+                    path = request.url().path(),
+                    status = field::Empty,
+                    request_id = field::Empty,
+                );
+                let result = client.execute(request).instrument(span.clone()).await;
+                if let Ok(response) = &result {
+                    span.record("status", response.status().as_u16());
+                    if let Some(request_id) = request_id(response.headers()) {
+                        span.record("request_id", request_id.as_str());
+                    }
+                    span.in_scope(|| debug!("Received response"));
+                }
+                result
+            }
+            Err(error) => Err(error),
+        };
+        match &result {
+            Ok(response) => self.interceptors.on_response(response, started.elapsed()),
+            Err(error) => self.interceptors.on_error(error, started.elapsed()),
+        }
+        result
+    }
+
+    /// Captures the metadata of `response`, hiding redacted headers.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models"
+    ))]
+    pub(crate) fn response_metadata(
+        &self,
+        response: &Response,
+        started: Instant,
+    ) -> ResponseMetadata {
+        let mut metadata = ResponseMetadata::from_response(response, started);
+        self.redact.apply(&mut metadata.headers);
+        metadata
+    }
+
+    /// Builds a POST request to `path` with `body` as JSON, compressed if it is large
+    /// enough.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "inference",
+        feature = "deploy"
+    ))]
+    pub(crate) fn post_json(&self, path: &str, body: &impl Serialize) -> RequestBuilder {
+        let request = self
+            .request(Method::POST, path)
+            .header(header::CONTENT_TYPE, "application/json");
+
+        let json = match self.serializer.serialize(body) {
+            Ok(json) => json,
+            // Fail the request when it is sent, like `RequestBuilder::json` does.
+            Err(error) => {
+                let error = stream::once(future::ready(Err::<Vec<u8>, _>(error)));
+                return request.body(Body::wrap_stream(error));
+            }
+        };
+
+        #[cfg(feature = "gzip")]
+        if let Some(compressed) = self
+            .compress_requests_above
+            .and_then(|min_size| crate::compression::gzip_json(&json, min_size))
+        {
+            return request
+                .header(header::CONTENT_ENCODING, "gzip")
+                .body(compressed);
+        }
+
+        request.body(json)
+    }
+}
+
+/// Overrides the timeout of `request` if `timeout` is set.
+pub(crate) fn with_timeout(request: RequestBuilder, timeout: Option<Duration>) -> RequestBuilder {
+    match timeout {
+        Some(timeout) => request.timeout(timeout),
+        None => request,
+    }
+}
+
+/// Validates `base_url` and strips its trailing slash.
+pub(crate) fn normalize_base_url(base_url: &str) -> Result<String, url::ParseError> {
+    // Validate the base URL once so requests cannot fail on it later.
+    url::Url::parse(base_url)?;
+    Ok(base_url.trim_end_matches('/').to_string())
+}
+
+/// The authorization header for `token`, marked sensitive.
+fn bearer(token: &SecretString) -> Result<HeaderValue, http::header::InvalidHeaderValue> {
+    let mut bearer = HeaderValue::from_str(&format!("Bearer {}", token.expose_secret()))?;
+    bearer.set_sensitive(true);
+    Ok(bearer)
 }
@@ -0,0 +1,17 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// Gzip-compresses `json` if it exceeds `min_size` bytes.
+///
+/// Returns `None` when the body is small enough to be sent as is, or when it could
+/// not be compressed, in which case the caller sends it uncompressed.
+pub(crate) fn gzip_json(json: &[u8], min_size: usize) -> Option<Vec<u8>> {
+    if json.len() <= min_size {
+        return None;
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(json).ok()?;
+    encoder.finish().ok()
+}
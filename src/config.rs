@@ -0,0 +1,211 @@
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+/// A configuration file format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Guesses the format from the extension of `path`.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(ConfigFormat::Json),
+            "toml" => Some(ConfigFormat::Toml),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Unknown configuration format for {0}")]
+    UnknownFormat(PathBuf),
+    #[error("Invalid JSON configuration: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("Invalid TOML configuration: {0}")]
+    TomlError(#[from] toml::de::Error),
+    #[error("Invalid YAML configuration: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+}
+
+/// Parses a request, or any other deserializable value, from configuration text.
+///
+/// Request fields left out of the configuration take the same defaults as the
+/// request builders, and the messages of a chat completion may be omitted so the
+/// result can seed a [`RequestTemplate`](crate::request_template::RequestTemplate).
+/// Unknown fields, such as a misspelled parameter, are rejected.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::ChatCompletionRequest;
+/// use deepinfra_client_rs::config::{from_str, ConfigFormat};
+///
+/// let request: ChatCompletionRequest = from_str(
+///     r#"
+///     model = "meta-llama/Meta-Llama-3.1-8B-Instruct"
+///     temperature = 0.2
+///
+///     [[messages]]
+///     role = "system"
+///     content = "Answer in one sentence."
+///     "#,
+///     ConfigFormat::Toml,
+/// )?;
+///
+/// let json = serde_json::to_value(&request)?;
+/// assert_eq!(json["model"], "meta-llama/Meta-Llama-3.1-8B-Instruct");
+/// assert_eq!(json["temperature"], 0.2);
+/// assert_eq!(json["top_p"], 1.0);
+/// assert_eq!(json["max_tokens"], 100000);
+/// assert_eq!(json["messages"][0]["role"], "system");
+///
+/// let request: ChatCompletionRequest = from_str("temperature: 0.7\n", ConfigFormat::Yaml)?;
+/// let json = serde_json::to_value(&request)?;
+/// assert_eq!(json["model"], "deepseek-ai/DeepSeek-V3");
+/// assert_eq!(json["messages"], serde_json::json!([]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn from_str<T: DeserializeOwned>(text: &str, format: ConfigFormat) -> Result<T, ConfigError> {
+    Ok(match format {
+        ConfigFormat::Json => serde_json::from_str(text)?,
+        ConfigFormat::Toml => toml::from_str(text)?,
+        ConfigFormat::Yaml => serde_yaml::from_str(text)?,
+    })
+}
+
+/// Reads and parses a configuration file, picking the format from its extension.
+///
+/// The file is read on every call, so calling it again after the file changed
+/// picks up the new configuration without restarting the application.
+///
+/// # Example
+///
+/// ```no_run
+/// use deepinfra_client_rs::audio_transcription::AudioTranscriptionRequest;
+/// use deepinfra_client_rs::config;
+///
+/// // transcription.yaml:
+/// //
+/// // model: openai/whisper-large-v3
+/// // language: it
+/// // source:
+/// //   filepath: meeting.mp3
+/// let request: AudioTranscriptionRequest = config::load("transcription.yaml")?;
+/// # Ok::<(), config::ConfigError>(())
+/// ```
+pub fn load<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T, ConfigError> {
+    let path = path.as_ref();
+    let format =
+        ConfigFormat::from_path(path).ok_or_else(|| ConfigError::UnknownFormat(path.into()))?;
+    from_str(&std::fs::read_to_string(path)?, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(deny_unknown_fields)]
+    struct Parameters {
+        model: String,
+        temperature: f64,
+    }
+
+    #[test]
+    fn parses_every_format() {
+        let sources = [
+            (
+                r#"{ "model": "m", "temperature": 0.5 }"#,
+                ConfigFormat::Json,
+            ),
+            ("model = \"m\"\ntemperature = 0.5\n", ConfigFormat::Toml),
+            ("model: m\ntemperature: 0.5\n", ConfigFormat::Yaml),
+        ];
+        for (text, format) in sources {
+            let parameters: Parameters = from_str(text, format).unwrap();
+            assert_eq!(
+                parameters,
+                Parameters {
+                    model: "m".to_string(),
+                    temperature: 0.5
+                },
+                "{format:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_malformed_input_per_format() {
+        let error = from_str::<Parameters>("{ \"model\": ", ConfigFormat::Json);
+        assert!(matches!(error, Err(ConfigError::JsonError(_))));
+        let error = from_str::<Parameters>("model = ", ConfigFormat::Toml);
+        assert!(matches!(error, Err(ConfigError::TomlError(_))));
+        let error = from_str::<Parameters>("model: [m\n", ConfigFormat::Yaml);
+        assert!(matches!(error, Err(ConfigError::YamlError(_))));
+        let error = from_str::<Parameters>("model: m\ntemperature: hot\n", ConfigFormat::Yaml);
+        assert!(matches!(error, Err(ConfigError::YamlError(_))));
+    }
+
+    #[test]
+    fn keeps_malformed_files_apart_from_response_decoding() {
+        let error = from_str::<Parameters>("{ \"model\": ", ConfigFormat::Json).unwrap_err();
+        let error = crate::error::DeepinfraError::from(error);
+        assert!(
+            matches!(error, crate::error::DeepinfraError::Other(_)),
+            "{error:?}"
+        );
+    }
+
+    #[test]
+    fn reports_unknown_fields_per_format() {
+        let sources = [
+            (r#"{ "model": "m", "temprature": 0.5 }"#, ConfigFormat::Json),
+            ("model = \"m\"\ntemprature = 0.5\n", ConfigFormat::Toml),
+            ("model: m\ntemprature: 0.5\n", ConfigFormat::Yaml),
+        ];
+        for (text, format) in sources {
+            let error = from_str::<Parameters>(text, format)
+                .expect_err("misspelled field accepted")
+                .to_string();
+            assert!(error.contains("unknown field `temprature`"), "{error}");
+        }
+    }
+
+    #[cfg(feature = "chat_completition")]
+    #[test]
+    fn rejects_unknown_request_fields() {
+        use crate::chat_completition::ChatCompletionRequest;
+
+        let request: ChatCompletionRequest =
+            from_str("temperature: 0.5\n", ConfigFormat::Yaml).unwrap();
+        assert_eq!(serde_json::to_value(request).unwrap()["temperature"], 0.5);
+
+        let error = from_str::<ChatCompletionRequest>("temprature: 0.5\n", ConfigFormat::Yaml);
+        assert!(matches!(error, Err(ConfigError::YamlError(_))));
+    }
+
+    #[test]
+    fn guesses_the_format_from_the_extension() {
+        let format = |path: &str| ConfigFormat::from_path(Path::new(path));
+        assert_eq!(format("request.json"), Some(ConfigFormat::Json));
+        assert_eq!(format("request.TOML"), Some(ConfigFormat::Toml));
+        assert_eq!(format("request.yml"), Some(ConfigFormat::Yaml));
+        assert_eq!(format("request.ini"), None);
+        assert_eq!(format("request"), None);
+    }
+
+    #[test]
+    fn rejects_files_with_unknown_extensions() {
+        let error = load::<Parameters>("request.ini");
+        assert!(matches!(error, Err(ConfigError::UnknownFormat(_))));
+    }
+}
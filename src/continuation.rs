@@ -0,0 +1,217 @@
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse, FinishReason, Message,
+    Usage,
+};
+use crate::client::DeepinfraClient;
+use bon::Builder;
+use std::future::Future;
+use tracing::instrument;
+
+/// Limits for [`DeepinfraClient::chat_completion_with_continuation`].
+#[derive(Clone, Debug, Builder)]
+pub struct ContinuationOptions {
+    /// Maximum number of follow-up requests issued after the first one.
+    #[builder(default = 4)]
+    pub max_continuations: u32,
+    /// Upper bound on completion tokens generated across all requests.
+    pub max_total_tokens: Option<u32>,
+}
+
+impl Default for ContinuationOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl DeepinfraClient {
+    /// Sends a chat completion request and keeps generating while the model stops
+    /// because it ran out of tokens.
+    ///
+    /// Whenever the first choice finishes with `length`, the partial output is
+    /// [prefilled](ChatCompletionRequest::prefill) as an assistant message and the
+    /// request is re-issued, until the model stops on its own or `options` are
+    /// exhausted. The returned response contains the stitched content in its first
+    /// choice, the finish reason of the last request and the summed usage.
+    ///
+    /// Post-processors registered on the client run once, on the stitched content.
+    ///
+    /// Only the first choice is continued, so `n` should be left at 1.
+    #[instrument(skip(self, request))]
+    pub async fn chat_completion_with_continuation(
+        &self,
+        request: ChatCompletionRequest,
+        options: ContinuationOptions,
+    ) -> Result<ChatCompletionResponse, ChatCompletionError> {
+        let mut response = continue_generation(request, &options, |request| async move {
+            self.chat_completion_unprocessed(&request).await
+        })
+        .await?;
+        self.post_process(&mut response)?;
+        Ok(response)
+    }
+}
+
+/// Runs the continuation loop, sending each step through `send`.
+async fn continue_generation<F, Fut>(
+    mut request: ChatCompletionRequest,
+    options: &ContinuationOptions,
+    mut send: F,
+) -> Result<ChatCompletionResponse, ChatCompletionError>
+where
+    F: FnMut(ChatCompletionRequest) -> Fut,
+    Fut: Future<Output = Result<ChatCompletionResponse, ChatCompletionError>>,
+{
+    // A trailing assistant message supplied by the caller is kept as a prefix.
+    let prefix = match request.messages.last() {
+        Some(Message::Assistant(message)) => message.content.clone(),
+        _ => String::new(),
+    };
+    let mut stitched = String::new();
+    let mut usage: Option<Usage> = None;
+    let mut continuations = 0;
+
+    loop {
+        let mut response = send(request.clone()).await?;
+
+        if let Some(step) = response.usage.take() {
+            usage = Some(match usage {
+                Some(total) => total + step,
+                None => step,
+            });
+        }
+
+        let Some(choice) = response.choices.first_mut() else {
+            response.usage = usage;
+            return Ok(response);
+        };
+        if let Message::Assistant(message) = &choice.message {
+            stitched.push_str(&message.content);
+        }
+
+        let generated = usage.as_ref().map_or(0, |usage| usage.completion_tokens);
+        let remaining = options
+            .max_total_tokens
+            .map(|max| max.saturating_sub(generated.max(0) as u32));
+        let exhausted = continuations >= options.max_continuations || remaining == Some(0);
+
+        if choice.finish_reason != FinishReason::Length || exhausted {
+            if let Message::Assistant(message) = &mut choice.message {
+                message.content = stitched;
+            }
+            response.usage = usage;
+            return Ok(response);
+        }
+
+        // Resume from the partial output, replacing the previous prefill.
+        if matches!(request.messages.last(), Some(Message::Assistant(_))) {
+            request.messages.pop();
+        }
+        request = request.prefill(format!("{prefix}{stitched}"));
+        if let Some(remaining) = remaining {
+            request.max_tokens = request.max_tokens.min(remaining);
+        }
+        continuations += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat_completition::UserMessage;
+    use crate::post_processing::{PostProcessingPipeline, TrimWhitespace};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn response(content: &str, finish_reason: &str) -> ChatCompletionResponse {
+        serde_json::from_value(json!({
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": finish_reason,
+            }],
+            "usage": { "prompt_tokens": 10, "completion_tokens": 2, "total_tokens": 12 },
+        }))
+        .unwrap()
+    }
+
+    fn request() -> ChatCompletionRequest {
+        ChatCompletionRequest::builder()
+            .messages(vec![Message::User(
+                UserMessage::builder().content("Tell a story.").build(),
+            )])
+            .build()
+    }
+
+    /// Replays `steps` and records the requests that were sent.
+    async fn run(
+        request: ChatCompletionRequest,
+        options: &ContinuationOptions,
+        steps: &[(&str, &str)],
+    ) -> (ChatCompletionResponse, Vec<serde_json::Value>) {
+        let sent = Mutex::new(Vec::new());
+        let response = continue_generation(request, options, |request| {
+            let mut sent = sent.lock().unwrap();
+            let (content, finish_reason) = steps[sent.len()];
+            sent.push(serde_json::to_value(&request).unwrap());
+            async move { Ok(response(content, finish_reason)) }
+        })
+        .await
+        .unwrap();
+        (response, sent.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn stitches_raw_fragments() {
+        let steps = [("the ", "length"), ("cat", "stop")];
+        let (response, sent) = run(request(), &ContinuationOptions::default(), &steps).await;
+
+        assert_eq!(response.content(), Some("the cat"));
+        assert_eq!(response.usage.as_ref().unwrap().completion_tokens, 4);
+        assert_eq!(sent.len(), 2);
+
+        // Post-processing the stitched text once keeps the seam intact.
+        let pipeline = PostProcessingPipeline::new().with(TrimWhitespace);
+        let content = response.content().unwrap().to_string();
+        assert_eq!(pipeline.apply(content).unwrap(), "the cat");
+    }
+
+    #[tokio::test]
+    async fn continues_the_final_message() {
+        let steps = [("one ", "length"), ("two ", "length"), ("three", "stop")];
+        let (_, sent) = run(request(), &ContinuationOptions::default(), &steps).await;
+
+        assert!(sent[0].get("continue_final_message").is_none());
+        for (step, prefill) in [(1, "one "), (2, "one two ")] {
+            let messages = sent[step]["messages"].as_array().unwrap();
+            assert_eq!(messages.len(), 2);
+            assert_eq!(messages[1]["role"], "assistant");
+            assert_eq!(messages[1]["content"], prefill);
+            assert_eq!(sent[step]["continue_final_message"], true);
+            assert_eq!(sent[step]["add_generation_prompt"], false);
+        }
+    }
+
+    #[tokio::test]
+    async fn keeps_a_caller_prefix() {
+        let steps = [("1, 2", "length"), (", 3]", "stop")];
+        let (response, sent) = run(
+            request().prefill("["),
+            &ContinuationOptions::default(),
+            &steps,
+        )
+        .await;
+
+        assert_eq!(response.content(), Some("1, 2, 3]"));
+        assert_eq!(sent[1]["messages"][1]["content"], "[1, 2");
+    }
+
+    #[tokio::test]
+    async fn stops_after_max_continuations() {
+        let options = ContinuationOptions::builder().max_continuations(1).build();
+        let steps = [("a", "length"), ("b", "length"), ("c", "stop")];
+        let (response, sent) = run(request(), &options, &steps).await;
+
+        assert_eq!(sent.len(), 2);
+        assert_eq!(response.content(), Some("ab"));
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use crate::chat_completition::ContentPart;
+use base64::Engine;
+
+/// Default limit on the size of an inline image, before base64 encoding.
+pub const DEFAULT_MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DataUrlError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Unsupported image format, expected PNG, JPEG, GIF or WebP")]
+    UnsupportedFormat,
+    #[error("Image is {size} bytes, more than the limit of {limit} bytes")]
+    TooLarge { size: u64, limit: u64 },
+}
+
+/// Detects the MIME type of PNG, JPEG, GIF and WebP images from their first bytes.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::data_url::image_mime_type;
+///
+/// assert_eq!(image_mime_type(b"\x89PNG\r\n\x1a\n..."), Some("image/png"));
+/// assert_eq!(image_mime_type(b"GIF89a..."), Some("image/gif"));
+/// assert_eq!(image_mime_type(b"%PDF-1.7"), None);
+/// ```
+pub fn image_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', ..] => Some("image/png"),
+        [0xff, 0xd8, 0xff, ..] => Some("image/jpeg"),
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Builds a `data:image/...;base64,` URL from image bytes.
+///
+/// The MIME type is detected from the content, so a mislabelled file cannot produce
+/// a URL the API rejects.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::data_url::{image_data_url, DataUrlError, DEFAULT_MAX_IMAGE_BYTES};
+///
+/// let url = image_data_url(b"GIF89a\x01\x00\x01\x00", DEFAULT_MAX_IMAGE_BYTES)?;
+/// assert_eq!(url, "data:image/gif;base64,R0lGODlhAQABAA==");
+///
+/// assert!(matches!(
+///     image_data_url(b"not an image", DEFAULT_MAX_IMAGE_BYTES),
+///     Err(DataUrlError::UnsupportedFormat)
+/// ));
+/// assert!(matches!(
+///     image_data_url(b"GIF89a\x01\x00\x01\x00", 4),
+///     Err(DataUrlError::TooLarge { size: 10, limit: 4 })
+/// ));
+/// # Ok::<(), DataUrlError>(())
+/// ```
+pub fn image_data_url(bytes: &[u8], max_bytes: u64) -> Result<String, DataUrlError> {
+    check_size(bytes.len() as u64, max_bytes)?;
+    let mime_type = image_mime_type(bytes).ok_or(DataUrlError::UnsupportedFormat)?;
+    let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Ok(format!("data:{mime_type};base64,{data}"))
+}
+
+/// Builds a `data:` URL from the image file at `path`.
+///
+/// The size is checked before the file is read.
+pub fn image_file_data_url(path: impl AsRef<Path>, max_bytes: u64) -> Result<String, DataUrlError> {
+    let path = path.as_ref();
+    check_size(std::fs::metadata(path)?.len(), max_bytes)?;
+    image_data_url(&std::fs::read(path)?, max_bytes)
+}
+
+fn check_size(size: u64, limit: u64) -> Result<(), DataUrlError> {
+    if size > limit {
+        return Err(DataUrlError::TooLarge { size, limit });
+    }
+    Ok(())
+}
+
+impl ContentPart {
+    /// An image part embedding `bytes`, with the MIME type detected from the content
+    /// and the size checked against [`DEFAULT_MAX_IMAGE_BYTES`].
+    pub fn image_data(bytes: &[u8]) -> Result<Self, DataUrlError> {
+        Ok(Self::image_url(image_data_url(
+            bytes,
+            DEFAULT_MAX_IMAGE_BYTES,
+        )?))
+    }
+
+    /// An image part embedding the file at `path`, like [`ContentPart::image_data`].
+    pub fn image_file_checked(path: impl AsRef<Path>) -> Result<Self, DataUrlError> {
+        Ok(Self::image_url(image_file_data_url(
+            path,
+            DEFAULT_MAX_IMAGE_BYTES,
+        )?))
+    }
+}
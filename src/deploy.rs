@@ -0,0 +1,408 @@
+use crate::api_error::{read_json, ApiError, DecodeError};
+use crate::client::DeepinfraClient;
+use crate::endpoints::Endpoint;
+use crate::pagination::{Page, Paginated, CURSOR_PARAM};
+use crate::shutdown::ShutdownError;
+use bon::Builder;
+use futures::FutureExt;
+use http::Method;
+use secrecy::{ExposeSecret, SecretString};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize, Serializer};
+use tracing::instrument;
+
+const DEPLOYMENTS_PATH: &str = Endpoint::Deployments.path();
+
+/// The GPU a deployment runs on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum GpuType {
+    /// NVIDIA A100 with 80 GB of memory.
+    A100,
+    /// NVIDIA H100 with 80 GB of memory.
+    H100,
+    /// NVIDIA H200 with 141 GB of memory.
+    H200,
+    /// NVIDIA L40S with 48 GB of memory.
+    L40S,
+    /// A GPU this crate does not know about, sent verbatim.
+    Other(String),
+}
+
+impl GpuType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            GpuType::A100 => "A100-80GB",
+            GpuType::H100 => "H100-80GB",
+            GpuType::H200 => "H200-141GB",
+            GpuType::L40S => "L40S-48GB",
+            GpuType::Other(gpu) => gpu,
+        }
+    }
+}
+
+impl From<String> for GpuType {
+    fn from(gpu: String) -> Self {
+        match gpu.as_str() {
+            "A100-80GB" => GpuType::A100,
+            "H100-80GB" => GpuType::H100,
+            "H200-141GB" => GpuType::H200,
+            "L40S-48GB" => GpuType::L40S,
+            _ => GpuType::Other(gpu),
+        }
+    }
+}
+
+impl From<GpuType> for String {
+    fn from(gpu: GpuType) -> Self {
+        match gpu {
+            GpuType::Other(gpu) => gpu,
+            gpu => gpu.as_str().to_string(),
+        }
+    }
+}
+
+/// How many instances of a deployment may run.
+#[derive(Clone, Debug, Serialize, Deserialize, Builder)]
+pub struct ScalingSettings {
+    /// Instances kept running without traffic; 0 lets the deployment scale to zero.
+    #[builder(default = 0)]
+    pub min_instances: u32,
+    #[builder(default = 1)]
+    pub max_instances: u32,
+}
+
+impl Default for ScalingSettings {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// A model repository on Hugging Face.
+#[derive(Clone, Debug, Serialize, Builder)]
+pub struct HuggingFaceRepo {
+    /// Repository name, e.g. "my-org/my-model".
+    #[builder(into)]
+    pub repo: String,
+    /// Branch, tag or commit to deploy (default: the main branch).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(into)]
+    pub revision: Option<String>,
+    /// Access token for private repositories.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_token"
+    )]
+    #[builder(into)]
+    pub token: Option<SecretString>,
+}
+
+fn serialize_token<S: Serializer>(
+    token: &Option<SecretString>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    token
+        .as_ref()
+        .map(ExposeSecret::expose_secret)
+        .serialize(serializer)
+}
+
+/// The weights a deployment serves.
+#[derive(Clone, Debug)]
+pub enum ModelSource {
+    /// A full model.
+    HuggingFace(HuggingFaceRepo),
+    /// A LoRA adapter applied to a base model hosted by DeepInfra.
+    Lora {
+        base_model: String,
+        adapter: HuggingFaceRepo,
+    },
+}
+
+impl ModelSource {
+    /// A full model from Hugging Face.
+    pub fn hugging_face(repo: HuggingFaceRepo) -> Self {
+        ModelSource::HuggingFace(repo)
+    }
+
+    /// A LoRA `adapter` applied to `base_model`.
+    pub fn lora(base_model: impl Into<String>, adapter: HuggingFaceRepo) -> Self {
+        ModelSource::Lora {
+            base_model: base_model.into(),
+            adapter,
+        }
+    }
+}
+
+/// A request to deploy a custom model.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::deploy::*;
+///
+/// let request = DeploymentRequest::builder()
+///     .model_name("my-org/support-bot")
+///     .gpu(GpuType::H100)
+///     .source(ModelSource::lora(
+///         "meta-llama/Meta-Llama-3.1-8B-Instruct",
+///         HuggingFaceRepo::builder().repo("my-org/support-lora").build(),
+///     ))
+///     .scaling(ScalingSettings::builder().max_instances(2).build())
+///     .build();
+///
+/// let json = serde_json::to_value(&request)?;
+/// assert_eq!(json["gpu"], "H100-80GB");
+/// assert_eq!(json["base_model"], "meta-llama/Meta-Llama-3.1-8B-Instruct");
+/// assert_eq!(json["hf"]["repo"], "my-org/support-lora");
+/// assert_eq!(json["settings"]["max_instances"], 2);
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+#[derive(Clone, Debug, Serialize, Builder)]
+#[serde(into = "WireDeploymentRequest")]
+pub struct DeploymentRequest {
+    /// Name the deployed model is called by, e.g. "my-org/my-model".
+    #[builder(into)]
+    model_name: String,
+    gpu: GpuType,
+    /// GPUs per instance.
+    #[builder(default = 1)]
+    num_gpus: u32,
+    /// Maximum number of requests batched together.
+    max_batch_size: Option<u32>,
+    source: ModelSource,
+    #[builder(default)]
+    scaling: ScalingSettings,
+}
+
+/// The body of a [`DeploymentRequest`] as the API expects it.
+#[derive(Serialize)]
+struct WireDeploymentRequest {
+    model_name: String,
+    gpu: GpuType,
+    num_gpus: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_batch_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_model: Option<String>,
+    hf: HuggingFaceRepo,
+    settings: ScalingSettings,
+}
+
+impl From<DeploymentRequest> for WireDeploymentRequest {
+    fn from(request: DeploymentRequest) -> Self {
+        let (base_model, hf) = match request.source {
+            ModelSource::HuggingFace(repo) => (None, repo),
+            ModelSource::Lora {
+                base_model,
+                adapter,
+            } => (Some(base_model), adapter),
+        };
+        Self {
+            model_name: request.model_name,
+            gpu: request.gpu,
+            num_gpus: request.num_gpus,
+            max_batch_size: request.max_batch_size,
+            base_model,
+            hf,
+            settings: request.scaling,
+        }
+    }
+}
+
+/// The lifecycle state of a deployment.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum DeploymentStatus {
+    Initializing,
+    Deploying,
+    /// Serving requests.
+    Running,
+    Failed,
+    Deleted,
+    /// A status this crate does not know about, kept verbatim.
+    Other(String),
+}
+
+impl DeploymentStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DeploymentStatus::Initializing => "initializing",
+            DeploymentStatus::Deploying => "deploying",
+            DeploymentStatus::Running => "running",
+            DeploymentStatus::Failed => "failed",
+            DeploymentStatus::Deleted => "deleted",
+            DeploymentStatus::Other(status) => status,
+        }
+    }
+}
+
+impl From<String> for DeploymentStatus {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "initializing" => DeploymentStatus::Initializing,
+            "deploying" => DeploymentStatus::Deploying,
+            "running" => DeploymentStatus::Running,
+            "failed" => DeploymentStatus::Failed,
+            "deleted" => DeploymentStatus::Deleted,
+            _ => DeploymentStatus::Other(status),
+        }
+    }
+}
+
+impl From<DeploymentStatus> for String {
+    fn from(status: DeploymentStatus) -> Self {
+        match status {
+            DeploymentStatus::Other(status) => status,
+            status => status.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for DeploymentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A custom model deployment.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Deployment {
+    pub deploy_id: String,
+    pub model_name: String,
+    pub status: DeploymentStatus,
+    /// Why the deployment failed, when `status` is `Failed`.
+    pub fail_reason: Option<String>,
+    pub task: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreatedDeployment {
+    deploy_id: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeployError {
+    #[error("Request error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Could not decode response: {0}")]
+    DecodeError(#[from] DecodeError),
+    #[error("Client shutdown: {0}")]
+    ShutdownError(#[from] ShutdownError),
+}
+
+impl DeepinfraClient {
+    /// Deploys a custom model and returns the ID of the new deployment.
+    ///
+    /// The deployment starts in the `Initializing` state; poll
+    /// [`DeepinfraClient::deployment`] until it is `Running`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::deploy::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), DeployError> {
+    /// let request = DeploymentRequest::builder()
+    ///     .model_name("my-org/my-model")
+    ///     .gpu(GpuType::A100)
+    ///     .source(ModelSource::hugging_face(
+    ///         HuggingFaceRepo::builder()
+    ///             .repo("my-org/my-model")
+    ///             .token("hf_...")
+    ///             .build(),
+    ///     ))
+    ///     .build();
+    ///
+    /// let deploy_id = client.create_deployment(request).await?;
+    /// let deployment = client.deployment(&deploy_id).await?;
+    /// println!("{}", deployment.status);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request))]
+    pub async fn create_deployment(
+        &self,
+        request: DeploymentRequest,
+    ) -> Result<String, DeployError> {
+        self.lifecycle
+            .track(async {
+                let path = format!("{DEPLOYMENTS_PATH}/llm");
+                let response = self.send(self.post_json(&path, &request)).await?;
+                let response = ApiError::check(response).await?;
+                let created: CreatedDeployment = read_json(response).await??;
+                Ok(created.deploy_id)
+            })
+            .await?
+    }
+
+    /// Lists the deployments of the account, fetching all pages.
+    #[instrument(skip(self))]
+    pub async fn list_deployments(&self) -> Result<Vec<Deployment>, DeployError> {
+        self.list_deployments_stream().collect_all().await
+    }
+
+    /// Streams the deployments of the account, fetching the next page when the
+    /// previous one is used up.
+    pub fn list_deployments_stream(&self) -> Paginated<Deployment, DeployError> {
+        let client = self.clone();
+        let fetch = move |cursor| {
+            let client = client.clone();
+            async move {
+                client
+                    .lifecycle
+                    .track(client.send_list_deployments(cursor))
+                    .await?
+            }
+            .boxed()
+        };
+        Paginated::new(Box::new(fetch), |deployment| deployment.deploy_id.clone())
+    }
+
+    /// Fetches the status of a deployment.
+    #[instrument(skip(self))]
+    pub async fn deployment(&self, deploy_id: &str) -> Result<Deployment, DeployError> {
+        self.lifecycle
+            .track(self.send_get_deployment(deploy_id))
+            .await?
+    }
+
+    /// Deletes a deployment.
+    #[instrument(skip(self))]
+    pub async fn delete_deployment(&self, deploy_id: &str) -> Result<(), DeployError> {
+        self.lifecycle
+            .track(async {
+                let path = format!("{DEPLOYMENTS_PATH}/{deploy_id}");
+                let response = self.send(self.request(Method::DELETE, &path)).await?;
+                ApiError::check(response).await?;
+                Ok(())
+            })
+            .await?
+    }
+
+    async fn send_list_deployments(
+        &self,
+        cursor: Option<String>,
+    ) -> Result<Page<Deployment>, DeployError> {
+        let mut request = self.request(Method::GET, &format!("{DEPLOYMENTS_PATH}/list"));
+        if let Some(cursor) = cursor {
+            request = request.query(&[(CURSOR_PARAM, cursor)]);
+        }
+        let response = ApiError::check(self.send(request).await?).await?;
+        Ok(read_json(response).await??)
+    }
+
+    async fn send_get_deployment<T: DeserializeOwned>(
+        &self,
+        suffix: &str,
+    ) -> Result<T, DeployError> {
+        let path = format!("{DEPLOYMENTS_PATH}/{suffix}");
+        let response = self.send(self.request(Method::GET, &path)).await?;
+        let response = ApiError::check(response).await?;
+        Ok(read_json(response).await??)
+    }
+}
@@ -0,0 +1,234 @@
+use std::io::Write;
+use std::time::Duration;
+
+use crate::api_error::ApiError;
+use crate::client::{with_timeout, DeepinfraClient};
+use crate::shutdown::ShutdownError;
+use bon::Builder;
+use http::{header, Method};
+use reqwest::RequestBuilder;
+use tracing::{instrument, warn};
+use url::Url;
+
+/// Limits applied when downloading a generated asset.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::download::DownloadOptions;
+///
+/// let options = DownloadOptions::builder()
+///     .max_bytes(20 * 1024 * 1024)
+///     .retries(3)
+///     .build();
+/// ```
+#[derive(Clone, Debug, Builder)]
+pub struct DownloadOptions {
+    /// Fails the download once the asset exceeds this many bytes.
+    pub max_bytes: Option<u64>,
+    /// How often a download is retried after a timeout, connection error, rate limit
+    /// or server error.
+    #[builder(default = 2)]
+    pub retries: u32,
+    /// Delay before the first retry, doubled for every further one. A `Retry-After`
+    /// header takes precedence.
+    #[builder(default = Duration::from_millis(500))]
+    pub backoff: Duration,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DownloadError {
+    #[error("Request error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error("Asset exceeds the limit of {0} bytes")]
+    TooLarge(u64),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Client shutdown: {0}")]
+    ShutdownError(#[from] ShutdownError),
+}
+
+impl DownloadError {
+    /// Whether retrying the download may succeed.
+    fn is_retryable(&self) -> bool {
+        match self {
+            DownloadError::ApiError(error) => error.is_retryable(),
+            DownloadError::Transport(error) => error.is_timeout() || error.is_connect(),
+            _ => false,
+        }
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        match self {
+            DownloadError::ApiError(ApiError::RateLimited { retry_after, .. }) => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl DeepinfraClient {
+    /// Downloads an asset returned as a URL, such as a hosted image, video or audio
+    /// file, with the default [`DownloadOptions`].
+    ///
+    /// The request goes through the client's HTTP client. The authorization header is
+    /// only sent to the host of the base URL and to DeepInfra domains, never to third
+    /// party hosts. Relative URLs are resolved against the base URL.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::download::DownloadError;
+    /// # async fn run(client: DeepinfraClient, url: &str) -> Result<(), DownloadError> {
+    /// let bytes = client.download(url).await?;
+    /// std::fs::write("image.png", bytes)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn download(&self, url: &str) -> Result<Vec<u8>, DownloadError> {
+        self.download_with(url, &DownloadOptions::default()).await
+    }
+
+    /// Downloads an asset into memory with custom limits.
+    #[instrument(skip(self))]
+    pub async fn download_with(
+        &self,
+        url: &str,
+        options: &DownloadOptions,
+    ) -> Result<Vec<u8>, DownloadError> {
+        let mut bytes = Vec::new();
+        self.download_to(url, options, &mut bytes).await?;
+        Ok(bytes)
+    }
+
+    /// Streams an asset into `writer` and returns the number of bytes written.
+    ///
+    /// A retry after a partial download starts over, so `writer` may receive the
+    /// beginning of the asset more than once; download into memory when that matters.
+    #[instrument(skip(self, writer))]
+    pub async fn download_to(
+        &self,
+        url: &str,
+        options: &DownloadOptions,
+        writer: &mut (impl Write + Send),
+    ) -> Result<u64, DownloadError> {
+        let url = self.asset_url(url)?;
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .lifecycle
+                .track(self.send_download(&url, options, &mut *writer))
+                .await?;
+            match result {
+                Err(error) if attempt < options.retries && error.is_retryable() => {
+                    let delay = error
+                        .retry_after()
+                        .unwrap_or(options.backoff * 2u32.saturating_pow(attempt));
+                    warn!(%error, attempt, ?delay, "Download failed, retrying");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Resolves `url` against the base URL.
+    fn asset_url(&self, url: &str) -> Result<Url, url::ParseError> {
+        Url::parse(&format!("{}/", self.settings().base_url))?.join(url)
+    }
+
+    /// Builds a GET request to `url` with the client timeout, authorized only for
+    /// trusted hosts.
+    fn asset_request(&self, url: &Url) -> Result<RequestBuilder, url::ParseError> {
+        let base_url = Url::parse(&self.settings().base_url)?;
+        let deepinfra = url
+            .host_str()
+            .is_some_and(|host| host == "deepinfra.com" || host.ends_with(".deepinfra.com"));
+        let trusted = url.origin() == base_url.origin() || url.scheme() == "https" && deepinfra;
+
+        let mut headers = self.headers.clone();
+        if !trusted {
+            headers.remove(header::AUTHORIZATION);
+        }
+        let request = self
+            .client
+            .request(Method::GET, url.clone())
+            .headers(headers);
+        Ok(with_timeout(request, self.timeout))
+    }
+
+    async fn send_download(
+        &self,
+        url: &Url,
+        options: &DownloadOptions,
+        writer: &mut (impl Write + Send),
+    ) -> Result<u64, DownloadError> {
+        let response = self.send(self.asset_request(url)?).await?;
+        let mut response = ApiError::check(response).await?;
+
+        let max_bytes = options.max_bytes.unwrap_or(u64::MAX);
+        // The length header is only a hint, so the limit is also checked while reading.
+        if response
+            .content_length()
+            .is_some_and(|length| length > max_bytes)
+        {
+            return Err(DownloadError::TooLarge(max_bytes));
+        }
+
+        let mut written = 0;
+        while let Some(chunk) = response.chunk().await? {
+            written += chunk.len() as u64;
+            if written > max_bytes {
+                return Err(DownloadError::TooLarge(max_bytes));
+            }
+            writer.write_all(&chunk)?;
+        }
+        writer.flush()?;
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> DeepinfraClient {
+        DeepinfraClient::builder()
+            .token("token")
+            .timeout(Duration::from_secs(7))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn applies_the_client_timeout() {
+        let client = client();
+        let url = client.asset_url("/assets/image.png").unwrap();
+        let request = client.asset_request(&url).unwrap().build().unwrap();
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn authorizes_only_trusted_hosts() {
+        let client = client();
+        let trusted = Url::parse("https://cdn.deepinfra.com/image.png").unwrap();
+        let request = client.asset_request(&trusted).unwrap().build().unwrap();
+        assert!(request.headers().contains_key(header::AUTHORIZATION));
+
+        let untrusted = Url::parse("https://example.com/image.png").unwrap();
+        let request = client.asset_request(&untrusted).unwrap().build().unwrap();
+        assert!(!request.headers().contains_key(header::AUTHORIZATION));
+    }
+}
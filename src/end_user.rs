@@ -0,0 +1,92 @@
+use std::fmt::Write;
+
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+
+/// The end-user identifier sent in the `user` field of a request.
+///
+/// DeepInfra uses it to monitor and detect abuse per user; it must not contain
+/// identifying information such as an email address. Use [`UserHasher`] to derive one
+/// from an internal user ID.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EndUser(String);
+
+impl EndUser {
+    /// An identifier sent verbatim.
+    pub fn new(id: impl Into<String>) -> Self {
+        EndUser(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for EndUser {
+    fn from(id: String) -> Self {
+        EndUser(id)
+    }
+}
+
+impl From<&str> for EndUser {
+    fn from(id: &str) -> Self {
+        EndUser(id.to_string())
+    }
+}
+
+impl std::fmt::Display for EndUser {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Derives pseudonymous [`EndUser`] identifiers with HMAC-SHA256 under an
+/// application secret.
+///
+/// The same internal ID always yields the same identifier, so abuse reports can be
+/// traced back to a user by the application, while the identifier itself reveals
+/// nothing without the key. Keep the key secret and stable; rotating it changes
+/// every identifier.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::end_user::UserHasher;
+///
+/// let hasher = UserHasher::new(b"application secret");
+/// let user = hasher.hash("alice@example.com");
+///
+/// assert_eq!(user, hasher.hash("alice@example.com"));
+/// assert_ne!(user, hasher.hash("bob@example.com"));
+/// assert_eq!(user.as_str().len(), 64);
+/// assert!(!user.as_str().contains("alice"));
+/// ```
+#[derive(Clone)]
+pub struct UserHasher {
+    key: hmac::Key,
+}
+
+impl UserHasher {
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, key),
+        }
+    }
+
+    /// The hex-encoded HMAC of `internal_id`.
+    pub fn hash(&self, internal_id: &str) -> EndUser {
+        let tag = hmac::sign(&self.key, internal_id.as_bytes());
+        let mut hex = String::with_capacity(tag.as_ref().len() * 2);
+        for byte in tag.as_ref() {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        EndUser(hex)
+    }
+}
+
+impl std::fmt::Debug for UserHasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserHasher").finish_non_exhaustive()
+    }
+}
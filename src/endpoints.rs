@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models",
+    feature = "deploy"
+))]
+use crate::client::normalize_base_url;
+
+/// An API endpoint whose URL can be overridden.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Endpoint {
+    ChatCompletions,
+    AudioTranscriptions,
+    AudioTranslations,
+    /// The native inference API; the model name is appended to its URL.
+    Inference,
+    /// Status of queued inference jobs; the job ID is appended to its URL.
+    InferenceJobs,
+    Models,
+    /// Custom model deployments, which live at the API root rather than under the
+    /// versioned base URL.
+    Deployments,
+}
+
+impl Endpoint {
+    /// The path of the endpoint relative to the base URL.
+    pub const fn path(self) -> &'static str {
+        match self {
+            Endpoint::ChatCompletions => "openai/chat/completions",
+            Endpoint::AudioTranscriptions => "openai/audio/transcriptions",
+            Endpoint::AudioTranslations => "openai/audio/translations",
+            Endpoint::Inference => "inference",
+            Endpoint::InferenceJobs => "inference/requests",
+            Endpoint::Models => "openai/models",
+            Endpoint::Deployments => "../deploy",
+        }
+    }
+}
+
+/// Full URLs that replace the base URL and path of individual endpoints.
+///
+/// Endpoints without an override are resolved against the client's base URL, so a
+/// gateway serving chat and audio from different hosts only needs the endpoints
+/// that differ.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::client::DeepinfraClient;
+/// use deepinfra_client_rs::endpoints::{Endpoint, EndpointUrls};
+///
+/// let client = DeepinfraClient::builder()
+///     .token("your_api_token")
+///     .base_url("https://chat.gateway.example.com/v1")
+///     .endpoint_urls(EndpointUrls::new().set(
+///         Endpoint::AudioTranscriptions,
+///         "https://audio.gateway.example.com/v1/transcriptions",
+///     ))
+///     .build()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct EndpointUrls {
+    urls: HashMap<Endpoint, String>,
+}
+
+impl EndpointUrls {
+    /// Creates a set without overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends requests for `endpoint` to `url`, replacing any previous override.
+    pub fn set(mut self, endpoint: Endpoint, url: impl Into<String>) -> Self {
+        self.urls.insert(endpoint, url.into());
+        self
+    }
+
+    /// The override for `path`, with the rest of the path appended for endpoints
+    /// such as inference that take a suffix. The most specific endpoint wins, so
+    /// job status paths do not match the inference override.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models",
+        feature = "deploy"
+    ))]
+    pub(crate) fn resolve(&self, path: &str) -> Option<String> {
+        self.urls
+            .iter()
+            .filter_map(|(endpoint, url)| {
+                let url = match path.strip_prefix(endpoint.path())? {
+                    "" => url.clone(),
+                    rest => format!("{url}/{}", rest.strip_prefix('/')?),
+                };
+                Some((endpoint.path().len(), url))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map(|(_, url)| url)
+    }
+
+    /// Validates every URL and strips trailing slashes.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models",
+        feature = "deploy"
+    ))]
+    pub(crate) fn normalize(mut self) -> Result<Self, url::ParseError> {
+        for url in self.urls.values_mut() {
+            *url = normalize_base_url(url)?;
+        }
+        Ok(self)
+    }
+}
@@ -0,0 +1,432 @@
+use crate::api_error::{ApiError, DecodeError};
+use crate::shutdown::ShutdownError;
+
+/// A boxed module-specific error without a crate-wide equivalent.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A crate-wide error that every module-specific error converts into.
+///
+/// Applications that call several endpoints can use `?` to turn any error of this
+/// crate into a `DeepinfraError` and handle status codes, retries and request IDs in
+/// one place.
+///
+/// # Example
+///
+/// ```no_run
+/// # use deepinfra_client_rs::prelude::*;
+/// # use deepinfra_client_rs::chat_completition::*;
+/// use deepinfra_client_rs::error::DeepinfraError;
+///
+/// async fn ask(client: &DeepinfraClient, request: ChatCompletionRequest) -> Result<String, DeepinfraError> {
+///     let response = client.chat_completition(request).await?;
+///     Ok(response.content().unwrap_or_default().to_string())
+/// }
+/// ```
+#[derive(Debug, thiserror::Error)]
+pub enum DeepinfraError {
+    /// The API answered with a non-success status.
+    #[error("API error: {0}")]
+    Api(#[from] ApiError),
+    /// The request could not be sent or the response could not be read.
+    #[error("Transport error: {0}")]
+    Transport(#[from] reqwest::Error),
+    /// The response body, or a value parsed from it, did not have the expected shape.
+    #[error("Could not decode response: {0}")]
+    Decode(#[from] DecodeError),
+    /// The client is shutting down.
+    #[error("Client shutdown: {0}")]
+    Shutdown(#[from] ShutdownError),
+    /// Reading or writing a local file failed.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    /// An error specific to one module, such as a rejected post-processing step.
+    #[error("{0}")]
+    Other(BoxError),
+}
+
+impl DeepinfraError {
+    /// The HTTP status code of the failed response, if one was received.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            DeepinfraError::Api(error) => Some(error.status()),
+            DeepinfraError::Transport(error) => error.status().map(|status| status.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// The request identifier reported by the API, if a response was received.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            DeepinfraError::Api(error) => error.request_id(),
+            DeepinfraError::Decode(error) => error.request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The API error details, if the API answered with a non-success status.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            DeepinfraError::Api(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// The provider's explanation if the request was rejected by a content policy.
+    pub fn content_rejection(&self) -> Option<&str> {
+        self.api_error().and_then(ApiError::content_rejection)
+    }
+
+    /// Whether retrying the same request later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DeepinfraError::Api(error) => error.is_retryable(),
+            DeepinfraError::Transport(error) => {
+                error.is_timeout()
+                    || error.is_connect()
+                    || error
+                        .status()
+                        .is_some_and(|status| status.is_server_error())
+            }
+            _ => false,
+        }
+    }
+
+    fn other(error: impl std::error::Error + Send + Sync + 'static) -> Self {
+        DeepinfraError::Other(Box::new(error))
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::chat_completition::ChatCompletionError> for DeepinfraError {
+    fn from(error: crate::chat_completition::ChatCompletionError) -> Self {
+        use crate::chat_completition::ChatCompletionError;
+        match error {
+            ChatCompletionError::Transport(error) => error.into(),
+            ChatCompletionError::PostProcessError(error) => DeepinfraError::other(error),
+            ChatCompletionError::ShutdownError(error) => error.into(),
+            ChatCompletionError::DecodeError(error) => error.into(),
+            ChatCompletionError::ApiError(error) => error.into(),
+            error @ (ChatCompletionError::BufferOverflowError(_)
+            | ChatCompletionError::TooManyStopSequences(_)
+            | ChatCompletionError::UnsupportedReasoningParameter { .. }
+            | ChatCompletionError::InvalidReasoningBudget { .. }) => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "audio_transcription")]
+impl From<crate::audio_transcription::AudioTranscriptionError> for DeepinfraError {
+    fn from(error: crate::audio_transcription::AudioTranscriptionError) -> Self {
+        use crate::audio_transcription::AudioTranscriptionError;
+        match error {
+            AudioTranscriptionError::Transport(error) => error.into(),
+            AudioTranscriptionError::IoError(error) => error.into(),
+            AudioTranscriptionError::ShutdownError(error) => error.into(),
+            AudioTranscriptionError::ApiError(error) => error.into(),
+            AudioTranscriptionError::DecodeError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::data_url::DataUrlError> for DeepinfraError {
+    fn from(error: crate::data_url::DataUrlError) -> Self {
+        use crate::data_url::DataUrlError;
+        match error {
+            DataUrlError::IoError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(all(feature = "chat_completition", feature = "audio_transcription"))]
+impl From<crate::audio_summary::AudioSummaryError> for DeepinfraError {
+    fn from(error: crate::audio_summary::AudioSummaryError) -> Self {
+        use crate::audio_summary::AudioSummaryError;
+        match error {
+            AudioSummaryError::TranscriptionError(error) => error.into(),
+            AudioSummaryError::SummarizationError(error) => error.into(),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::attachments::AttachmentError> for DeepinfraError {
+    fn from(error: crate::attachments::AttachmentError) -> Self {
+        use crate::attachments::AttachmentError;
+        match error {
+            AttachmentError::IoError(error) => error.into(),
+            #[cfg(feature = "audio_transcription")]
+            AttachmentError::AudioTranscriptionError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::summarization::SummarizeError> for DeepinfraError {
+    fn from(error: crate::summarization::SummarizeError) -> Self {
+        use crate::summarization::SummarizeError;
+        match error {
+            SummarizeError::ChatCompletionError(error) => error.into(),
+            #[cfg(feature = "models")]
+            SummarizeError::ModelsError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::truncation::TruncationError> for DeepinfraError {
+    fn from(error: crate::truncation::TruncationError) -> Self {
+        use crate::truncation::TruncationError;
+        match error {
+            TruncationError::ChatCompletionError(error) => error.into(),
+            TruncationError::SummarizeError(error) => error.into(),
+        }
+    }
+}
+
+#[cfg(feature = "image_generation")]
+impl From<crate::image_generation::ImageGenerationError> for DeepinfraError {
+    fn from(error: crate::image_generation::ImageGenerationError) -> Self {
+        use crate::image_generation::ImageGenerationError;
+        match error {
+            ImageGenerationError::Transport(error) => error.into(),
+            ImageGenerationError::ApiError(error) => error.into(),
+            ImageGenerationError::DecodeError(error) => error.into(),
+            ImageGenerationError::IoError(error) => error.into(),
+            ImageGenerationError::ShutdownError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "inference")]
+impl From<crate::rerank::RerankError> for DeepinfraError {
+    fn from(error: crate::rerank::RerankError) -> Self {
+        use crate::rerank::RerankError;
+        match error {
+            RerankError::Transport(error) => error.into(),
+            RerankError::ApiError(error) => error.into(),
+            RerankError::DecodeError(error) => error.into(),
+            RerankError::ShutdownError(error) => error.into(),
+            error @ RerankError::ScoreCountMismatch { .. } => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "deploy")]
+impl From<crate::deploy::DeployError> for DeepinfraError {
+    fn from(error: crate::deploy::DeployError) -> Self {
+        use crate::deploy::DeployError;
+        match error {
+            DeployError::Transport(error) => error.into(),
+            DeployError::ApiError(error) => error.into(),
+            DeployError::DecodeError(error) => error.into(),
+            DeployError::ShutdownError(error) => error.into(),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<crate::image_input::ImageInputError> for DeepinfraError {
+    fn from(error: crate::image_input::ImageInputError) -> Self {
+        use crate::image_input::ImageInputError;
+        match error {
+            ImageInputError::IoError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "inference")]
+impl From<crate::inference::InferenceError> for DeepinfraError {
+    fn from(error: crate::inference::InferenceError) -> Self {
+        use crate::inference::InferenceError;
+        match error {
+            InferenceError::Transport(error) => error.into(),
+            InferenceError::ApiError(error) => error.into(),
+            InferenceError::DecodeError(error) => error.into(),
+            InferenceError::ShutdownError(error) => error.into(),
+        }
+    }
+}
+
+#[cfg(feature = "inference")]
+impl From<crate::jobs::JobError> for DeepinfraError {
+    fn from(error: crate::jobs::JobError) -> Self {
+        use crate::jobs::JobError;
+        match error {
+            JobError::Transport(error) => error.into(),
+            JobError::ApiError(error) => error.into(),
+            JobError::DecodeError(error) => error.into(),
+            JobError::ShutdownError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "models")]
+impl From<crate::models::ModelsError> for DeepinfraError {
+    fn from(error: crate::models::ModelsError) -> Self {
+        use crate::models::ModelsError;
+        match error {
+            ModelsError::Transport(error) => error.into(),
+            ModelsError::ApiError(error) => error.into(),
+            ModelsError::DecodeError(error) => error.into(),
+            ModelsError::ShutdownError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::batch::BatchError> for DeepinfraError {
+    fn from(error: crate::batch::BatchError) -> Self {
+        use crate::batch::BatchError;
+        match error {
+            BatchError::CheckpointError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::evaluation::EvaluationError> for DeepinfraError {
+    fn from(error: crate::evaluation::EvaluationError) -> Self {
+        use crate::evaluation::EvaluationError;
+        match error {
+            EvaluationError::ChatCompletionError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::quick::QuickError> for DeepinfraError {
+    fn from(error: crate::quick::QuickError) -> Self {
+        use crate::quick::QuickError;
+        match error {
+            QuickError::ChatCompletionError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::judge::JudgeError> for DeepinfraError {
+    fn from(error: crate::judge::JudgeError) -> Self {
+        use crate::judge::JudgeError;
+        match error {
+            JudgeError::ChatCompletionError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(all(feature = "chat_completition", feature = "schemars"))]
+impl From<crate::structured::StructuredOutputError> for DeepinfraError {
+    fn from(error: crate::structured::StructuredOutputError) -> Self {
+        use crate::structured::StructuredOutputError;
+        match error {
+            StructuredOutputError::ChatCompletionError(error) => error.into(),
+            StructuredOutputError::InvalidOutput(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::tool_runner::ToolRunnerError> for DeepinfraError {
+    fn from(error: crate::tool_runner::ToolRunnerError) -> Self {
+        use crate::tool_runner::ToolRunnerError;
+        match error {
+            ToolRunnerError::ChatCompletionError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "chat_completition")]
+impl From<crate::translation::TranslationError> for DeepinfraError {
+    fn from(error: crate::translation::TranslationError) -> Self {
+        use crate::translation::TranslationError;
+        match error {
+            TranslationError::ChatCompletionError(error) => error.into(),
+            TranslationError::InvalidJson(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "config")]
+impl From<crate::config::ConfigError> for DeepinfraError {
+    fn from(error: crate::config::ConfigError) -> Self {
+        use crate::config::ConfigError;
+        match error {
+            ConfigError::IoError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(all(feature = "chat_completition", feature = "config"))]
+impl From<crate::prompt_library::PromptLibraryError> for DeepinfraError {
+    fn from(error: crate::prompt_library::PromptLibraryError) -> Self {
+        use crate::prompt_library::PromptLibraryError;
+        match error {
+            PromptLibraryError::IoError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+#[cfg(feature = "hot_reload")]
+impl From<crate::hot_reload::HotReloadError> for DeepinfraError {
+    fn from(error: crate::hot_reload::HotReloadError) -> Self {
+        use crate::hot_reload::HotReloadError;
+        match error {
+            HotReloadError::ConfigError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+impl From<crate::download::DownloadError> for DeepinfraError {
+    fn from(error: crate::download::DownloadError) -> Self {
+        use crate::download::DownloadError;
+        match error {
+            DownloadError::Transport(error) => error.into(),
+            DownloadError::ApiError(error) => error.into(),
+            DownloadError::IoError(error) => error.into(),
+            DownloadError::ShutdownError(error) => error.into(),
+            error => DeepinfraError::other(error),
+        }
+    }
+}
+
+impl From<serde_json::Error> for DeepinfraError {
+    /// Wraps a JSON error raised on a value parsed from a response, such as tool
+    /// arguments or structured output, which has no body to quote.
+    fn from(source: serde_json::Error) -> Self {
+        DeepinfraError::Decode(DecodeError {
+            body_snippet: String::new(),
+            request_id: None,
+            source,
+        })
+    }
+}
+
+impl From<crate::client::DeepinfraClientBuilderError> for DeepinfraError {
+    fn from(error: crate::client::DeepinfraClientBuilderError) -> Self {
+        DeepinfraError::other(error)
+    }
+}
+
+impl From<crate::prompt_template::PromptTemplateError> for DeepinfraError {
+    fn from(error: crate::prompt_template::PromptTemplateError) -> Self {
+        DeepinfraError::other(error)
+    }
+}
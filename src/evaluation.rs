@@ -0,0 +1,97 @@
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse,
+};
+use crate::client::DeepinfraClient;
+use crate::prompt_template::{Bindings, PromptTemplate, PromptTemplateError};
+use futures::stream::{self, StreamExt};
+use tracing::instrument;
+
+/// Errors that can occur while evaluating a single set of bindings.
+#[derive(Debug, thiserror::Error)]
+pub enum EvaluationError {
+    #[error("Template error: {0}")]
+    TemplateError(#[from] PromptTemplateError),
+    #[error("Chat completion error: {0}")]
+    ChatCompletionError(#[from] ChatCompletionError),
+}
+
+/// The outcome of evaluating a template against one set of bindings.
+#[derive(Debug)]
+pub struct EvaluationResult {
+    /// The bindings this result was produced from.
+    pub bindings: Bindings,
+    /// The rendered prompt, if rendering succeeded.
+    pub prompt: Option<String>,
+    /// The model response, or the error that prevented it.
+    pub response: Result<ChatCompletionResponse, EvaluationError>,
+}
+
+impl DeepinfraClient {
+    /// Renders `template` once per entry in `bindings` and dispatches the resulting
+    /// requests with at most `concurrency` in flight at a time.
+    ///
+    /// `build_request` turns each rendered prompt into a full request, so model and
+    /// sampling parameters stay under the caller's control. Results are returned in
+    /// the same order as `bindings`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # use deepinfra_client_rs::prompt_template::*;
+    /// # async fn run(client: DeepinfraClient) {
+    /// let template = PromptTemplate::new("Summarize: {text}");
+    /// let bindings = vec![Bindings::from([("text".to_string(), "...".to_string())])];
+    ///
+    /// let results = client
+    ///     .evaluate(&template, bindings, 4, |prompt| {
+    ///         ChatCompletionRequest::builder()
+    ///             .messages(vec![Message::User(UserMessage::builder().content(prompt).build())])
+    ///             .build()
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    #[instrument(skip(self, template, bindings, build_request))]
+    pub async fn evaluate<F>(
+        &self,
+        template: &PromptTemplate,
+        bindings: Vec<Bindings>,
+        concurrency: usize,
+        build_request: F,
+    ) -> Vec<EvaluationResult>
+    where
+        F: Fn(String) -> ChatCompletionRequest,
+    {
+        let build_request = &build_request;
+
+        stream::iter(bindings)
+            .map(|bindings| async move {
+                let prompt = match template.render(&bindings) {
+                    Ok(prompt) => prompt,
+                    Err(error) => {
+                        return EvaluationResult {
+                            bindings,
+                            prompt: None,
+                            response: Err(error.into()),
+                        }
+                    }
+                };
+
+                let response = self
+                    .chat_completition(build_request(prompt.clone()))
+                    .await
+                    .map_err(EvaluationError::from);
+
+                EvaluationResult {
+                    bindings,
+                    prompt: Some(prompt),
+                    response,
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+}
@@ -0,0 +1,203 @@
+use crate::canonical::fnv1a;
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse, Message, SystemMessage,
+};
+use crate::client::DeepinfraClient;
+use bon::Builder;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use tracing::instrument;
+
+/// One configuration under test. Unset fields leave the incoming request untouched.
+#[derive(Clone, Debug, Builder)]
+pub struct ExperimentArm {
+    /// Label reported back on every response served by this arm.
+    #[builder(into)]
+    pub name: String,
+    /// Model to use instead of the request's model.
+    #[builder(into)]
+    pub model: Option<String>,
+    /// Sampling temperature to use instead of the request's temperature.
+    pub temperature: Option<f64>,
+    /// System prompt replacing any system message already in the request.
+    #[builder(into)]
+    pub system_prompt: Option<String>,
+}
+
+/// Which arm of an [`Experiment`] served a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arm {
+    A,
+    B,
+}
+
+/// Splits traffic between two [`ExperimentArm`]s.
+///
+/// Requests with a key are assigned deterministically from a stable hash of the key,
+/// so the same user always lands in the same arm. Requests without a key are
+/// assigned at random.
+///
+/// # Example
+///
+/// ```no_run
+/// # use deepinfra_client_rs::prelude::*;
+/// # use deepinfra_client_rs::chat_completition::*;
+/// # use deepinfra_client_rs::experiment::*;
+/// # async fn run(client: DeepinfraClient, request: ChatCompletionRequest) -> Result<(), ChatCompletionError> {
+/// let experiment = Experiment::builder()
+///     .a(ExperimentArm::builder().name("control").build())
+///     .b(ExperimentArm::builder()
+///         .name("llama")
+///         .model("meta-llama/Meta-Llama-3.1-70B-Instruct")
+///         .build())
+///     .b_share(0.1)
+///     .build();
+///
+/// let response = experiment.run(&client, Some("user-42"), request).await?;
+/// println!("served by {}", response.arm_name);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Builder)]
+pub struct Experiment {
+    /// The control configuration.
+    a: ExperimentArm,
+    /// The treatment configuration.
+    b: ExperimentArm,
+    /// Share of traffic, between 0 and 1, sent to arm B.
+    #[builder(default = 0.5)]
+    b_share: f64,
+}
+
+/// A chat completion response tagged with the arm that produced it.
+#[derive(Debug)]
+pub struct ExperimentResponse {
+    pub arm: Arm,
+    pub arm_name: String,
+    pub response: ChatCompletionResponse,
+}
+
+impl Experiment {
+    /// Picks the arm for a request, hashing `key` when present.
+    pub fn assign(&self, key: Option<&str>) -> Arm {
+        let hash = match key {
+            // FNV-1a barely changes its high bits for keys differing in the last
+            // bytes, such as sequential user IDs, so mix them in first.
+            Some(key) => fmix64(fnv1a(key.as_bytes())),
+            None => RandomState::new().hash_one(()),
+        };
+        // Map the hash onto [0, 1) and compare against the treatment share.
+        let position = (hash >> 11) as f64 / (1u64 << 53) as f64;
+        if position < self.b_share {
+            Arm::B
+        } else {
+            Arm::A
+        }
+    }
+
+    /// Returns the configuration for `arm`.
+    pub fn arm(&self, arm: Arm) -> &ExperimentArm {
+        match arm {
+            Arm::A => &self.a,
+            Arm::B => &self.b,
+        }
+    }
+
+    /// Assigns an arm, applies its overrides to `request` and sends it.
+    #[instrument(skip(self, client, request))]
+    pub async fn run(
+        &self,
+        client: &DeepinfraClient,
+        key: Option<&str>,
+        mut request: ChatCompletionRequest,
+    ) -> Result<ExperimentResponse, ChatCompletionError> {
+        let arm = self.assign(key);
+        let config = self.arm(arm);
+        config.apply(&mut request);
+
+        let response = client.chat_completition(request).await?;
+
+        Ok(ExperimentResponse {
+            arm,
+            arm_name: config.name.clone(),
+            response,
+        })
+    }
+}
+
+impl ExperimentArm {
+    pub(crate) fn apply(&self, request: &mut ChatCompletionRequest) {
+        if let Some(model) = &self.model {
+            request.model = model.clone();
+        }
+        if let Some(temperature) = self.temperature {
+            request.temperature = temperature;
+        }
+        if let Some(system_prompt) = &self.system_prompt {
+            request
+                .messages
+                .retain(|message| !matches!(message, Message::System(_)));
+            let system = SystemMessage::builder().content(system_prompt).build();
+            request.messages.insert(0, Message::System(system));
+        }
+    }
+}
+
+/// The 64-bit finalizer of MurmurHash3, which spreads every input bit over the
+/// whole output.
+fn fmix64(mut hash: u64) -> u64 {
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn experiment(b_share: f64) -> Experiment {
+        Experiment::builder()
+            .a(ExperimentArm::builder().name("a").build())
+            .b(ExperimentArm::builder().name("b").build())
+            .b_share(b_share)
+            .build()
+    }
+
+    fn b_count(experiment: &Experiment, keys: usize) -> usize {
+        (0..keys)
+            .filter(|i| experiment.assign(Some(&format!("user-{i}"))) == Arm::B)
+            .count()
+    }
+
+    #[test]
+    fn sequential_keys_follow_the_share() {
+        let even = b_count(&experiment(0.5), 10_000);
+        assert!((4_800..=5_200).contains(&even), "{even} of 10000 in B");
+
+        let small = b_count(&experiment(0.1), 10_000);
+        assert!((900..=1_100).contains(&small), "{small} of 10000 in B");
+    }
+
+    #[test]
+    fn keys_differing_in_the_last_byte_spread_over_both_arms() {
+        let b = b_count(&experiment(0.5), 10);
+        assert!(b > 0 && b < 10, "{b} of 10 in B");
+    }
+
+    #[test]
+    fn assignment_is_stable() {
+        let experiment = experiment(0.5);
+        assert_eq!(
+            experiment.assign(Some("user-42")),
+            experiment.assign(Some("user-42"))
+        );
+    }
+
+    #[test]
+    fn shares_of_zero_and_one_pick_a_single_arm() {
+        assert_eq!(b_count(&experiment(0.0), 1_000), 0);
+        assert_eq!(b_count(&experiment(1.0), 1_000), 1_000);
+    }
+}
@@ -0,0 +1,402 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use tracing::{instrument, warn};
+
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse,
+};
+use crate::chat_stream::ChatCompletionStream;
+use crate::client::DeepinfraClient;
+
+/// A client to fail over to, with the model names it uses.
+///
+/// Any client built with a custom [`base_url`](DeepinfraClient::builder) works, so
+/// the backend may be any OpenAI-compatible endpoint.
+#[derive(Clone, Debug)]
+pub struct FailoverBackend {
+    client: DeepinfraClient,
+    models: HashMap<String, String>,
+}
+
+impl FailoverBackend {
+    pub fn new(client: DeepinfraClient) -> Self {
+        Self {
+            client,
+            models: HashMap::new(),
+        }
+    }
+
+    /// Sends requests for `model` to this backend as `backend_model`. Models without
+    /// a mapping keep their name.
+    pub fn map_model(mut self, model: impl Into<String>, backend_model: impl Into<String>) -> Self {
+        self.models.insert(model.into(), backend_model.into());
+        self
+    }
+
+    fn prepare(&self, request: &ChatCompletionRequest) -> ChatCompletionRequest {
+        let mut request = request.clone();
+        if let Some(model) = self.models.get(&request.model) {
+            request.model = model.clone();
+        }
+        request
+    }
+}
+
+/// Sends chat completions to a primary client and falls back to the next backend
+/// when it is unavailable.
+///
+/// A backend counts as unavailable when the request cannot be sent, times out, or
+/// fails with a rate limit or server error. Other errors, such as invalid requests,
+/// are returned without trying the next backend.
+///
+/// # Example
+///
+/// ```no_run
+/// # use deepinfra_client_rs::prelude::*;
+/// # use deepinfra_client_rs::failover::*;
+/// # fn build() -> Result<FailoverClient, Box<dyn std::error::Error>> {
+/// let deepinfra = DeepinfraClient::builder().token("deepinfra_token").build()?;
+/// let gateway = DeepinfraClient::builder()
+///     .token("gateway_token")
+///     .base_url("https://llm-gateway.example.com/v1")
+///     .build()?;
+///
+/// let client = FailoverClient::new(deepinfra).fallback(
+///     FailoverBackend::new(gateway).map_model("deepseek-ai/DeepSeek-V3", "deepseek-chat"),
+/// );
+/// # Ok(client)
+/// # }
+/// ```
+///
+/// Endpoint paths such as `openai/chat/completions` are appended to each backend's
+/// base URL, so a fallback must serve the DeepInfra path layout, e.g. through a
+/// gateway or a rewriting proxy.
+#[derive(Clone, Debug)]
+pub struct FailoverClient {
+    backends: Vec<FailoverBackend>,
+}
+
+impl FailoverClient {
+    /// Creates a failover client that tries `primary` first.
+    pub fn new(primary: DeepinfraClient) -> Self {
+        Self {
+            backends: vec![FailoverBackend::new(primary)],
+        }
+    }
+
+    /// Adds a backend tried after the ones added before it.
+    pub fn fallback(mut self, backend: FailoverBackend) -> Self {
+        self.backends.push(backend);
+        self
+    }
+
+    /// Sends a chat completion to the first available backend.
+    #[instrument(skip(self, request))]
+    pub async fn chat_completition(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, ChatCompletionError> {
+        let (last, rest) = self.backends.split_last().expect("a primary backend");
+        for (index, backend) in rest.iter().enumerate() {
+            match backend
+                .client
+                .chat_completition(backend.prepare(&request))
+                .await
+            {
+                Err(error) if is_outage(&error) => {
+                    warn!(backend = index, %error, "Backend unavailable, failing over");
+                }
+                result => return result,
+            }
+        }
+        last.client.chat_completition(last.prepare(&request)).await
+    }
+
+    /// Opens a chat completion stream on the first available backend.
+    ///
+    /// Only opening the stream fails over; errors after the first chunk are returned
+    /// by the stream.
+    #[instrument(skip(self, request))]
+    pub async fn chat_completion_stream(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionStream, ChatCompletionError> {
+        let (last, rest) = self.backends.split_last().expect("a primary backend");
+        for (index, backend) in rest.iter().enumerate() {
+            match backend
+                .client
+                .chat_completion_stream(backend.prepare(&request))
+                .await
+            {
+                Err(error) if is_outage(&error) => {
+                    warn!(backend = index, %error, "Backend unavailable, failing over");
+                }
+                result => return result,
+            }
+        }
+        last.client
+            .chat_completion_stream(last.prepare(&request))
+            .await
+    }
+}
+
+/// How long a backend that failed with an outage stays out of rotation by default.
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Spreads chat completions across backends in proportion to their weights, taking
+/// backends out of rotation while they are unavailable.
+///
+/// Backends are picked by smooth weighted round-robin, so a backend of weight 3 next
+/// to one of weight 1 gets three of every four requests, interleaved rather than in
+/// bursts. When a request fails with an outage, as defined by [`FailoverClient`], it
+/// moves on to the next backend and the failed one sits out the
+/// [`cooldown`](Self::cooldown). Backends with weight 0 and backends cooling down are
+/// only tried once the others have failed.
+///
+/// # Example
+///
+/// ```no_run
+/// # use deepinfra_client_rs::prelude::*;
+/// # use deepinfra_client_rs::failover::*;
+/// # fn build() -> Result<WeightedClient, Box<dyn std::error::Error>> {
+/// let us = DeepinfraClient::builder()
+///     .token("gateway_token")
+///     .base_url("https://us.llm-gateway.example.com/v1")
+///     .build()?;
+/// let eu = DeepinfraClient::builder()
+///     .token("gateway_token")
+///     .base_url("https://eu.llm-gateway.example.com/v1")
+///     .build()?;
+/// let self_hosted = DeepinfraClient::builder()
+///     .token("local_token")
+///     .base_url("http://vllm.internal:8000/v1")
+///     .build()?;
+///
+/// let client = WeightedClient::new(FailoverBackend::new(us), 3)
+///     .backend(FailoverBackend::new(eu), 1)
+///     .backend(FailoverBackend::new(self_hosted), 0);
+/// # Ok(client)
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct WeightedClient {
+    backends: Vec<(FailoverBackend, u32)>,
+    slots: Arc<Mutex<Vec<Slot>>>,
+    cooldown: Duration,
+}
+
+/// Routing state of one backend.
+#[derive(Clone, Debug, Default)]
+struct Slot {
+    /// Smooth weighted round-robin counter.
+    current: i64,
+    /// When the backend returns to rotation after an outage.
+    down_until: Option<Instant>,
+}
+
+impl WeightedClient {
+    /// Creates a client routing every request to `backend` until more are added.
+    pub fn new(backend: FailoverBackend, weight: u32) -> Self {
+        Self {
+            backends: vec![(backend, weight)],
+            slots: Arc::new(Mutex::new(vec![Slot::default()])),
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// Adds a backend receiving requests in proportion to `weight`.
+    pub fn backend(mut self, backend: FailoverBackend, weight: u32) -> Self {
+        self.backends.push((backend, weight));
+        self.slots
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Slot::default());
+        self
+    }
+
+    /// How long a backend stays out of rotation after an outage, 30 seconds by
+    /// default.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Sends a chat completion to the next backend in rotation, moving on to the
+    /// others while backends are unavailable.
+    #[instrument(skip(self, request))]
+    pub async fn chat_completition(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, ChatCompletionError> {
+        let order = self.order();
+        let (last, rest) = order.split_last().expect("a backend");
+        for &index in rest {
+            let backend = &self.backends[index].0;
+            let result = backend
+                .client
+                .chat_completition(backend.prepare(&request))
+                .await;
+            if !self.record(index, &result) {
+                return result;
+            }
+        }
+        let backend = &self.backends[*last].0;
+        let result = backend
+            .client
+            .chat_completition(backend.prepare(&request))
+            .await;
+        self.record(*last, &result);
+        result
+    }
+
+    /// Opens a chat completion stream on the next backend in rotation, moving on to
+    /// the others while backends are unavailable.
+    ///
+    /// Only opening the stream is routed; errors after the first chunk are returned
+    /// by the stream.
+    #[instrument(skip(self, request))]
+    pub async fn chat_completion_stream(
+        &self,
+        request: ChatCompletionRequest,
+    ) -> Result<ChatCompletionStream, ChatCompletionError> {
+        let order = self.order();
+        let (last, rest) = order.split_last().expect("a backend");
+        for &index in rest {
+            let backend = &self.backends[index].0;
+            let result = backend
+                .client
+                .chat_completion_stream(backend.prepare(&request))
+                .await;
+            if !self.record(index, &result) {
+                return result;
+            }
+        }
+        let backend = &self.backends[*last].0;
+        let result = backend
+            .client
+            .chat_completion_stream(backend.prepare(&request))
+            .await;
+        self.record(*last, &result);
+        result
+    }
+
+    /// The backends to try for the next request: the pick of the weighted rotation,
+    /// then the other available backends by weight, then those cooling down.
+    fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut slots = self.slots.lock().unwrap_or_else(PoisonError::into_inner);
+        let available: Vec<bool> = slots
+            .iter()
+            .map(|slot| slot.down_until.is_none_or(|until| until <= now))
+            .collect();
+
+        let weight = |index: usize| self.backends[index].1;
+        let rotating = |index: &usize| available[*index] && weight(*index) > 0;
+
+        let mut total = 0;
+        for (index, slot) in slots.iter_mut().enumerate() {
+            if rotating(&index) {
+                slot.current += i64::from(weight(index));
+                total += i64::from(weight(index));
+            }
+        }
+        let pick = (0..slots.len())
+            .filter(rotating)
+            .max_by_key(|&index| (slots[index].current, Reverse(index)));
+        if let Some(pick) = pick {
+            slots[pick].current -= total;
+        }
+
+        let mut order: Vec<usize> = (0..slots.len()).collect();
+        order.sort_by_key(|&index| {
+            (
+                Some(index) != pick,
+                !available[index],
+                Reverse(weight(index)),
+            )
+        });
+        order
+    }
+
+    /// Records the outcome of a request to backend `index`, returning whether it
+    /// failed with an outage.
+    fn record<T>(&self, index: usize, result: &Result<T, ChatCompletionError>) -> bool {
+        let outage = match result {
+            Err(error) if is_outage(error) => {
+                warn!(backend = index, %error, "Backend unavailable, routing to the next one");
+                true
+            }
+            _ => false,
+        };
+        self.slots.lock().unwrap_or_else(PoisonError::into_inner)[index].down_until =
+            outage.then(|| Instant::now() + self.cooldown);
+        outage
+    }
+}
+
+/// Whether `error` means the backend is unavailable rather than the request invalid.
+fn is_outage(error: &ChatCompletionError) -> bool {
+    match error {
+        ChatCompletionError::ApiError(error) => error.is_retryable(),
+        ChatCompletionError::Transport(error) => {
+            error.is_timeout()
+                || error.is_connect()
+                || error
+                    .status()
+                    .is_some_and(|status| status.is_server_error())
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend() -> FailoverBackend {
+        FailoverBackend::new(DeepinfraClient::builder().token("token").build().unwrap())
+    }
+
+    fn picks(client: &WeightedClient, count: usize) -> Vec<usize> {
+        (0..count).map(|_| client.order()[0]).collect()
+    }
+
+    #[test]
+    fn interleaves_backends_by_weight() {
+        let client = WeightedClient::new(backend(), 3).backend(backend(), 1);
+        assert_eq!(picks(&client, 8), [0, 0, 1, 0, 0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn keeps_zero_weight_backends_as_a_last_resort() {
+        let client = WeightedClient::new(backend(), 0)
+            .backend(backend(), 1)
+            .backend(backend(), 2);
+        assert_eq!(client.order(), [2, 1, 0]);
+        assert_eq!(client.order(), [1, 2, 0]);
+    }
+
+    #[test]
+    fn skips_backends_during_their_cooldown() {
+        let client = WeightedClient::new(backend(), 1)
+            .backend(backend(), 1)
+            .cooldown(Duration::from_secs(60));
+        let outage: Result<(), _> = Err(ChatCompletionError::ApiError(
+            crate::api_error::ApiError::Server {
+                status: 503,
+                request_id: None,
+                body: "unavailable".to_string(),
+            },
+        ));
+
+        assert!(client.record(0, &outage));
+        assert_eq!(picks(&client, 3), [1, 1, 1]);
+        assert_eq!(client.order(), [1, 0]);
+
+        assert!(!client.record(0, &Ok(())));
+        assert_eq!(picks(&client, 2), [0, 1]);
+    }
+}
@@ -0,0 +1,289 @@
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference"
+))]
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+use tracing::{info, instrument, warn};
+
+use crate::client::{normalize_base_url, DeepinfraClient, Settings};
+use crate::config::{self, ConfigError};
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models",
+    feature = "deploy"
+))]
+use crate::endpoints::EndpointUrls;
+use crate::model_aliases::ModelAliases;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference"
+))]
+use crate::model_limits::{ModelLimit, ModelLimitError, ModelLimits};
+#[cfg(all(
+    feature = "rate_limit",
+    any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference"
+    )
+))]
+use crate::rate_limit::{RateLimit, RateLimiter};
+
+/// How often a watched configuration file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Client settings that can be changed while the client is in use.
+///
+/// Settings left out keep their current value. A present setting replaces the
+/// current one entirely, e.g. `model_limits` lists every limited model.
+///
+/// # Example
+///
+/// ```toml
+/// base_url = "https://api.deepinfra.com/v1"
+///
+/// [endpoint_urls]
+/// audio_transcriptions = "https://audio.gateway.example.com/v1/transcriptions"
+///
+/// [rate_limit]
+/// requests_per_second = 10
+///
+/// [model_limits."meta-llama/Meta-Llama-3.1-405B-Instruct"]
+/// max_concurrent = 2
+///
+/// [model_aliases]
+/// fast = "meta-llama/Meta-Llama-3.1-8B-Instruct"
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientConfig {
+    pub base_url: Option<String>,
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models",
+        feature = "deploy"
+    ))]
+    pub endpoint_urls: Option<EndpointUrls>,
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference"
+    ))]
+    pub model_limits: Option<HashMap<String, ModelLimit>>,
+    pub model_aliases: Option<ModelAliases>,
+    /// Requires the `rate_limit` feature.
+    #[cfg(all(
+        feature = "rate_limit",
+        any(
+            feature = "chat_completition",
+            feature = "audio_transcription",
+            feature = "inference"
+        )
+    ))]
+    pub rate_limit: Option<RateLimit>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HotReloadError {
+    #[error("Could not read configuration: {0}")]
+    ConfigError(#[from] ConfigError),
+    #[error("Invalid base URL {0}")]
+    InvalidBaseUrl(#[from] url::ParseError),
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference"
+    ))]
+    #[error("Invalid model limit: {0}")]
+    ModelLimitError(#[from] ModelLimitError),
+}
+
+/// Watches a configuration file for the client; stops when dropped.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    task: JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+    /// Stops watching the configuration file.
+    pub fn stop(self) {}
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl DeepinfraClient {
+    /// Applies `config` to this client and every clone of it.
+    ///
+    /// Requests that already hold a slot in the previous limits keep it; new requests
+    /// wait for the new limits.
+    pub fn apply_config(&self, config: &ClientConfig) -> Result<(), HotReloadError> {
+        let current = self.settings();
+        let base_url = match &config.base_url {
+            Some(base_url) => normalize_base_url(base_url)?,
+            None => current.base_url.clone(),
+        };
+        #[cfg(any(
+            feature = "chat_completition",
+            feature = "audio_transcription",
+            feature = "inference",
+            feature = "models",
+            feature = "deploy"
+        ))]
+        let endpoint_urls = match &config.endpoint_urls {
+            Some(urls) => urls.clone().normalize()?,
+            None => current.endpoint_urls.clone(),
+        };
+        #[cfg(any(
+            feature = "chat_completition",
+            feature = "audio_transcription",
+            feature = "inference"
+        ))]
+        let model_limits = match &config.model_limits {
+            Some(limits) => limits
+                .iter()
+                .try_fold(ModelLimits::new(), |limits, (model, limit)| {
+                    limits.with(model, *limit)
+                })?,
+            None => current.model_limits.clone(),
+        };
+        let model_aliases = config
+            .model_aliases
+            .clone()
+            .unwrap_or_else(|| current.model_aliases.clone());
+
+        self.replace_settings(Settings {
+            base_url,
+            #[cfg(any(
+                feature = "chat_completition",
+                feature = "audio_transcription",
+                feature = "inference",
+                feature = "models",
+                feature = "deploy"
+            ))]
+            endpoint_urls,
+            #[cfg(any(
+                feature = "chat_completition",
+                feature = "audio_transcription",
+                feature = "inference"
+            ))]
+            model_limits,
+            model_aliases,
+            #[cfg(all(
+                feature = "rate_limit",
+                any(
+                    feature = "chat_completition",
+                    feature = "audio_transcription",
+                    feature = "inference"
+                )
+            ))]
+            rate_limiter: match config.rate_limit {
+                Some(limit) => RateLimiter::from(limit),
+                None => current.rate_limiter.clone(),
+            },
+        });
+        Ok(())
+    }
+
+    /// Loads a [`ClientConfig`] from `path` and reloads it whenever the file changes.
+    ///
+    /// The file may be JSON, TOML or YAML, as for [`config::load`]. Loading the file
+    /// the first time must succeed; later invalid versions are logged and ignored, so
+    /// the client keeps its last good settings. Must be called within a Tokio runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::hot_reload::HotReloadError;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), HotReloadError> {
+    /// let _watcher = client.watch_config("deepinfra.toml")?;
+    /// // Edits to deepinfra.toml now apply to `client` and its clones.
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, path), fields(path = %path.as_ref().display()))]
+    pub fn watch_config(&self, path: impl AsRef<Path>) -> Result<ConfigWatcher, HotReloadError> {
+        let path = path.as_ref().to_path_buf();
+        let mut last_modified = modified_at(&path);
+        self.apply_config(&config::load(&path)?)?;
+
+        let client = self.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+                let modified = modified_at(&path);
+                if modified == last_modified {
+                    continue;
+                }
+                last_modified = modified;
+                match client.reload_config(&path) {
+                    Ok(()) => info!(path = %path.display(), "Reloaded client configuration"),
+                    Err(error) => {
+                        warn!(path = %path.display(), %error, "Ignoring invalid client configuration")
+                    }
+                }
+            }
+        });
+
+        Ok(ConfigWatcher { task })
+    }
+
+    fn reload_config(&self, path: &Path) -> Result<(), HotReloadError> {
+        self.apply_config(&config::load(path)?)
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(all(
+    test,
+    all(
+        feature = "rate_limit",
+        any(
+            feature = "chat_completition",
+            feature = "audio_transcription",
+            feature = "inference"
+        )
+    )
+))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_a_zero_model_rate_without_changing_the_settings() {
+        let client = DeepinfraClient::builder().token("token").build().unwrap();
+        let config: ClientConfig = serde_json::from_str(
+            r#"{
+                "base_url": "https://gateway.example.com/v1",
+                "model_limits": { "big": { "max_requests_per_second": 0 } }
+            }"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            client.apply_config(&config),
+            Err(HotReloadError::ModelLimitError(_))
+        ));
+        assert_eq!(client.settings().base_url, crate::client::DEFAULT_BASE_URL);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::api_error::{ApiError, DecodeError};
+use crate::client::DeepinfraClient;
+use crate::inference::{InferenceError, InferenceStatus};
+use crate::metadata::{InferenceMetrics, ResponseMetadata};
+use crate::raw::RawResponse;
+use crate::shutdown::ShutdownError;
+use base64::Engine;
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+use tracing::instrument;
+
+/// Represents a request to generate images from a text prompt.
+///
+/// Parameters left unset use the model's own defaults. Not every model accepts every
+/// parameter; FLUX models, for example, ignore `negative_prompt` and `guidance_scale`.
+#[derive(Clone, Debug, Serialize, Builder)]
+pub struct ImageGenerationRequest {
+    /// The image model to use (default: "black-forest-labs/FLUX-1-schnell").
+    #[serde(skip)]
+    #[builder(default = "black-forest-labs/FLUX-1-schnell".to_string(), into)]
+    model: String,
+    /// Text describing the desired image.
+    #[builder(into)]
+    prompt: String,
+    /// Text describing what the image should not contain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(into)]
+    negative_prompt: Option<String>,
+    /// Image width in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    /// Image height in pixels.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+    /// Number of images to generate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_images: Option<u32>,
+    /// How strongly the image should follow the prompt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    guidance_scale: Option<f64>,
+    /// Number of denoising steps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_inference_steps: Option<u32>,
+    /// Seed for reproducible generations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    /// Overrides the client's timeout for this request.
+    #[serde(skip)]
+    timeout: Option<Duration>,
+}
+
+/// A generated image, either inline as a base64 data URL or hosted at a URL.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum GeneratedImage {
+    /// Inline image data.
+    Base64 { mime_type: String, data: String },
+    /// Image hosted by DeepInfra.
+    Url(String),
+}
+
+impl From<String> for GeneratedImage {
+    fn from(value: String) -> Self {
+        let inline = value
+            .strip_prefix("data:")
+            .and_then(|rest| rest.split_once(";base64,"));
+        match inline {
+            Some((mime_type, data)) => GeneratedImage::Base64 {
+                mime_type: mime_type.to_string(),
+                data: data.to_string(),
+            },
+            None => GeneratedImage::Url(value),
+        }
+    }
+}
+
+impl From<GeneratedImage> for String {
+    fn from(image: GeneratedImage) -> Self {
+        match image {
+            GeneratedImage::Base64 { mime_type, data } => format!("data:{mime_type};base64,{data}"),
+            GeneratedImage::Url(url) => url,
+        }
+    }
+}
+
+impl GeneratedImage {
+    /// Decodes the image bytes. Fails for hosted images, which must be downloaded
+    /// with [`DeepinfraClient::download`].
+    pub fn bytes(&self) -> Result<Vec<u8>, ImageGenerationError> {
+        match self {
+            GeneratedImage::Base64 { data, .. } => {
+                Ok(base64::engine::general_purpose::STANDARD.decode(data)?)
+            }
+            GeneratedImage::Url(url) => Err(ImageGenerationError::NotInline(url.clone())),
+        }
+    }
+
+    /// Decodes the image and writes it to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ImageGenerationError> {
+        std::fs::write(path, self.bytes()?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImageGenerationResponse {
+    pub images: Vec<GeneratedImage>,
+    pub seed: Option<u64>,
+    pub nsfw_content_detected: Option<Vec<bool>>,
+    pub request_id: Option<String>,
+    pub inference_status: Option<InferenceStatus>,
+    /// Status and headers of the HTTP response, including DeepInfra runtime headers.
+    #[serde(skip)]
+    pub metadata: ResponseMetadata,
+}
+
+impl ImageGenerationResponse {
+    /// Splits the request latency into model runtime, as reported in
+    /// `inference_status`, and queueing plus network overhead.
+    pub fn inference_metrics(&self) -> InferenceMetrics {
+        let runtime = self
+            .inference_status
+            .as_ref()
+            .and_then(InferenceStatus::runtime);
+        self.metadata.inference_metrics_with(runtime)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImageGenerationError {
+    #[error("Request error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Could not decode response: {0}")]
+    DecodeError(#[from] DecodeError),
+    #[error("Invalid base64 image data: {0}")]
+    Base64Error(#[from] base64::DecodeError),
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Image is hosted at {0} and must be downloaded")]
+    NotInline(String),
+    #[error("Client shutdown: {0}")]
+    ShutdownError(#[from] ShutdownError),
+}
+
+impl From<InferenceError> for ImageGenerationError {
+    fn from(error: InferenceError) -> Self {
+        match error {
+            InferenceError::Transport(error) => error.into(),
+            InferenceError::ApiError(error) => error.into(),
+            InferenceError::DecodeError(error) => error.into(),
+            InferenceError::ShutdownError(error) => error.into(),
+        }
+    }
+}
+
+impl DeepinfraClient {
+    /// Generates images from a text prompt.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::image_generation::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), ImageGenerationError> {
+    /// let request = ImageGenerationRequest::builder()
+    ///     .model("stabilityai/sdxl-turbo")
+    ///     .prompt("A lighthouse at dawn, oil painting")
+    ///     .negative_prompt("blurry")
+    ///     .width(1024)
+    ///     .height(1024)
+    ///     .build();
+    ///
+    /// let response = client.image_generation(request).await?;
+    /// response.images[0].save("lighthouse.png")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request))]
+    pub async fn image_generation(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, ImageGenerationError> {
+        self.lifecycle
+            .track(self.send_image_generation(request))
+            .await?
+    }
+
+    /// Generates images and returns the response before deserialization.
+    #[instrument(skip(self, request))]
+    pub async fn image_generation_raw(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<RawResponse, ImageGenerationError> {
+        self.lifecycle
+            .track(async {
+                Ok(self
+                    .send_inference_raw(&request.model, &request, request.timeout)
+                    .await?)
+            })
+            .await?
+    }
+
+    async fn send_image_generation(
+        &self,
+        request: ImageGenerationRequest,
+    ) -> Result<ImageGenerationResponse, ImageGenerationError> {
+        let (mut response, metadata): (ImageGenerationResponse, _) = self
+            .send_inference(&request.model, &request, request.timeout)
+            .await?;
+        response.metadata = metadata;
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::chat_completition::ContentPart;
+use bon::Builder;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat, ImageReader};
+
+/// How local images are scaled down before they are embedded in a vision request.
+///
+/// Vision models tile or downsample large images anyway, so pixels beyond their
+/// working resolution only add upload size and image tokens.
+#[derive(Clone, Debug, Builder)]
+pub struct ImageResize {
+    /// Length in pixels of the longest side after scaling.
+    #[builder(default = 1536)]
+    pub max_dimension: u32,
+    /// JPEG quality, from 1 to 100, of re-encoded opaque images.
+    #[builder(default = 85)]
+    pub jpeg_quality: u8,
+}
+
+impl Default for ImageResize {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImageInputError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Image error: {0}")]
+    ImageError(#[from] image::ImageError),
+}
+
+impl ContentPart {
+    /// An image part embedding `bytes`, scaled down to fit `resize`.
+    ///
+    /// Images that already fit are embedded unchanged. Larger ones are resized,
+    /// keeping their aspect ratio, and re-encoded as JPEG, or as PNG when they have
+    /// an alpha channel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deepinfra_client_rs::chat_completition::ContentPart;
+    /// use deepinfra_client_rs::image_input::ImageResize;
+    /// use image::{ImageFormat, RgbImage};
+    /// use std::io::Cursor;
+    ///
+    /// let mut png = Vec::new();
+    /// RgbImage::new(3000, 2000).write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    ///
+    /// let resize = ImageResize::builder().max_dimension(1024).build();
+    /// let part = ContentPart::image_bytes_resized(&png, &resize)?;
+    ///
+    /// let ContentPart::ImageUrl { image_url } = part else { unreachable!() };
+    /// assert!(image_url.url.starts_with("data:image/jpeg;base64,"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn image_bytes_resized(
+        bytes: &[u8],
+        resize: &ImageResize,
+    ) -> Result<Self, ImageInputError> {
+        let reader = ImageReader::new(Cursor::new(bytes)).with_guessed_format()?;
+        let format = reader.format();
+        let image = reader.decode()?;
+
+        if let Some(format) = format.filter(|_| {
+            image.width() <= resize.max_dimension && image.height() <= resize.max_dimension
+        }) {
+            return Ok(Self::image_bytes(bytes, format.to_mime_type()));
+        }
+
+        let image = image.resize(
+            resize.max_dimension,
+            resize.max_dimension,
+            FilterType::Lanczos3,
+        );
+        let (encoded, mime_type) = encode(&image, resize.jpeg_quality)?;
+        Ok(Self::image_bytes(&encoded, mime_type))
+    }
+
+    /// An image part embedding the file at `path`, scaled down to fit `resize`.
+    pub fn image_file_resized(
+        path: impl AsRef<Path>,
+        resize: &ImageResize,
+    ) -> Result<Self, ImageInputError> {
+        Self::image_bytes_resized(&std::fs::read(path)?, resize)
+    }
+}
+
+/// Encodes `image` as JPEG, or as PNG to keep transparency.
+fn encode(
+    image: &DynamicImage,
+    jpeg_quality: u8,
+) -> Result<(Vec<u8>, &'static str), ImageInputError> {
+    let mut encoded = Vec::new();
+    if image.color().has_alpha() {
+        image.write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png)?;
+        Ok((encoded, ImageFormat::Png.to_mime_type()))
+    } else {
+        let encoder = JpegEncoder::new_with_quality(&mut encoded, jpeg_quality.clamp(1, 100));
+        DynamicImage::from(image.to_rgb8()).write_with_encoder(encoder)?;
+        Ok((encoded, ImageFormat::Jpeg.to_mime_type()))
+    }
+}
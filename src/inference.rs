@@ -0,0 +1,157 @@
+use crate::api_error::{read_json, ApiError, DecodeError};
+use crate::client::{with_timeout, DeepinfraClient};
+use crate::endpoints::Endpoint;
+use crate::metadata::{InferenceMetrics, ResponseMetadata};
+use crate::raw::RawResponse;
+use crate::shutdown::ShutdownError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tracing::instrument;
+
+const INFERENCE_PATH: &str = Endpoint::Inference.path();
+
+/// Execution details reported by DeepInfra's native inference API.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InferenceStatus {
+    pub status: String,
+    pub runtime_ms: Option<u64>,
+    pub cost: Option<f64>,
+}
+
+impl InferenceStatus {
+    /// The reported model runtime.
+    pub fn runtime(&self) -> Option<Duration> {
+        self.runtime_ms.map(Duration::from_millis)
+    }
+}
+
+/// The response of a native inference call.
+///
+/// `output` receives every field of the response body except the common
+/// `request_id` and `inference_status`, so it can be a model-specific struct or a
+/// plain [`serde_json::Value`].
+#[derive(Debug, Deserialize)]
+pub struct InferenceResponse<T = serde_json::Value> {
+    #[serde(flatten)]
+    pub output: T,
+    pub request_id: Option<String>,
+    pub inference_status: Option<InferenceStatus>,
+    /// Status and headers of the HTTP response, including DeepInfra runtime headers.
+    #[serde(skip)]
+    pub metadata: ResponseMetadata,
+}
+
+impl<T> InferenceResponse<T> {
+    /// Splits the request latency into model runtime and queueing plus network
+    /// overhead.
+    pub fn inference_metrics(&self) -> InferenceMetrics {
+        let runtime = self
+            .inference_status
+            .as_ref()
+            .and_then(InferenceStatus::runtime);
+        self.metadata.inference_metrics_with(runtime)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InferenceError {
+    #[error("Request error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Could not decode response: {0}")]
+    DecodeError(#[from] DecodeError),
+    #[error("Client shutdown: {0}")]
+    ShutdownError(#[from] ShutdownError),
+}
+
+impl DeepinfraClient {
+    /// Calls any DeepInfra model through the native inference API.
+    ///
+    /// The input and output shapes are model-specific; see the model page on
+    /// DeepInfra for the accepted fields.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::inference::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), InferenceError> {
+    /// let input = serde_json::json!({ "inputs": ["I love this crate"] });
+    /// let response: InferenceResponse = client
+    ///     .inference("cardiffnlp/twitter-roberta-base-sentiment-latest", &input)
+    ///     .await?;
+    /// println!("{}", response.output["results"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, input))]
+    pub async fn inference<I, O>(
+        &self,
+        model: &str,
+        input: &I,
+    ) -> Result<InferenceResponse<O>, InferenceError>
+    where
+        I: Serialize,
+        O: DeserializeOwned,
+    {
+        let (mut response, metadata): (InferenceResponse<O>, _) = self
+            .lifecycle
+            .track(self.send_inference(model, input, None))
+            .await??;
+        response.metadata = metadata;
+        Ok(response)
+    }
+
+    /// Calls a model through the native inference API and returns the response
+    /// before deserialization.
+    #[instrument(skip(self, input))]
+    pub async fn inference_raw<I: Serialize>(
+        &self,
+        model: &str,
+        input: &I,
+    ) -> Result<RawResponse, InferenceError> {
+        self.lifecycle
+            .track(self.send_inference_raw(model, input, None))
+            .await?
+    }
+
+    /// Posts `input` to the inference endpoint of `model` without reading the body.
+    pub(crate) async fn send_inference_raw<I: Serialize>(
+        &self,
+        model: &str,
+        input: &I,
+        timeout: Option<Duration>,
+    ) -> Result<RawResponse, InferenceError> {
+        let model = &self.resolve_model(model);
+        let _permit = self.throttle(model).await;
+        let request = self.post_json(&format!("{INFERENCE_PATH}/{model}"), input);
+        Ok(self.send_raw(with_timeout(request, timeout)).await?)
+    }
+
+    /// Posts `input` to the inference endpoint of `model` and decodes the body.
+    /// `timeout` overrides the client's timeout.
+    pub(crate) async fn send_inference<I, O>(
+        &self,
+        model: &str,
+        input: &I,
+        timeout: Option<Duration>,
+    ) -> Result<(O, ResponseMetadata), InferenceError>
+    where
+        I: Serialize,
+        O: DeserializeOwned,
+    {
+        let model = &self.resolve_model(model);
+        let _permit = self.throttle(model).await;
+        let started = Instant::now();
+        let request = self.post_json(&format!("{INFERENCE_PATH}/{model}"), input);
+        let http_response = self.send(with_timeout(request, timeout)).await?;
+        let http_response = ApiError::check(http_response).await?;
+        let metadata = self.response_metadata(&http_response, started);
+
+        let output = read_json(http_response).await??;
+
+        Ok((output, metadata.finish(started)))
+    }
+}
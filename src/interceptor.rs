@@ -0,0 +1,114 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::{Request, Response};
+
+/// Hooks called around every HTTP request the client sends, for logging, metrics,
+/// custom headers or credentials that change over time.
+///
+/// Every method has an empty default, so an interceptor only implements the hooks
+/// it needs. Hooks run synchronously on the request path and should return quickly;
+/// refresh credentials in the background and only read them here.
+pub trait Interceptor: Send + Sync {
+    /// Called right before `request` is sent. The request can be modified, e.g. to
+    /// add headers.
+    fn on_request(&self, request: &mut Request) {
+        let _ = request;
+    }
+
+    /// Called when response headers arrive, whatever the status. The body is still
+    /// unread.
+    fn on_response(&self, response: &Response, elapsed: Duration) {
+        let _ = (response, elapsed);
+    }
+
+    /// Called when the request could not be built or sent, or no response arrived.
+    fn on_error(&self, error: &reqwest::Error, elapsed: Duration) {
+        let _ = (error, elapsed);
+    }
+}
+
+/// An ordered list of [`Interceptor`]s.
+///
+/// Request hooks run in the order the interceptors were added, so a later
+/// interceptor sees the changes of an earlier one.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::client::DeepinfraClient;
+/// use deepinfra_client_rs::interceptor::{Interceptor, Interceptors};
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::time::Duration;
+///
+/// #[derive(Default)]
+/// struct Tenant;
+///
+/// impl Interceptor for Tenant {
+///     fn on_request(&self, request: &mut reqwest::Request) {
+///         request
+///             .headers_mut()
+///             .insert("x-tenant", "acme".parse().unwrap());
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct Failures(AtomicU64);
+///
+/// impl Interceptor for Failures {
+///     fn on_error(&self, error: &reqwest::Error, _elapsed: Duration) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///         eprintln!("request failed: {error}");
+///     }
+/// }
+///
+/// let client = DeepinfraClient::builder()
+///     .token("your_api_token")
+///     .interceptors(Interceptors::new().with(Tenant).with(Failures::default()))
+///     .build()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct Interceptors {
+    interceptors: Vec<Arc<dyn Interceptor>>,
+}
+
+impl Interceptors {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an interceptor to the end of the list.
+    pub fn with(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    pub(crate) fn on_request(&self, request: &mut Request) {
+        for interceptor in &self.interceptors {
+            interceptor.on_request(request);
+        }
+    }
+
+    pub(crate) fn on_response(&self, response: &Response, elapsed: Duration) {
+        for interceptor in &self.interceptors {
+            interceptor.on_response(response, elapsed);
+        }
+    }
+
+    pub(crate) fn on_error(&self, error: &reqwest::Error, elapsed: Duration) {
+        for interceptor in &self.interceptors {
+            interceptor.on_error(error, elapsed);
+        }
+    }
+}
+
+impl fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interceptors")
+            .field("interceptors", &self.interceptors.len())
+            .finish()
+    }
+}
@@ -0,0 +1,291 @@
+use crate::api_error::{decode, ApiError, DecodeError};
+use crate::client::DeepinfraClient;
+use crate::endpoints::Endpoint;
+use crate::inference::{InferenceError, InferenceResponse, InferenceStatus};
+use crate::metadata::ResponseMetadata;
+use crate::shutdown::ShutdownError;
+use bon::Builder;
+use http::Method;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tracing::{debug, instrument};
+
+const INFERENCE_JOBS_PATH: &str = Endpoint::InferenceJobs.path();
+
+/// The state of a queued inference job, as reported in `inference_status`.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::jobs::JobState;
+///
+/// assert!(JobState::Queued.can_transition_to(&JobState::Running));
+/// assert!(JobState::Running.can_transition_to(&JobState::Succeeded));
+/// assert!(!JobState::Running.can_transition_to(&JobState::Queued));
+/// assert!(!JobState::Failed.can_transition_to(&JobState::Running));
+/// assert_eq!(JobState::from("succeeded".to_string()), JobState::Succeeded);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum JobState {
+    /// Waiting for a worker.
+    Queued,
+    Running,
+    /// Finished; the output is available.
+    Succeeded,
+    Failed,
+    /// A state this crate does not know about, kept verbatim.
+    Other(String),
+}
+
+impl JobState {
+    pub fn as_str(&self) -> &str {
+        match self {
+            JobState::Queued => "queued",
+            JobState::Running => "running",
+            JobState::Succeeded => "succeeded",
+            JobState::Failed => "failed",
+            JobState::Other(state) => state,
+        }
+    }
+
+    /// Whether the job has finished, successfully or not.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobState::Succeeded | JobState::Failed)
+    }
+
+    /// Whether a job in this state may be reported in `next` later on.
+    ///
+    /// Jobs only move forward: queued, running, then succeeded or failed. Unknown
+    /// states are accepted from any non-terminal state.
+    pub fn can_transition_to(&self, next: &JobState) -> bool {
+        match (self, next) {
+            (current, next) if current == next => true,
+            (JobState::Succeeded | JobState::Failed, _) => false,
+            (_, JobState::Queued) => matches!(self, JobState::Other(_)),
+            _ => true,
+        }
+    }
+}
+
+impl From<String> for JobState {
+    fn from(state: String) -> Self {
+        match state.as_str() {
+            "queued" => JobState::Queued,
+            "running" => JobState::Running,
+            "succeeded" => JobState::Succeeded,
+            "failed" => JobState::Failed,
+            _ => JobState::Other(state),
+        }
+    }
+}
+
+impl From<JobState> for String {
+    fn from(state: JobState) -> Self {
+        match state {
+            JobState::Other(state) => state,
+            state => state.as_str().to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for JobState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An inference request submitted for asynchronous execution.
+#[derive(Clone, Debug)]
+pub struct Job {
+    /// The request ID assigned by DeepInfra.
+    pub id: String,
+    pub model: String,
+    /// The last state observed for the job.
+    pub state: JobState,
+}
+
+/// How [`DeepinfraClient::wait_for_job`] polls a job.
+#[derive(Clone, Debug, Builder)]
+pub struct JobPollOptions {
+    /// Delay between two status requests.
+    #[builder(default = Duration::from_secs(2))]
+    pub interval: Duration,
+    /// Gives up once the job has not finished after this long.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for JobPollOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobError {
+    #[error("Request error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Could not decode response: {0}")]
+    DecodeError(#[from] DecodeError),
+    #[error("Job input must serialize to a JSON object")]
+    InvalidInput,
+    #[error("The API did not return a job ID")]
+    MissingJobId,
+    #[error("Job {0} failed")]
+    JobFailed(String),
+    #[error("Job moved from {from} to {to}")]
+    InvalidTransition { from: JobState, to: JobState },
+    #[error("Job {id} did not finish within {timeout:?}")]
+    TimedOut { id: String, timeout: Duration },
+    #[error("Client shutdown: {0}")]
+    ShutdownError(#[from] ShutdownError),
+}
+
+impl From<InferenceError> for JobError {
+    fn from(error: InferenceError) -> Self {
+        match error {
+            InferenceError::Transport(error) => error.into(),
+            InferenceError::ApiError(error) => error.into(),
+            InferenceError::DecodeError(error) => error.into(),
+            InferenceError::ShutdownError(error) => error.into(),
+        }
+    }
+}
+
+impl DeepinfraClient {
+    /// Submits `input` to `model` for asynchronous execution and returns the queued
+    /// job.
+    ///
+    /// With a `webhook`, DeepInfra posts the result to that URL once the job
+    /// finishes; the job can be polled either way.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::inference::InferenceResponse;
+    /// # use deepinfra_client_rs::jobs::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), JobError> {
+    /// let input = serde_json::json!({ "prompt": "A timelapse of a city at night" });
+    /// let mut job = client
+    ///     .submit_job("genmo/mochi-1-preview", &input, Some("https://example.com/hooks/video"))
+    ///     .await?;
+    ///
+    /// let response: InferenceResponse = client
+    ///     .wait_for_job(&mut job, &JobPollOptions::default())
+    ///     .await?;
+    /// println!("{}", response.output);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, input))]
+    pub async fn submit_job<I: Serialize>(
+        &self,
+        model: &str,
+        input: &I,
+        webhook: Option<&str>,
+    ) -> Result<Job, JobError> {
+        let mut body = serde_json::to_value(input).map_err(|_| JobError::InvalidInput)?;
+        let fields = body.as_object_mut().ok_or(JobError::InvalidInput)?;
+        if let Some(webhook) = webhook {
+            fields.insert("webhook".to_string(), webhook.into());
+        }
+
+        let (response, _): (InferenceResponse, _) = self
+            .lifecycle
+            .track(self.send_inference(model, &body, None))
+            .await??;
+
+        Ok(Job {
+            id: response.request_id.ok_or(JobError::MissingJobId)?,
+            model: model.to_string(),
+            state: response
+                .inference_status
+                .map_or(JobState::Queued, |status| status.status.into()),
+        })
+    }
+
+    /// Fetches the current status of `job` and records its new state.
+    ///
+    /// The output fields of the response are only present once the job succeeded.
+    #[instrument(skip(self, job), fields(job = %job.id))]
+    pub async fn poll_job(&self, job: &mut Job) -> Result<InferenceResponse, JobError> {
+        let (body, metadata) = self.lifecycle.track(self.send_poll_job(job)).await??;
+        decode_job_response(&body, metadata)
+    }
+
+    /// Polls `job` until it finishes and decodes its output.
+    #[instrument(skip(self, job, options), fields(job = %job.id))]
+    pub async fn wait_for_job<O: DeserializeOwned>(
+        &self,
+        job: &mut Job,
+        options: &JobPollOptions,
+    ) -> Result<InferenceResponse<O>, JobError> {
+        let started = Instant::now();
+        loop {
+            let (body, metadata) = self.lifecycle.track(self.send_poll_job(job)).await??;
+            match job.state {
+                JobState::Succeeded => return decode_job_response(&body, metadata),
+                JobState::Failed => return Err(JobError::JobFailed(job.id.clone())),
+                _ => {}
+            }
+
+            if let Some(timeout) = options.timeout {
+                if started.elapsed() + options.interval > timeout {
+                    return Err(JobError::TimedOut {
+                        id: job.id.clone(),
+                        timeout,
+                    });
+                }
+            }
+            debug!(state = %job.state, "Job not finished yet");
+            tokio::time::sleep(options.interval).await;
+        }
+    }
+
+    /// Requests the status of `job`, updates its state and returns the body.
+    async fn send_poll_job(&self, job: &mut Job) -> Result<(Vec<u8>, ResponseMetadata), JobError> {
+        let started = Instant::now();
+        let request = self.request(Method::GET, &format!("{INFERENCE_JOBS_PATH}/{}", job.id));
+        let http_response = self.send(request).await?;
+        let http_response = ApiError::check(http_response).await?;
+        let metadata = self.response_metadata(&http_response, started);
+        let body = http_response.bytes().await?.to_vec();
+
+        let status: JobStatus = decode(&body)
+            .map_err(|error| error.with_request_id(metadata.request_id().map(str::to_string)))?;
+        if let Some(state) = status
+            .inference_status
+            .map(|status| JobState::from(status.status))
+        {
+            if !job.state.can_transition_to(&state) {
+                return Err(JobError::InvalidTransition {
+                    from: job.state.clone(),
+                    to: state,
+                });
+            }
+            job.state = state;
+        }
+
+        Ok((body, metadata.finish(started)))
+    }
+}
+
+/// The part of a status response needed to track the job.
+#[derive(Deserialize)]
+struct JobStatus {
+    inference_status: Option<InferenceStatus>,
+}
+
+fn decode_job_response<O: DeserializeOwned>(
+    body: &[u8],
+    metadata: ResponseMetadata,
+) -> Result<InferenceResponse<O>, JobError> {
+    let mut response: InferenceResponse<O> = decode(body)
+        .map_err(|error| error.with_request_id(metadata.request_id().map(str::to_string)))?;
+    response.metadata = metadata;
+    Ok(response)
+}
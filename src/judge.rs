@@ -0,0 +1,195 @@
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse, Message, ResponseFormat,
+    ResponseFormatType, SystemMessage, UserMessage,
+};
+use crate::client::DeepinfraClient;
+use bon::Builder;
+use serde::Deserialize;
+use std::fmt::Write;
+use tracing::instrument;
+
+const DEFAULT_CRITERIA: &str = "helpfulness, correctness and clarity";
+
+/// Options for [`DeepinfraClient::best_of_n`].
+#[derive(Clone, Debug, Builder)]
+pub struct JudgeOptions {
+    /// Number of candidates to sample (the API accepts 1 to 4).
+    #[builder(default = 4)]
+    pub n: u32,
+    /// Model used to score the candidates.
+    #[builder(default = "deepseek-ai/DeepSeek-V3".to_string(), into)]
+    pub judge_model: String,
+    /// What the judge should value, e.g. "factual accuracy and brevity".
+    #[builder(into)]
+    pub criteria: Option<String>,
+}
+
+impl Default for JudgeOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// A sampled candidate with the judge's verdict.
+#[derive(Clone, Debug)]
+pub struct JudgedCandidate {
+    /// Index of the choice in the sampling response.
+    pub index: usize,
+    pub content: String,
+    /// Score between 0 and 10 assigned by the judge.
+    pub score: f64,
+    pub rationale: Option<String>,
+}
+
+/// The outcome of [`DeepinfraClient::best_of_n`].
+#[derive(Debug)]
+pub struct BestOfN {
+    /// The highest-scoring candidate.
+    pub winner: JudgedCandidate,
+    /// Every candidate, in sampling order.
+    pub candidates: Vec<JudgedCandidate>,
+    /// The raw sampling response.
+    pub response: ChatCompletionResponse,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum JudgeError {
+    #[error("Chat completion error: {0}")]
+    ChatCompletionError(#[from] ChatCompletionError),
+    #[error("The model returned no candidates")]
+    NoCandidates,
+    #[error("The judge returned an invalid verdict {0}")]
+    InvalidVerdict(String),
+}
+
+#[derive(Deserialize)]
+struct Verdict {
+    scores: Vec<Score>,
+}
+
+#[derive(Deserialize)]
+struct Score {
+    index: usize,
+    score: f64,
+    rationale: Option<String>,
+}
+
+impl DeepinfraClient {
+    /// Samples `n` candidates for `request` and asks a judge model to score them,
+    /// returning the best one.
+    #[instrument(skip(self, request))]
+    pub async fn best_of_n(
+        &self,
+        mut request: ChatCompletionRequest,
+        options: &JudgeOptions,
+    ) -> Result<BestOfN, JudgeError> {
+        request.n = options.n;
+        let conversation = render_conversation(&request.messages);
+        let response = self.chat_completition(request).await?;
+
+        let candidates: Vec<(usize, String)> = response
+            .choices
+            .iter()
+            .enumerate()
+            .filter_map(|(index, choice)| match &choice.message {
+                Message::Assistant(message) => Some((index, message.content.clone())),
+                _ => None,
+            })
+            .collect();
+        if candidates.is_empty() {
+            return Err(JudgeError::NoCandidates);
+        }
+
+        let candidates = self
+            .judge_candidates(
+                &conversation,
+                candidates,
+                &options.judge_model,
+                options.criteria.as_deref(),
+            )
+            .await?;
+
+        let winner = candidates
+            .iter()
+            .max_by(|a, b| a.score.total_cmp(&b.score))
+            .cloned()
+            .ok_or(JudgeError::NoCandidates)?;
+
+        Ok(BestOfN {
+            winner,
+            candidates,
+            response,
+        })
+    }
+
+    /// Asks `judge_model` to score each `(index, content)` candidate reply to
+    /// `conversation` from 0 to 10. Candidates the judge skipped score 0.
+    pub(crate) async fn judge_candidates(
+        &self,
+        conversation: &str,
+        candidates: Vec<(usize, String)>,
+        judge_model: &str,
+        criteria: Option<&str>,
+    ) -> Result<Vec<JudgedCandidate>, JudgeError> {
+        let mut prompt = format!("Conversation:\n{conversation}\nCandidate replies:\n");
+        for (index, content) in &candidates {
+            let _ = write!(
+                prompt,
+                "\n<candidate index=\"{index}\">\n{content}\n</candidate>\n"
+            );
+        }
+
+        let criteria = criteria.unwrap_or(DEFAULT_CRITERIA);
+        let instructions = format!(
+            "You are an impartial judge. Score each candidate reply to the conversation \
+             from 0 to 10 based on {criteria}. Reply with a JSON object of the form \
+             {{\"scores\": [{{\"index\": <candidate index>, \"score\": <number>, \
+             \"rationale\": \"<one sentence>\"}}]}} covering every candidate."
+        );
+
+        let judge_request = ChatCompletionRequest::builder()
+            .model(judge_model.to_string())
+            .temperature(0.0)
+            .max_tokens(1024)
+            .response_format(ResponseFormat {
+                response_type: ResponseFormatType::JsonObject,
+            })
+            .messages(vec![
+                Message::System(SystemMessage::builder().content(instructions).build()),
+                Message::User(UserMessage::builder().content(prompt).build()),
+            ])
+            .build();
+
+        let verdict = self.chat_completition(judge_request).await?;
+        let verdict = verdict.content().unwrap_or_default();
+        let verdict: Verdict = serde_json::from_str(verdict)
+            .map_err(|_| JudgeError::InvalidVerdict(verdict.to_string()))?;
+
+        Ok(candidates
+            .into_iter()
+            .map(|(index, content)| {
+                let score = verdict.scores.iter().find(|score| score.index == index);
+                JudgedCandidate {
+                    index,
+                    content,
+                    score: score.map_or(0.0, |score| score.score),
+                    rationale: score.and_then(|score| score.rationale.clone()),
+                }
+            })
+            .collect())
+    }
+}
+
+pub(crate) fn render_conversation(messages: &[Message]) -> String {
+    let mut rendered = String::new();
+    for message in messages {
+        let role = match message {
+            Message::System(_) => "system",
+            Message::User(_) => "user",
+            Message::Assistant(_) => "assistant",
+            Message::Tool(_) => "tool",
+        };
+        let _ = writeln!(rendered, "[{role}] {}", message.content());
+    }
+    rendered
+}
@@ -0,0 +1,177 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+/// Error returned when parsing a string that is not an ISO-639-1 code.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown ISO-639-1 language code {0:?}")]
+pub struct UnknownLanguage(pub String);
+
+macro_rules! languages {
+    ($($variant:ident => $code:literal,)*) => {
+        /// A language identified by its ISO-639-1 code.
+        ///
+        /// The named variants cover the languages supported by Whisper. `Other` sends
+        /// a code verbatim, for languages added to the API after this crate.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use deepinfra_client_rs::language::Language;
+        ///
+        /// assert_eq!(Language::Italian.code(), "it");
+        /// assert_eq!("de".parse::<Language>(), Ok(Language::German));
+        /// assert!("English".parse::<Language>().is_err());
+        /// ```
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Language {
+            $($variant,)*
+            Other(String),
+        }
+
+        impl Language {
+            /// The ISO-639-1 code of the language.
+            pub fn code(&self) -> &str {
+                match self {
+                    $(Language::$variant => $code,)*
+                    Language::Other(code) => code,
+                }
+            }
+        }
+
+        impl FromStr for Language {
+            type Err = UnknownLanguage;
+
+            /// Parses a known ISO-639-1 code, ignoring case.
+            fn from_str(code: &str) -> Result<Self, Self::Err> {
+                match code.to_ascii_lowercase().as_str() {
+                    $($code => Ok(Language::$variant),)*
+                    _ => Err(UnknownLanguage(code.to_string())),
+                }
+            }
+        }
+    };
+}
+
+languages! {
+    Afrikaans => "af",
+    Albanian => "sq",
+    Amharic => "am",
+    Arabic => "ar",
+    Armenian => "hy",
+    Assamese => "as",
+    Azerbaijani => "az",
+    Bashkir => "ba",
+    Basque => "eu",
+    Belarusian => "be",
+    Bengali => "bn",
+    Bosnian => "bs",
+    Breton => "br",
+    Bulgarian => "bg",
+    Burmese => "my",
+    Catalan => "ca",
+    Chinese => "zh",
+    Croatian => "hr",
+    Czech => "cs",
+    Danish => "da",
+    Dutch => "nl",
+    English => "en",
+    Estonian => "et",
+    Faroese => "fo",
+    Finnish => "fi",
+    French => "fr",
+    Galician => "gl",
+    Georgian => "ka",
+    German => "de",
+    Greek => "el",
+    Gujarati => "gu",
+    HaitianCreole => "ht",
+    Hausa => "ha",
+    Hebrew => "he",
+    Hindi => "hi",
+    Hungarian => "hu",
+    Icelandic => "is",
+    Indonesian => "id",
+    Italian => "it",
+    Japanese => "ja",
+    Javanese => "jv",
+    Kannada => "kn",
+    Kazakh => "kk",
+    Khmer => "km",
+    Korean => "ko",
+    Lao => "lo",
+    Latin => "la",
+    Latvian => "lv",
+    Lingala => "ln",
+    Lithuanian => "lt",
+    Luxembourgish => "lb",
+    Macedonian => "mk",
+    Malagasy => "mg",
+    Malay => "ms",
+    Malayalam => "ml",
+    Maltese => "mt",
+    Maori => "mi",
+    Marathi => "mr",
+    Mongolian => "mn",
+    Nepali => "ne",
+    Norwegian => "no",
+    NorwegianNynorsk => "nn",
+    Occitan => "oc",
+    Pashto => "ps",
+    Persian => "fa",
+    Polish => "pl",
+    Portuguese => "pt",
+    Punjabi => "pa",
+    Romanian => "ro",
+    Russian => "ru",
+    Sanskrit => "sa",
+    Serbian => "sr",
+    Shona => "sn",
+    Sindhi => "sd",
+    Sinhala => "si",
+    Slovak => "sk",
+    Slovenian => "sl",
+    Somali => "so",
+    Spanish => "es",
+    Sundanese => "su",
+    Swahili => "sw",
+    Swedish => "sv",
+    Tagalog => "tl",
+    Tajik => "tg",
+    Tamil => "ta",
+    Tatar => "tt",
+    Telugu => "te",
+    Thai => "th",
+    Tibetan => "bo",
+    Turkish => "tr",
+    Turkmen => "tk",
+    Ukrainian => "uk",
+    Urdu => "ur",
+    Uzbek => "uz",
+    Vietnamese => "vi",
+    Welsh => "cy",
+    Yiddish => "yi",
+    Yoruba => "yo",
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    /// Accepts any string, keeping unknown codes in [`Language::Other`].
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(code.parse().unwrap_or(Language::Other(code)))
+    }
+}
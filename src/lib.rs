@@ -1,4 +1,38 @@
+pub mod api;
+pub mod api_error;
+
+#[cfg(feature = "chat_completition")]
+pub mod attachments;
+
+#[cfg(feature = "chat_completition")]
+pub mod audit;
+
+#[cfg(feature = "chat_completition")]
+pub mod batch;
+
+#[cfg(feature = "bench")]
+pub mod bench;
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+#[cfg(feature = "chat_completition")]
+mod canonical;
+
+pub mod chunking;
 pub mod client;
+#[cfg(all(
+    feature = "gzip",
+    any(
+        feature = "chat_completition",
+        feature = "inference",
+        feature = "deploy"
+    )
+))]
+mod compression;
+
+#[cfg(feature = "config")]
+pub mod config;
 
 #[cfg(feature = "chat_completition")]
 pub mod chat_completition;
@@ -6,7 +40,156 @@ pub mod chat_completition;
 #[cfg(feature = "audio_transcription")]
 pub mod audio_transcription;
 
+#[cfg(all(feature = "chat_completition", feature = "audio_transcription"))]
+pub mod audio_summary;
+
+#[cfg(feature = "audio_transcription")]
+pub mod audio_translation;
+
+#[cfg(feature = "chat_completition")]
+pub mod chat_stream;
+
+#[cfg(feature = "chat_completition")]
+pub mod continuation;
+
+#[cfg(feature = "chat_completition")]
+pub mod data_url;
+
+#[cfg(feature = "deploy")]
+pub mod deploy;
+
+pub mod download;
+pub mod end_user;
+pub mod endpoints;
+pub mod error;
+
+#[cfg(feature = "chat_completition")]
+pub mod evaluation;
+
+#[cfg(feature = "chat_completition")]
+pub mod experiment;
+
+#[cfg(feature = "chat_completition")]
+pub mod failover;
+
+#[cfg(feature = "hot_reload")]
+pub mod hot_reload;
+
+#[cfg(feature = "image_generation")]
+pub mod image_generation;
+
+#[cfg(feature = "image")]
+pub mod image_input;
+
+#[cfg(feature = "inference")]
+pub mod inference;
+
+pub mod interceptor;
+
+#[cfg(feature = "inference")]
+pub mod jobs;
+
+#[cfg(feature = "chat_completition")]
+pub mod judge;
+
+#[cfg(feature = "audio_transcription")]
+pub mod language;
+
+pub mod metadata;
+pub mod model_aliases;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference"
+))]
+pub mod model_limits;
+
+#[cfg(feature = "models")]
+pub mod models;
+
+#[cfg(all(
+    feature = "rate_limit",
+    any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference"
+    )
+))]
+mod pacing;
+
+#[cfg(any(feature = "models", feature = "deploy"))]
+pub mod pagination;
+
+pub mod post_processing;
 pub mod prelude;
 
+#[cfg(all(feature = "chat_completition", feature = "config"))]
+pub mod prompt_library;
+
+pub mod prompt_template;
+
+#[cfg(feature = "chat_completition")]
+pub mod quick;
+
+pub mod shutdown;
+
+#[cfg(all(
+    feature = "rate_limit",
+    any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference"
+    )
+))]
+pub mod rate_limit;
+
+pub mod raw;
+pub mod redact;
+
+#[cfg(feature = "inference")]
+pub mod rerank;
+
+#[cfg(feature = "chat_completition")]
+pub mod request_diff;
+
+#[cfg(feature = "chat_completition")]
+pub mod request_template;
+
+#[cfg(feature = "chat_completition")]
+pub mod seed_sweep;
+
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "inference",
+    feature = "deploy"
+))]
+pub mod serialization;
+
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+mod sse;
+
+#[cfg(all(feature = "chat_completition", feature = "schemars"))]
+pub mod structured;
+
+#[cfg(feature = "audio_transcription")]
+pub mod subtitles;
+
+#[cfg(feature = "chat_completition")]
+pub mod summarization;
+
+#[cfg(feature = "chat_completition")]
+pub mod tool_runner;
+
+#[cfg(feature = "audio_transcription")]
+pub mod transcription_stream;
+
+#[cfg(feature = "chat_completition")]
+pub mod translation;
+
+#[cfg(feature = "chat_completition")]
+pub mod truncation;
+
+pub use erased_serde;
 pub use http;
 pub use reqwest;
+pub use secrecy;
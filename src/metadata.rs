@@ -0,0 +1,191 @@
+use std::time::Duration;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models"
+))]
+use std::time::Instant;
+
+use http::HeaderMap;
+
+use crate::api_error::REQUEST_ID_HEADER;
+
+/// Milliseconds the request waited for a model replica before it started running.
+pub const QUEUE_TIME_HEADER: &str = "x-deepinfra-queue-time-ms";
+/// Milliseconds the model spent running the request.
+pub const INFERENCE_TIME_HEADER: &str = "x-deepinfra-inference-time-ms";
+/// Milliseconds DeepInfra spent on the request in total, queueing included.
+pub const RUNTIME_HEADER: &str = "x-deepinfra-runtime-ms";
+
+/// Transport-level metadata captured from an API response.
+///
+/// DeepInfra reports backend and runtime details through response headers; they are
+/// kept verbatim so changes in the serving stack can be detected even for headers
+/// this crate does not know about.
+#[derive(Clone, Debug, Default)]
+pub struct ResponseMetadata {
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// All response headers.
+    pub headers: HeaderMap,
+    /// Wall-clock time from sending the request to receiving the full response body,
+    /// measured by the client.
+    pub elapsed: Duration,
+    /// Time the request waited for a model replica, from the
+    /// [`QUEUE_TIME_HEADER`].
+    pub queue_time: Option<Duration>,
+    /// Time the model spent running, from the [`INFERENCE_TIME_HEADER`].
+    pub inference_time: Option<Duration>,
+    /// Time DeepInfra spent on the request in total, from the [`RUNTIME_HEADER`].
+    pub runtime: Option<Duration>,
+}
+
+impl ResponseMetadata {
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models"
+    ))]
+    pub(crate) fn from_response(response: &reqwest::Response, started: Instant) -> Self {
+        Self {
+            status: response.status().as_u16(),
+            headers: response.headers().clone(),
+            elapsed: started.elapsed(),
+            queue_time: duration_header(response.headers(), QUEUE_TIME_HEADER),
+            inference_time: duration_header(response.headers(), INFERENCE_TIME_HEADER),
+            runtime: duration_header(response.headers(), RUNTIME_HEADER),
+        }
+    }
+
+    /// Records the end of the body transfer.
+    #[cfg(any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models"
+    ))]
+    pub(crate) fn finish(mut self, started: Instant) -> Self {
+        self.elapsed = started.elapsed();
+        self
+    }
+
+    /// Splits the request latency into model runtime, queueing and the rest, as far
+    /// as DeepInfra reported them in the timing headers.
+    pub fn inference_metrics(&self) -> InferenceMetrics {
+        self.inference_metrics_with(None)
+    }
+
+    /// Like [`Self::inference_metrics`], preferring a `runtime` reported in the
+    /// response body over the inference time header.
+    pub(crate) fn inference_metrics_with(&self, runtime: Option<Duration>) -> InferenceMetrics {
+        InferenceMetrics {
+            queue_time: self.queue_time,
+            ..InferenceMetrics::new(self.elapsed, runtime.or(self.inference_time))
+        }
+    }
+
+    /// The request identifier from the `x-request-id` header, for DeepInfra support.
+    pub fn request_id(&self) -> Option<&str> {
+        self.header(REQUEST_ID_HEADER)
+    }
+
+    /// Returns the value of a header as a string, if present and valid UTF-8.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+/// Splits the latency of a request between model execution and everything else.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InferenceMetrics {
+    /// Total latency observed by the client.
+    pub total: Duration,
+    /// Time the model spent running, when reported by DeepInfra.
+    pub runtime: Option<Duration>,
+    /// Latency not spent running the model: queueing plus network transfer.
+    /// Only known when `runtime` is.
+    pub overhead: Option<Duration>,
+    /// The part of `overhead` spent waiting for a model replica, when reported by
+    /// DeepInfra.
+    pub queue_time: Option<Duration>,
+}
+
+impl InferenceMetrics {
+    pub fn new(total: Duration, runtime: Option<Duration>) -> Self {
+        Self {
+            total,
+            runtime,
+            overhead: runtime.map(|runtime| total.saturating_sub(runtime)),
+            queue_time: None,
+        }
+    }
+}
+
+/// Parses a header holding a non-negative number of milliseconds.
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models"
+))]
+fn duration_header(headers: &HeaderMap, name: &str) -> Option<Duration> {
+    let millis: f64 = headers.get(name)?.to_str().ok()?.trim().parse().ok()?;
+    Duration::try_from_secs_f64(millis / 1000.0).ok()
+}
+
+#[cfg(all(
+    test,
+    any(
+        feature = "chat_completition",
+        feature = "audio_transcription",
+        feature = "inference",
+        feature = "models"
+    )
+))]
+mod tests {
+    use super::*;
+
+    fn metadata(headers: &[(&str, &str)]) -> ResponseMetadata {
+        let mut response = http::Response::builder();
+        for (name, value) in headers {
+            response = response.header(*name, *value);
+        }
+        let response = reqwest::Response::from(response.body("").unwrap());
+        ResponseMetadata::from_response(&response, Instant::now())
+    }
+
+    #[test]
+    fn parses_the_timing_headers() {
+        let metadata = metadata(&[
+            (QUEUE_TIME_HEADER, "12"),
+            (INFERENCE_TIME_HEADER, "250.5"),
+            (RUNTIME_HEADER, "263"),
+        ]);
+        assert_eq!(metadata.queue_time, Some(Duration::from_millis(12)));
+        assert_eq!(
+            metadata.inference_time,
+            Some(Duration::from_micros(250_500))
+        );
+        assert_eq!(metadata.runtime, Some(Duration::from_millis(263)));
+
+        let metrics = ResponseMetadata {
+            elapsed: Duration::from_millis(300),
+            ..metadata
+        }
+        .inference_metrics();
+        assert_eq!(metrics.runtime, Some(Duration::from_micros(250_500)));
+        assert_eq!(metrics.overhead, Some(Duration::from_micros(49_500)));
+        assert_eq!(metrics.queue_time, Some(Duration::from_millis(12)));
+    }
+
+    #[test]
+    fn ignores_missing_and_malformed_timing_headers() {
+        let metadata = metadata(&[(QUEUE_TIME_HEADER, "soon"), (RUNTIME_HEADER, "-1")]);
+        assert_eq!(metadata.queue_time, None);
+        assert_eq!(metadata.inference_time, None);
+        assert_eq!(metadata.runtime, None);
+        assert_eq!(metadata.inference_metrics().overhead, None);
+    }
+}
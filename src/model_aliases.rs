@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// Logical model names resolved to DeepInfra models when a request is sent.
+///
+/// Application code can refer to stable names such as `fast` or `smart` while the
+/// models behind them are changed in one place, or at runtime with the
+/// `hot_reload` feature. Names without an alias are sent unchanged.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::client::DeepinfraClient;
+/// use deepinfra_client_rs::model_aliases::ModelAliases;
+///
+/// let aliases = ModelAliases::new()
+///     .alias("fast", "meta-llama/Meta-Llama-3.1-8B-Instruct")
+///     .alias("smart", "deepseek-ai/DeepSeek-V3");
+/// assert_eq!(aliases.resolve("fast"), "meta-llama/Meta-Llama-3.1-8B-Instruct");
+/// assert_eq!(aliases.resolve("openai/whisper-large-v3"), "openai/whisper-large-v3");
+///
+/// let client = DeepinfraClient::builder()
+///     .token("your_api_token")
+///     .model_aliases(aliases)
+///     .build()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct ModelAliases {
+    aliases: HashMap<String, String>,
+}
+
+impl ModelAliases {
+    /// Creates a set without aliases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves `name` to `model`, replacing any previous alias for it.
+    pub fn alias(mut self, name: impl Into<String>, model: impl Into<String>) -> Self {
+        self.aliases.insert(name.into(), model.into());
+        self
+    }
+
+    /// The model `name` stands for. Aliases are not resolved recursively.
+    pub fn resolve<'a>(&'a self, name: &'a str) -> &'a str {
+        self.aliases.get(name).map_or(name, String::as_str)
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+use std::sync::{Mutex, PoisonError};
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+use std::time::Duration;
+
+#[cfg(feature = "rate_limit")]
+use crate::pacing::Pacer;
+use bon::Builder;
+use serde::Deserialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+use tokio::time::Instant;
+
+/// Concurrency and throughput caps for a single model.
+#[derive(Clone, Copy, Debug, Default, Builder, Deserialize)]
+pub struct ModelLimit {
+    /// Maximum number of requests to the model in flight at once.
+    pub max_concurrent: Option<usize>,
+    /// Maximum number of streams to the model open at once. Streams also count
+    /// towards `max_concurrent`.
+    pub max_concurrent_streams: Option<usize>,
+    /// Minimum time between the starts of two streams to the model, in
+    /// milliseconds, so a burst of users opens its streams one after the other.
+    pub min_stream_interval_ms: Option<u64>,
+    /// Maximum number of requests to the model started per second; must be
+    /// positive. Requires the `rate_limit` feature.
+    #[cfg(feature = "rate_limit")]
+    pub max_requests_per_second: Option<f64>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ModelLimitError {
+    #[cfg(feature = "rate_limit")]
+    #[error("Invalid rate of {rate} requests per second for {model}, expected a positive number")]
+    InvalidRate { model: String, rate: f64 },
+}
+
+/// Enforces a [`ModelLimit`] across every clone of a client.
+#[derive(Debug)]
+struct Gate {
+    concurrency: Option<Arc<Semaphore>>,
+    #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+    streams: Option<Arc<Semaphore>>,
+    #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+    stagger: Option<Stagger>,
+    #[cfg(feature = "rate_limit")]
+    pacer: Option<Pacer>,
+}
+
+/// Spaces out the starts of streams to a model.
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+#[derive(Debug)]
+struct Stagger {
+    interval: Duration,
+    /// The earliest start of the next stream.
+    next: Mutex<Instant>,
+}
+
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+impl Stagger {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Reserves the next start slot and waits for it.
+    async fn wait(&self) {
+        let start = {
+            let mut next = self.next.lock().unwrap_or_else(PoisonError::into_inner);
+            let start = (*next).max(Instant::now());
+            *next = start + self.interval;
+            start
+        };
+        tokio::time::sleep_until(start).await;
+    }
+}
+
+/// Per-model request limits enforced by the client.
+///
+/// Requests to models without a limit are not throttled. Limits are shared between
+/// clones of the client they are configured on. Streams hold their slots for as long
+/// as they are open, and can be capped and staggered separately.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::client::DeepinfraClient;
+/// use deepinfra_client_rs::model_limits::{ModelLimit, ModelLimits};
+///
+/// let limits = ModelLimits::new()
+///     .with(
+///         "meta-llama/Meta-Llama-3.1-405B-Instruct",
+///         ModelLimit::builder().max_concurrent(2).build(),
+///     )?
+///     .with(
+///         "meta-llama/Meta-Llama-3.1-8B-Instruct",
+///         ModelLimit::builder()
+///             .max_concurrent(50)
+///             .max_concurrent_streams(20)
+///             .min_stream_interval_ms(50)
+///             .build(),
+///     )?;
+///
+/// let client = DeepinfraClient::builder()
+///     .token("your_api_token")
+///     .model_limits(limits)
+///     .build();
+/// # Ok::<(), deepinfra_client_rs::model_limits::ModelLimitError>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ModelLimits {
+    gates: HashMap<String, Arc<Gate>>,
+}
+
+/// Held while a throttled request is in flight.
+pub(crate) type ModelPermit = Option<OwnedSemaphorePermit>;
+
+/// Held while a throttled stream is open, on top of its [`ModelPermit`].
+#[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+pub(crate) type StreamPermit = Option<OwnedSemaphorePermit>;
+
+impl ModelLimits {
+    /// Creates a set without limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits requests to `model`, replacing any previous limit for it.
+    pub fn with(
+        mut self,
+        model: impl Into<String>,
+        limit: ModelLimit,
+    ) -> Result<Self, ModelLimitError> {
+        let model = model.into();
+        #[cfg(feature = "rate_limit")]
+        let pacer = match limit.max_requests_per_second {
+            Some(rate) => {
+                Some(
+                    Pacer::per_second(rate).ok_or_else(|| ModelLimitError::InvalidRate {
+                        model: model.clone(),
+                        rate,
+                    })?,
+                )
+            }
+            None => None,
+        };
+        let gate = Gate {
+            concurrency: limit
+                .max_concurrent
+                .map(|permits| Arc::new(Semaphore::new(permits.max(1)))),
+            #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+            streams: limit
+                .max_concurrent_streams
+                .map(|permits| Arc::new(Semaphore::new(permits.max(1)))),
+            #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+            stagger: limit
+                .min_stream_interval_ms
+                .filter(|&interval| interval > 0)
+                .map(|interval| Stagger::new(Duration::from_millis(interval))),
+            #[cfg(feature = "rate_limit")]
+            pacer,
+        };
+        self.gates.insert(model, Arc::new(gate));
+        Ok(self)
+    }
+
+    /// Waits until a request to `model` may start.
+    pub(crate) async fn acquire(&self, model: &str) -> ModelPermit {
+        let gate = self.gates.get(model)?;
+
+        // The semaphore is never closed.
+        let permit = match &gate.concurrency {
+            Some(semaphore) => Arc::clone(semaphore).acquire_owned().await.ok(),
+            None => None,
+        };
+
+        #[cfg(feature = "rate_limit")]
+        if let Some(pacer) = &gate.pacer {
+            pacer.until_ready().await;
+        }
+
+        permit
+    }
+
+    /// Waits until a stream to `model` may start; the request limits are acquired
+    /// separately with [`Self::acquire`].
+    #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+    pub(crate) async fn acquire_stream(&self, model: &str) -> StreamPermit {
+        let gate = self.gates.get(model)?;
+
+        // The semaphore is never closed.
+        let permit = match &gate.streams {
+            Some(semaphore) => Arc::clone(semaphore).acquire_owned().await.ok(),
+            None => None,
+        };
+
+        if let Some(stagger) = &gate.stagger {
+            stagger.wait().await;
+        }
+
+        permit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[cfg(feature = "rate_limit")]
+    #[test]
+    fn rejects_non_positive_rates() {
+        for rate in [0.0, -2.0, f64::NAN] {
+            let limit = ModelLimit::builder().max_requests_per_second(rate).build();
+            let error = ModelLimits::new().with("model", limit).unwrap_err();
+            assert!(
+                matches!(error, ModelLimitError::InvalidRate { model, .. } if model == "model")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn caps_concurrency_per_model() {
+        let limit = ModelLimit::builder().max_concurrent(1).build();
+        let limits = ModelLimits::new().with("big", limit).unwrap();
+
+        let first = limits.acquire("big").await;
+        assert!(first.is_some());
+        let second = tokio::time::timeout(Duration::from_millis(20), limits.acquire("big")).await;
+        assert!(
+            second.is_err(),
+            "second request started while the first was in flight"
+        );
+        assert!(limits.acquire("small").await.is_none());
+
+        drop(first);
+        assert!(limits.acquire("big").await.is_some());
+    }
+
+    #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+    #[tokio::test]
+    async fn caps_open_streams_per_model() {
+        let limit = ModelLimit::builder().max_concurrent_streams(1).build();
+        let limits = ModelLimits::new().with("big", limit).unwrap();
+
+        let stream = limits.acquire_stream("big").await;
+        assert!(stream.is_some());
+        let second = tokio::time::timeout(Duration::from_millis(20), limits.acquire_stream("big"));
+        assert!(
+            second.await.is_err(),
+            "second stream opened while the first was"
+        );
+        assert!(
+            limits.acquire("big").await.is_none(),
+            "plain requests are not capped by the stream limit"
+        );
+
+        drop(stream);
+        assert!(limits.acquire_stream("big").await.is_some());
+    }
+
+    #[cfg(any(feature = "chat_completition", feature = "audio_transcription"))]
+    #[tokio::test]
+    async fn staggers_stream_starts() {
+        let limit = ModelLimit::builder().min_stream_interval_ms(30).build();
+        let limits = ModelLimits::new().with("big", limit).unwrap();
+
+        let started = Instant::now();
+        for _ in 0..3 {
+            limits.acquire_stream("big").await;
+        }
+        assert!(started.elapsed() >= Duration::from_millis(60));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::api_error::{read_json, ApiError, DecodeError};
+use crate::client::DeepinfraClient;
+use crate::endpoints::Endpoint;
+use crate::pagination::{Page, Paginated, CURSOR_PARAM};
+use crate::raw::RawResponse;
+use crate::shutdown::ShutdownError;
+use bon::Builder;
+use futures::FutureExt;
+use http::Method;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+const MODELS_PATH: &str = Endpoint::Models.path();
+
+/// Price of a model in USD per million tokens.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input_tokens: Option<f64>,
+    pub output_tokens: Option<f64>,
+}
+
+/// DeepInfra-specific details attached to a model listing.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModelMetadata {
+    pub description: Option<String>,
+    /// Maximum number of tokens in the context window.
+    pub context_length: Option<u32>,
+    /// Maximum number of tokens the model can generate.
+    pub max_tokens: Option<u32>,
+    pub pricing: Option<ModelPricing>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Whether the model is scheduled for removal.
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+/// A model available on DeepInfra.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Model {
+    /// The model identifier used in requests.
+    pub id: String,
+    pub created: Option<u64>,
+    pub owned_by: Option<String>,
+    /// Missing for models that do not publish any metadata.
+    #[serde(default)]
+    pub metadata: ModelMetadata,
+}
+
+/// Criteria for selecting models from the catalogue.
+///
+/// Every criterion left unset matches all models. Deprecated models are excluded
+/// unless `include_deprecated` is set.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::models::ModelFilter;
+///
+/// let filter = ModelFilter::builder()
+///     .tag("vision")
+///     .min_context_length(32_000)
+///     .build();
+/// ```
+#[derive(Clone, Debug, Default, Builder)]
+pub struct ModelFilter {
+    /// Only models carrying this tag.
+    #[builder(into)]
+    pub tag: Option<String>,
+    /// Only models whose identifier contains this string.
+    #[builder(into)]
+    pub search: Option<String>,
+    /// Only models with at least this context window.
+    pub min_context_length: Option<u32>,
+    /// Only models whose input price does not exceed this value.
+    pub max_input_price: Option<f64>,
+    #[builder(default)]
+    pub include_deprecated: bool,
+}
+
+impl ModelFilter {
+    /// Whether `model` satisfies every criterion.
+    pub fn matches(&self, model: &Model) -> bool {
+        let metadata = &model.metadata;
+        (self.include_deprecated || !metadata.deprecated)
+            && self
+                .tag
+                .as_ref()
+                .is_none_or(|tag| metadata.tags.contains(tag))
+            && self
+                .search
+                .as_ref()
+                .is_none_or(|search| model.id.contains(search.as_str()))
+            && self.min_context_length.is_none_or(|min| {
+                metadata
+                    .context_length
+                    .is_some_and(|context_length| context_length >= min)
+            })
+            && self.max_input_price.is_none_or(|max| {
+                metadata
+                    .pricing
+                    .as_ref()
+                    .and_then(|pricing| pricing.input_tokens)
+                    .is_some_and(|price| price <= max)
+            })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ModelsError {
+    #[error("Request error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Could not decode response: {0}")]
+    DecodeError(#[from] DecodeError),
+    #[error("Model {0} not found")]
+    NotFound(String),
+    #[error("Client shutdown: {0}")]
+    ShutdownError(#[from] ShutdownError),
+}
+
+impl DeepinfraClient {
+    /// Lists every model available through the OpenAI-compatible API, fetching all
+    /// pages of the catalogue; use [`DeepinfraClient::find_models`] to narrow it down.
+    #[instrument(skip(self))]
+    pub async fn list_models(&self) -> Result<Vec<Model>, ModelsError> {
+        self.list_models_stream().collect_all().await
+    }
+
+    /// Streams the models available through the OpenAI-compatible API, fetching the
+    /// next page of the catalogue when the previous one is used up.
+    pub fn list_models_stream(&self) -> Paginated<Model, ModelsError> {
+        let client = self.clone();
+        let fetch = move |cursor| {
+            let client = client.clone();
+            async move {
+                client
+                    .lifecycle
+                    .track(client.send_list_models(cursor))
+                    .await?
+            }
+            .boxed()
+        };
+        Paginated::new(Box::new(fetch), |model| model.id.clone())
+    }
+
+    /// Lists the models and returns the response before deserialization.
+    #[instrument(skip(self))]
+    pub async fn list_models_raw(&self) -> Result<RawResponse, ModelsError> {
+        self.lifecycle
+            .track(async {
+                Ok(self
+                    .send_raw(self.request(Method::GET, MODELS_PATH))
+                    .await?)
+            })
+            .await?
+    }
+
+    /// Lists the models matching `filter`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::models::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), ModelsError> {
+    /// let filter = ModelFilter::builder().min_context_length(128_000).build();
+    /// let models = client.find_models(&filter).await?;
+    /// let model = models.first().map(|model| model.id.clone());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn find_models(&self, filter: &ModelFilter) -> Result<Vec<Model>, ModelsError> {
+        let mut models = self.list_models().await?;
+        models.retain(|model| filter.matches(model));
+        Ok(models)
+    }
+
+    /// Fetches the listing of a single model.
+    #[instrument(skip(self))]
+    pub async fn model(&self, id: &str) -> Result<Model, ModelsError> {
+        self.list_models()
+            .await?
+            .into_iter()
+            .find(|model| model.id == id)
+            .ok_or_else(|| ModelsError::NotFound(id.to_string()))
+    }
+
+    async fn send_list_models(&self, cursor: Option<String>) -> Result<Page<Model>, ModelsError> {
+        let mut request = self.request(Method::GET, MODELS_PATH);
+        if let Some(cursor) = cursor {
+            request = request.query(&[(CURSOR_PARAM, cursor)]);
+        }
+        let response = ApiError::check(self.send(request).await?).await?;
+        Ok(read_json(response).await??)
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use governor::{DefaultDirectRateLimiter, Quota};
+
+/// Spaces out request starts; used by the client-wide, per-model and batch limits.
+#[derive(Clone)]
+pub(crate) struct Pacer(Arc<DefaultDirectRateLimiter>);
+
+impl Pacer {
+    pub(crate) fn new(quota: Quota) -> Self {
+        Self(Arc::new(DefaultDirectRateLimiter::direct(quota)))
+    }
+
+    /// Starts at most `rate` requests per second, evenly spaced. Returns `None`
+    /// unless `rate` is a positive, finite number of a representable period.
+    pub(crate) fn per_second(rate: f64) -> Option<Self> {
+        if !(rate.is_finite() && rate > 0.0) {
+            return None;
+        }
+        let period = Duration::try_from_secs_f64(rate.recip()).ok()?;
+        Quota::with_period(period).map(Self::new)
+    }
+
+    /// Waits until a request may start.
+    pub(crate) async fn until_ready(&self) {
+        self.0.until_ready().await;
+    }
+}
+
+impl fmt::Debug for Pacer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pacer").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_rates_without_a_period() {
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e-300, 1e300] {
+            assert!(Pacer::per_second(rate).is_none(), "{rate}");
+        }
+    }
+
+    #[test]
+    fn accepts_fractional_rates() {
+        assert!(Pacer::per_second(0.5).is_some());
+        assert!(Pacer::per_second(20.0).is_some());
+    }
+}
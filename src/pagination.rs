@@ -0,0 +1,151 @@
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream, Stream, StreamExt, TryStreamExt};
+use serde::Deserialize;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Query parameter carrying the cursor of the next page.
+pub(crate) const CURSOR_PARAM: &str = "after";
+
+/// One page of a list endpoint.
+///
+/// Pages follow the OpenAI list convention: the items under `data` and `has_more`
+/// telling whether another page follows, requested with the ID of the last item as
+/// `after` cursor. Endpoints that answer with the whole list, as a bare array or
+/// without `has_more`, yield a single page.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Page<T> {
+    Cursor {
+        data: Vec<T>,
+        #[serde(default)]
+        has_more: bool,
+    },
+    All(Vec<T>),
+}
+
+impl<T> Page<T> {
+    /// The items of the page and whether another page follows.
+    fn into_parts(self) -> (Vec<T>, bool) {
+        match self {
+            Page::Cursor { data, has_more } => (data, has_more),
+            Page::All(items) => (items, false),
+        }
+    }
+}
+
+/// Fetches the page after `cursor`, or the first page for `None`.
+pub(crate) type FetchPage<T, E> =
+    Box<dyn Fn(Option<String>) -> BoxFuture<'static, Result<Page<T>, E>> + Send + Sync>;
+
+/// The items of a list endpoint, fetched page by page as the stream is polled.
+///
+/// # Example
+///
+/// ```no_run
+/// # use deepinfra_client_rs::prelude::*;
+/// # use deepinfra_client_rs::models::*;
+/// # use futures::TryStreamExt;
+/// # async fn run(client: DeepinfraClient) -> Result<(), ModelsError> {
+/// let mut models = client.list_models_stream();
+/// while let Some(model) = models.try_next().await? {
+///     println!("{}", model.id);
+/// }
+///
+/// let everything = client.list_models_stream().collect_all().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Paginated<T, E> {
+    inner: BoxStream<'static, Result<T, E>>,
+}
+
+impl<T: Send + 'static, E: Send + 'static> Paginated<T, E> {
+    /// Pages through a list endpoint with `fetch`, using `cursor` to take the cursor
+    /// of the next page from the last item of a page.
+    pub(crate) fn new(fetch: FetchPage<T, E>, cursor: fn(&T) -> String) -> Self {
+        let pages = stream::try_unfold(Some(None), move |next| {
+            let page = next.map(&fetch);
+            async move {
+                let Some(page) = page else {
+                    return Ok(None);
+                };
+                let (items, has_more) = page.await?.into_parts();
+                let next = match items.last() {
+                    Some(last) if has_more => Some(Some(cursor(last))),
+                    _ => None,
+                };
+                Ok(Some((stream::iter(items).map(Ok), next)))
+            }
+        });
+        Self {
+            inner: pages.try_flatten().boxed(),
+        }
+    }
+
+    /// Fetches every remaining page and returns all items.
+    pub async fn collect_all(self) -> Result<Vec<T>, E> {
+        self.try_collect().await
+    }
+}
+
+impl<T, E> Stream for Paginated<T, E> {
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl<T, E> fmt::Debug for Paginated<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Paginated").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+    use std::sync::{Arc, Mutex};
+
+    /// The cursors a test list was asked for, in order.
+    type Cursors = Arc<Mutex<Vec<Option<String>>>>;
+
+    fn pages(bodies: Vec<&'static str>) -> (Paginated<String, ()>, Cursors) {
+        let cursors = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&cursors);
+        let fetch: FetchPage<String, ()> = Box::new(move |cursor| {
+            let mut seen = seen.lock().unwrap();
+            let body = bodies[seen.len()];
+            seen.push(cursor);
+            async move { Ok(serde_json::from_str(body).unwrap()) }.boxed()
+        });
+        (Paginated::new(fetch, String::clone), cursors)
+    }
+
+    #[tokio::test]
+    async fn follows_cursors_until_the_last_page() {
+        let (items, cursors) = pages(vec![
+            r#"{"data": ["a", "b"], "has_more": true}"#,
+            r#"{"data": ["c"], "has_more": true}"#,
+            r#"{"data": [], "has_more": false}"#,
+        ]);
+
+        assert_eq!(items.collect_all().await.unwrap(), ["a", "b", "c"]);
+        assert_eq!(
+            *cursors.lock().unwrap(),
+            [None, Some("b".to_string()), Some("c".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn treats_whole_lists_as_a_single_page() {
+        for body in [r#"["a", "b"]"#, r#"{"data": ["a", "b"]}"#] {
+            let (items, cursors) = pages(vec![body]);
+            assert_eq!(items.collect_all().await.unwrap(), ["a", "b"]);
+            assert_eq!(cursors.lock().unwrap().len(), 1);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::sync::Arc;
+
+use thiserror::Error;
+
+/// Errors raised by a post-processor that rejects the model output.
+#[derive(Error, Debug)]
+pub enum PostProcessError {
+    /// The content was rejected by a guard.
+    #[error("Content rejected: {0}")]
+    Rejected(String),
+}
+
+/// A single step applied to assistant content before it is returned to the caller.
+///
+/// Any `Fn(String) -> Result<String, PostProcessError>` closure is a post-processor,
+/// which is the easiest way to plug in a regex guard or custom rewrite.
+pub trait PostProcessor: Send + Sync {
+    fn process(&self, content: String) -> Result<String, PostProcessError>;
+}
+
+impl<F> PostProcessor for F
+where
+    F: Fn(String) -> Result<String, PostProcessError> + Send + Sync,
+{
+    fn process(&self, content: String) -> Result<String, PostProcessError> {
+        self(content)
+    }
+}
+
+/// Removes `<think>...</think>` reasoning blocks emitted by reasoning models.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StripThinkBlocks;
+
+impl PostProcessor for StripThinkBlocks {
+    fn process(&self, content: String) -> Result<String, PostProcessError> {
+        const OPEN: &str = "<think>";
+        const CLOSE: &str = "</think>";
+
+        let mut output = String::with_capacity(content.len());
+        let mut rest = content.as_str();
+        while let Some(start) = rest.find(OPEN) {
+            output.push_str(&rest[..start]);
+            match rest[start..].find(CLOSE) {
+                Some(end) => rest = &rest[start + end + CLOSE.len()..],
+                // An unterminated block swallows the remainder of the output.
+                None => rest = "",
+            }
+        }
+        output.push_str(rest);
+
+        Ok(output)
+    }
+}
+
+/// Trims leading and trailing whitespace.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TrimWhitespace;
+
+impl PostProcessor for TrimWhitespace {
+    fn process(&self, content: String) -> Result<String, PostProcessError> {
+        Ok(content.trim().to_string())
+    }
+}
+
+/// Truncates content to at most the given number of characters.
+#[derive(Clone, Copy, Debug)]
+pub struct MaxLength(pub usize);
+
+impl PostProcessor for MaxLength {
+    fn process(&self, mut content: String) -> Result<String, PostProcessError> {
+        if let Some((index, _)) = content.char_indices().nth(self.0) {
+            content.truncate(index);
+        }
+        Ok(content)
+    }
+}
+
+/// An ordered list of [`PostProcessor`]s.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::post_processing::*;
+///
+/// let pipeline = PostProcessingPipeline::new()
+///     .with(StripThinkBlocks)
+///     .with(TrimWhitespace);
+///
+/// let content = pipeline.apply("<think>hmm</think>  Hello!".to_string()).unwrap();
+/// assert_eq!(content, "Hello!");
+/// ```
+#[derive(Clone, Default)]
+pub struct PostProcessingPipeline {
+    processors: Vec<Arc<dyn PostProcessor>>,
+}
+
+impl PostProcessingPipeline {
+    /// Creates an empty pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a post-processor to the end of the pipeline.
+    pub fn with(mut self, processor: impl PostProcessor + 'static) -> Self {
+        self.processors.push(Arc::new(processor));
+        self
+    }
+
+    /// Returns `true` if no post-processors are registered.
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    /// Runs `content` through every post-processor in order.
+    pub fn apply(&self, content: String) -> Result<String, PostProcessError> {
+        self.processors
+            .iter()
+            .try_fold(content, |content, processor| processor.process(content))
+    }
+}
+
+impl fmt::Debug for PostProcessingPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostProcessingPipeline")
+            .field("processors", &self.processors.len())
+            .finish()
+    }
+}
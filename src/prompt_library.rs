@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::canonical::fnv1a;
+use crate::chat_completition::{
+    AssistantMessage, ChatCompletionRequest, Message, SystemMessage, UserMessage,
+};
+use crate::config::{self, ConfigError, ConfigFormat};
+use crate::prompt_template::{Bindings, PromptTemplate, PromptTemplateError};
+use crate::request_template::RequestTemplate;
+use serde::Deserialize;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptLibraryError {
+    #[error("Could not load prompt {name}: {error}")]
+    ConfigError { name: String, error: ConfigError },
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Unknown prompt {0}")]
+    UnknownPrompt(String),
+    #[error("Could not render prompt: {0}")]
+    PromptTemplateError(#[from] PromptTemplateError),
+}
+
+/// A user message and the answer the model is expected to give to it.
+#[derive(Clone, Debug, Deserialize)]
+pub struct FewShotExample {
+    pub user: String,
+    pub assistant: String,
+}
+
+/// A prompt file as written on disk.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PromptFile {
+    version: Option<String>,
+    system: Option<String>,
+    user: Option<String>,
+    #[serde(default)]
+    examples: Vec<FewShotExample>,
+    #[serde(default = "default_parameters")]
+    parameters: ChatCompletionRequest,
+}
+
+fn default_parameters() -> ChatCompletionRequest {
+    ChatCompletionRequest::builder()
+        .messages(Vec::new())
+        .build()
+}
+
+/// A named prompt: system and user templates, few-shot examples and parameters.
+///
+/// The system and user messages are [`PromptTemplate`]s rendered against the
+/// bindings of each call; examples are sent verbatim between them.
+#[derive(Clone, Debug)]
+pub struct Prompt {
+    name: String,
+    version: String,
+    system: Option<PromptTemplate>,
+    user: Option<PromptTemplate>,
+    examples: Vec<FewShotExample>,
+    parameters: RequestTemplate,
+}
+
+impl Prompt {
+    /// Parses a prompt from configuration text.
+    ///
+    /// The version is taken from the `version` field, or derived from a hash of
+    /// `text` so that any edit to the prompt yields a new version.
+    pub fn parse(
+        name: impl Into<String>,
+        text: &str,
+        format: ConfigFormat,
+    ) -> Result<Self, ConfigError> {
+        let file: PromptFile = config::from_str(text, format)?;
+        Ok(Self {
+            name: name.into(),
+            version: file
+                .version
+                .unwrap_or_else(|| format!("{:016x}", fnv1a(text.as_bytes()))),
+            system: file.system.map(PromptTemplate::new),
+            user: file.user.map(PromptTemplate::new),
+            examples: file.examples,
+            parameters: RequestTemplate::from(file.parameters),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The version of the prompt, to be logged alongside its completions.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Renders the messages of the prompt: system, examples, then user.
+    pub fn messages(&self, bindings: &Bindings) -> Result<Vec<Message>, PromptTemplateError> {
+        let mut messages = Vec::with_capacity(self.examples.len() * 2 + 2);
+        if let Some(system) = &self.system {
+            messages.push(Message::System(
+                SystemMessage::builder()
+                    .content(system.render(bindings)?)
+                    .build(),
+            ));
+        }
+        for example in &self.examples {
+            messages.push(Message::User(
+                UserMessage::builder()
+                    .content(example.user.as_str())
+                    .build(),
+            ));
+            messages.push(Message::Assistant(
+                AssistantMessage::builder()
+                    .content(example.assistant.as_str())
+                    .build(),
+            ));
+        }
+        if let Some(user) = &self.user {
+            messages.push(Message::User(
+                UserMessage::builder()
+                    .content(user.render(bindings)?)
+                    .build(),
+            ));
+        }
+        Ok(messages)
+    }
+
+    /// Builds a ready-to-send request with the prompt's parameters.
+    pub fn request(
+        &self,
+        bindings: &Bindings,
+    ) -> Result<ChatCompletionRequest, PromptTemplateError> {
+        Ok(self.parameters.request(self.messages(bindings)?))
+    }
+}
+
+/// A set of named prompts, typically loaded from a directory.
+///
+/// Every JSON, TOML or YAML file in the directory is a prompt named after the file
+/// without extension, with optional `version`, `system`, `user`, `examples` and
+/// `parameters` fields. `parameters` accepts the fields of a
+/// [`ChatCompletionRequest`] except the messages.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::config::ConfigFormat;
+/// use deepinfra_client_rs::prompt_library::{Prompt, PromptLibrary};
+/// use deepinfra_client_rs::prompt_template::Bindings;
+///
+/// // prompts/classify.toml
+/// let prompt = Prompt::parse(
+///     "classify",
+///     r#"
+///     version = "2"
+///     system = "Classify the sentiment as positive or negative."
+///     user = "{review}"
+///
+///     [[examples]]
+///     user = "Great value for money."
+///     assistant = "positive"
+///
+///     [parameters]
+///     model = "meta-llama/Meta-Llama-3.1-8B-Instruct"
+///     temperature = 0.0
+///     "#,
+///     ConfigFormat::Toml,
+/// )?;
+///
+/// let mut library = PromptLibrary::new();
+/// library.insert(prompt);
+///
+/// let bindings = Bindings::from([("review".to_string(), "Broke after a day.".to_string())]);
+/// let request = library.request("classify", &bindings)?;
+///
+/// let json = serde_json::to_value(&request)?;
+/// assert_eq!(json["model"], "meta-llama/Meta-Llama-3.1-8B-Instruct");
+/// assert_eq!(json["messages"].as_array().unwrap().len(), 4);
+/// assert_eq!(json["messages"][3]["content"], "Broke after a day.");
+/// assert_eq!(library.get("classify").unwrap().version(), "2");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PromptLibrary {
+    prompts: BTreeMap<String, Prompt>,
+}
+
+impl PromptLibrary {
+    /// Creates an empty library.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every prompt file in `dir`. Files with other extensions are skipped.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, PromptLibraryError> {
+        let mut library = Self::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let (Some(format), Some(name)) = (
+                ConfigFormat::from_path(&path),
+                path.file_stem().and_then(|stem| stem.to_str()),
+            ) else {
+                continue;
+            };
+            let text = std::fs::read_to_string(&path)?;
+            let prompt = Prompt::parse(name, &text, format).map_err(|error| {
+                PromptLibraryError::ConfigError {
+                    name: name.to_string(),
+                    error,
+                }
+            })?;
+            library.insert(prompt);
+        }
+        Ok(library)
+    }
+
+    /// Adds `prompt`, replacing any prompt with the same name.
+    pub fn insert(&mut self, prompt: Prompt) {
+        self.prompts.insert(prompt.name.clone(), prompt);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Prompt> {
+        self.prompts.get(name)
+    }
+
+    /// The names of all prompts, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.prompts.keys().map(String::as_str)
+    }
+
+    /// Builds a ready-to-send request from the prompt called `name`.
+    pub fn request(
+        &self,
+        name: &str,
+        bindings: &Bindings,
+    ) -> Result<ChatCompletionRequest, PromptLibraryError> {
+        let prompt = self
+            .get(name)
+            .ok_or_else(|| PromptLibraryError::UnknownPrompt(name.to_string()))?;
+        Ok(prompt.request(bindings)?)
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+/// Variable bindings used to render a [`PromptTemplate`].
+pub type Bindings = HashMap<String, String>;
+
+/// Errors that can occur when rendering a prompt template.
+#[derive(Error, Debug)]
+pub enum PromptTemplateError {
+    /// A placeholder in the template has no corresponding binding.
+    #[error("Missing binding for placeholder {0}")]
+    MissingBinding(String),
+    /// A `{` was opened but never closed.
+    #[error("Unclosed placeholder at byte {0}")]
+    UnclosedPlaceholder(usize),
+    /// A `}` appeared without a matching `{`.
+    #[error("Unmatched closing brace at byte {0}")]
+    UnmatchedBrace(usize),
+}
+
+/// A prompt with `{name}` placeholders, rendered against a set of [`Bindings`].
+///
+/// Literal braces are written as `{{` and `}}`, mirroring `format!`.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::prompt_template::{Bindings, PromptTemplate};
+///
+/// let template = PromptTemplate::new("Translate {text} into {language}.");
+/// let bindings = Bindings::from([
+///     ("text".to_string(), "ciao".to_string()),
+///     ("language".to_string(), "English".to_string()),
+/// ]);
+/// assert_eq!(template.render(&bindings).unwrap(), "Translate ciao into English.");
+/// ```
+#[derive(Clone, Debug)]
+pub struct PromptTemplate {
+    template: String,
+}
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+impl PromptTemplate {
+    /// Creates a new template from its source text.
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+        }
+    }
+
+    /// Returns the raw template source.
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// Returns the names of all placeholders, in order of appearance.
+    pub fn placeholders(&self) -> Result<Vec<&str>, PromptTemplateError> {
+        Ok(self
+            .segments()?
+            .into_iter()
+            .filter_map(|segment| match segment {
+                Segment::Placeholder(name) => Some(name),
+                Segment::Literal(_) => None,
+            })
+            .collect())
+    }
+
+    /// Renders the template, replacing every placeholder with its binding.
+    pub fn render(&self, bindings: &Bindings) -> Result<String, PromptTemplateError> {
+        let mut rendered = String::with_capacity(self.template.len());
+        for segment in self.segments()? {
+            match segment {
+                Segment::Literal(text) => rendered.push_str(text),
+                Segment::Placeholder(name) => {
+                    let value = bindings
+                        .get(name)
+                        .ok_or_else(|| PromptTemplateError::MissingBinding(name.to_string()))?;
+                    rendered.push_str(value);
+                }
+            }
+        }
+        Ok(rendered)
+    }
+
+    fn segments(&self) -> Result<Vec<Segment<'_>>, PromptTemplateError> {
+        let source = self.template.as_str();
+        let bytes = source.as_bytes();
+        let mut segments = Vec::new();
+        let mut literal_start = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'{' if bytes.get(i + 1) == Some(&b'{') => {
+                    segments.push(Segment::Literal(&source[literal_start..=i]));
+                    i += 2;
+                    literal_start = i;
+                }
+                b'}' if bytes.get(i + 1) == Some(&b'}') => {
+                    segments.push(Segment::Literal(&source[literal_start..=i]));
+                    i += 2;
+                    literal_start = i;
+                }
+                b'{' => {
+                    let end = source[i..]
+                        .find('}')
+                        .map(|offset| i + offset)
+                        .ok_or(PromptTemplateError::UnclosedPlaceholder(i))?;
+                    segments.push(Segment::Literal(&source[literal_start..i]));
+                    segments.push(Segment::Placeholder(source[i + 1..end].trim()));
+                    i = end + 1;
+                    literal_start = i;
+                }
+                b'}' => return Err(PromptTemplateError::UnmatchedBrace(i)),
+                _ => i += 1,
+            }
+        }
+        segments.push(Segment::Literal(&source[literal_start..]));
+
+        Ok(segments)
+    }
+}
+
+/// Renders a template literal whose placeholders are checked at compile time.
+///
+/// The literal is expanded by `format!`: every `{name}` placeholder must have a
+/// `name = value` binding or a local variable of the same name, and every binding
+/// must be used, otherwise compilation fails.
+///
+/// Only plain `{name}` placeholders and `{{`/`}}` escapes render the same as in a
+/// [`PromptTemplate`]. Unlike a runtime template, the macro does not accept spaces
+/// around names (`{ name }`), and it accepts format specs such as `{name:>8}` that
+/// a runtime template would look up as a binding named `name:>8`. Stick to the shared
+/// subset if the literal may later move into a runtime template.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::prompt;
+///
+/// let text = "ciao";
+/// let rendered = prompt!("Translate {text} into {language}.", text = text, language = "English");
+/// assert_eq!(rendered, "Translate ciao into English.");
+/// ```
+///
+/// A placeholder without a binding is rejected:
+///
+/// ```compile_fail
+/// use deepinfra_client_rs::prompt;
+///
+/// let rendered = prompt!("Translate {text} into {language}.", text = "ciao");
+/// ```
+#[macro_export]
+macro_rules! prompt {
+    ($template:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        ::std::format!($template $(, $name = $value)*)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings(pairs: &[(&str, &str)]) -> Bindings {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn renders_placeholders_and_escapes() {
+        let template = PromptTemplate::new("{{{ greeting }}} {name}!");
+        let rendered = template
+            .render(&bindings(&[("greeting", "ciao"), ("name", "Ada")]))
+            .unwrap();
+        assert_eq!(rendered, "{ciao} Ada!");
+    }
+
+    #[test]
+    fn reports_template_errors() {
+        let template = PromptTemplate::new("{missing}");
+        assert!(matches!(
+            template.render(&Bindings::new()),
+            Err(PromptTemplateError::MissingBinding(name)) if name == "missing"
+        ));
+        assert!(matches!(
+            PromptTemplate::new("ab {open").placeholders(),
+            Err(PromptTemplateError::UnclosedPlaceholder(3))
+        ));
+        assert!(matches!(
+            PromptTemplate::new("a}").placeholders(),
+            Err(PromptTemplateError::UnmatchedBrace(1))
+        ));
+    }
+
+    #[test]
+    fn macro_matches_templates_on_the_shared_syntax() {
+        let source = "{{literal}} {text} in {language}.";
+        let runtime = PromptTemplate::new(source)
+            .render(&bindings(&[("text", "ciao"), ("language", "English")]))
+            .unwrap();
+        let checked = prompt!(
+            "{{literal}} {text} in {language}.",
+            text = "ciao",
+            language = "English"
+        );
+        assert_eq!(checked, runtime);
+    }
+}
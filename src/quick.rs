@@ -0,0 +1,57 @@
+use crate::chat_completition::{ChatCompletionError, ChatCompletionRequest, Message, UserMessage};
+use crate::client::{DeepinfraClient, DeepinfraClientBuilderError};
+
+/// The environment variable conventionally holding the DeepInfra API token.
+pub const DEFAULT_TOKEN_ENV: &str = "DEEPINFRA_API_KEY";
+
+#[derive(Debug, thiserror::Error)]
+pub enum QuickError {
+    #[error("Environment variable {0} is not set")]
+    MissingToken(String),
+    #[error("Could not build client: {0}")]
+    ClientError(#[from] DeepinfraClientBuilderError),
+    #[error("Chat completion error: {0}")]
+    ChatCompletionError(#[from] ChatCompletionError),
+}
+
+/// Builds a client with default settings from the token in the environment
+/// variable `token_env`, e.g. [`DEFAULT_TOKEN_ENV`].
+pub fn client(token_env: &str) -> Result<DeepinfraClient, QuickError> {
+    let token = std::env::var(token_env)
+        .ok()
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| QuickError::MissingToken(token_env.to_string()))?;
+    Ok(DeepinfraClient::builder().token(token).build()?)
+}
+
+/// Sends `prompt` to `model` as a single user message and returns the reply text.
+///
+/// Meant for scripts and examples; build a [`ChatCompletionRequest`] for anything
+/// beyond that.
+///
+/// # Example
+///
+/// ```no_run
+/// use deepinfra_client_rs::quick::{self, DEFAULT_TOKEN_ENV};
+///
+/// # async fn run() -> Result<(), quick::QuickError> {
+/// let reply = quick::chat(
+///     DEFAULT_TOKEN_ENV,
+///     "meta-llama/Meta-Llama-3.1-8B-Instruct",
+///     "Write a haiku about Rust.",
+/// )
+/// .await?;
+/// println!("{reply}");
+/// # Ok(())
+/// # }
+/// ```
+pub async fn chat(token_env: &str, model: &str, prompt: &str) -> Result<String, QuickError> {
+    let request = ChatCompletionRequest::builder()
+        .model(model.to_string())
+        .messages(vec![Message::User(
+            UserMessage::builder().content(prompt).build(),
+        )])
+        .build();
+    let response = client(token_env)?.chat_completition(request).await?;
+    Ok(response.content().unwrap_or_default().to_string())
+}
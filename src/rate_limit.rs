@@ -0,0 +1,84 @@
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use crate::pacing::Pacer;
+use bon::Builder;
+use governor::Quota;
+use serde::Deserialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Client-wide request limits, applied to every model request.
+///
+/// Requests wait for a free slot instead of failing, which keeps batch jobs below
+/// DeepInfra's rate limits without having to handle 429 responses.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::client::DeepinfraClient;
+/// use deepinfra_client_rs::rate_limit::RateLimit;
+///
+/// let client = DeepinfraClient::builder()
+///     .token("your_api_token")
+///     .rate_limit(
+///         RateLimit::builder()
+///             .requests_per_second(10)
+///             .max_concurrent(32)
+///             .build(),
+///     )
+///     .build();
+/// ```
+#[derive(Clone, Copy, Debug, Default, Builder, Deserialize)]
+pub struct RateLimit {
+    /// Maximum number of requests started per second.
+    pub requests_per_second: Option<u32>,
+    /// Number of requests that may start at once after an idle period
+    /// (default: `requests_per_second`).
+    pub burst: Option<u32>,
+    /// Maximum number of requests in flight at once.
+    pub max_concurrent: Option<usize>,
+}
+
+/// Enforces a [`RateLimit`] across every clone of a client.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RateLimiter {
+    pacer: Option<Pacer>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+impl From<RateLimit> for RateLimiter {
+    fn from(limit: RateLimit) -> Self {
+        let pacer = limit
+            .requests_per_second
+            .and_then(NonZeroU32::new)
+            .map(|rate| {
+                let mut quota = Quota::per_second(rate);
+                if let Some(burst) = limit.burst.and_then(NonZeroU32::new) {
+                    quota = quota.allow_burst(burst);
+                }
+                Pacer::new(quota)
+            });
+
+        Self {
+            pacer,
+            concurrency: limit
+                .max_concurrent
+                .map(|permits| Arc::new(Semaphore::new(permits.max(1)))),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Waits until a request may start.
+    pub(crate) async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        // The semaphore is never closed.
+        let permit = match &self.concurrency {
+            Some(semaphore) => Arc::clone(semaphore).acquire_owned().await.ok(),
+            None => None,
+        };
+        if let Some(pacer) = &self.pacer {
+            pacer.until_ready().await;
+        }
+        permit
+    }
+}
@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models"
+))]
+use std::time::Instant;
+
+use crate::api_error::{decode, ApiError, DecodeError};
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models"
+))]
+use crate::client::DeepinfraClient;
+use crate::metadata::ResponseMetadata;
+use http::{HeaderMap, StatusCode};
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models"
+))]
+use reqwest::RequestBuilder;
+use serde::de::DeserializeOwned;
+
+/// An endpoint response before deserialization, returned by the `*_raw` variants of
+/// the endpoint methods.
+///
+/// Non-success statuses are returned like any other response; call
+/// [`RawResponse::error_for_status`] to turn them into an [`ApiError`]. Headers are
+/// redacted like those of [`ResponseMetadata`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use deepinfra_client_rs::prelude::*;
+/// # use deepinfra_client_rs::chat_completition::*;
+/// # async fn run(client: DeepinfraClient, request: ChatCompletionRequest) -> Result<(), Box<dyn std::error::Error>> {
+/// let raw = client.chat_completion_raw(request).await?;
+/// println!("{} {:?}", raw.status(), raw.request_id());
+/// println!("{}", raw.text());
+///
+/// let response: ChatCompletionResponse = raw.error_for_status()?.json()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RawResponse {
+    /// Status, headers and latency of the response.
+    pub metadata: ResponseMetadata,
+    /// The unparsed response body.
+    pub body: Vec<u8>,
+}
+
+impl RawResponse {
+    pub fn status(&self) -> u16 {
+        self.metadata.status
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.metadata.status)
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.metadata.headers
+    }
+
+    /// The request identifier from the `x-request-id` header.
+    pub fn request_id(&self) -> Option<&str> {
+        self.metadata.request_id()
+    }
+
+    /// The body as text; invalid UTF-8 is replaced.
+    pub fn text(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.body)
+    }
+
+    /// Decodes the body as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, DecodeError> {
+        decode(&self.body)
+            .map_err(|error| error.with_request_id(self.request_id().map(str::to_string)))
+    }
+
+    /// Returns the response unchanged if it succeeded, or the corresponding
+    /// [`ApiError`] otherwise.
+    pub fn error_for_status(self) -> Result<Self, ApiError> {
+        if self.is_success() {
+            return Ok(self);
+        }
+        let status =
+            StatusCode::from_u16(self.metadata.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = self.text().into_owned();
+        Err(ApiError::from_parts(status, &self.metadata.headers, body))
+    }
+}
+
+#[cfg(any(
+    feature = "chat_completition",
+    feature = "audio_transcription",
+    feature = "inference",
+    feature = "models"
+))]
+impl DeepinfraClient {
+    /// Sends `request` and reads the whole body without checking the status.
+    pub(crate) async fn send_raw(&self, request: RequestBuilder) -> reqwest::Result<RawResponse> {
+        let started = Instant::now();
+        let response = self.send(request).await?;
+        let metadata = self.response_metadata(&response, started);
+        let body = response.bytes().await?.to_vec();
+        Ok(RawResponse {
+            metadata: metadata.finish(started),
+            body,
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use http::header::{self, HeaderName};
+use http::HeaderMap;
+
+/// Headers whose values are hidden from `Debug` output.
+///
+/// The client marks matching request headers and the headers kept in
+/// [`ResponseMetadata`](crate::metadata::ResponseMetadata) as sensitive, so logging
+/// them prints `Sensitive` instead of the value. The default policy covers the
+/// usual credential and session headers.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::client::DeepinfraClient;
+/// use deepinfra_client_rs::redact::RedactPolicy;
+///
+/// let client = DeepinfraClient::builder()
+///     .token("your_api_token")
+///     .redact(RedactPolicy::default().header("x-gateway-key"))
+///     .build()?;
+///
+/// assert!(!format!("{client:?}").contains("your_api_token"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct RedactPolicy {
+    headers: HashSet<HeaderName>,
+}
+
+impl Default for RedactPolicy {
+    fn default() -> Self {
+        Self {
+            headers: HashSet::from([
+                header::AUTHORIZATION,
+                header::PROXY_AUTHORIZATION,
+                header::COOKIE,
+                header::SET_COOKIE,
+                HeaderName::from_static("x-api-key"),
+            ]),
+        }
+    }
+}
+
+impl RedactPolicy {
+    /// A policy that redacts nothing beyond the authorization header, which the
+    /// client always hides.
+    pub fn none() -> Self {
+        Self {
+            headers: HashSet::new(),
+        }
+    }
+
+    /// Also redacts `name`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid header name.
+    pub fn header(mut self, name: &str) -> Self {
+        let name = HeaderName::try_from(name).expect("valid header name");
+        self.headers.insert(name);
+        self
+    }
+
+    /// Whether values of `name` are redacted.
+    pub fn redacts(&self, name: &HeaderName) -> bool {
+        self.headers.contains(name)
+    }
+
+    /// Marks the values of every redacted header in `headers` as sensitive.
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        for (name, value) in headers.iter_mut() {
+            if self.redacts(name) {
+                value.set_sensitive(true);
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use crate::chat_completition::ChatCompletionRequest;
+use serde_json::Value;
+use std::fmt;
+
+/// A parameter whose value differs between two requests; `None` means unset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterChange {
+    pub name: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// How the message list of a request changed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MessageChange {
+    /// A message only present in the second request, at `index` in it.
+    Added { index: usize, message: Value },
+    /// A message only present in the first request, at `index` in it.
+    Removed { index: usize, message: Value },
+    /// A message replaced in place, at `index` in the second request.
+    Changed {
+        index: usize,
+        before: Value,
+        after: Value,
+    },
+}
+
+/// The differences between two chat completion requests.
+///
+/// Requests are compared in their [canonical form](ChatCompletionRequest::canonical_json),
+/// so fields that do not influence the output are ignored. The [`Display`](fmt::Display)
+/// implementation renders a report with a line diff of changed message contents.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::*;
+///
+/// let system = |text: &str| Message::System(SystemMessage::builder().content(text).build());
+/// let user = Message::User(UserMessage::builder().content("Hi").build());
+///
+/// let before = ChatCompletionRequest::builder()
+///     .messages(vec![system("Be brief.\nUse English."), user.clone()])
+///     .build();
+/// let after = ChatCompletionRequest::builder()
+///     .messages(vec![system("Be brief.\nUse Italian."), user])
+///     .temperature(0.2)
+///     .build();
+///
+/// let diff = before.diff(&after);
+/// assert_eq!(
+///     diff.to_string(),
+///     "parameters:\n  temperature: 1.0 -> 0.2\nmessages:\n  [0] system changed:\n      Be brief.\n    - Use English.\n    + Use Italian.\n",
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestDiff {
+    pub parameters: Vec<ParameterChange>,
+    pub messages: Vec<MessageChange>,
+}
+
+impl RequestDiff {
+    /// Whether the requests are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty() && self.messages.is_empty()
+    }
+}
+
+impl ChatCompletionRequest {
+    /// Compares this request with `other`, e.g. the same prompt rendered by two
+    /// versions of a template.
+    pub fn diff(&self, other: &ChatCompletionRequest) -> RequestDiff {
+        let mut before = self.canonical_value();
+        let mut after = other.canonical_value();
+        let before_messages = take_messages(&mut before);
+        let after_messages = take_messages(&mut after);
+
+        let (before, after) = match (before, after) {
+            (Value::Object(before), Value::Object(after)) => (before, after),
+            _ => Default::default(),
+        };
+        let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+        names.sort();
+        names.dedup();
+        let parameters = names
+            .into_iter()
+            .filter(|name| before.get(*name) != after.get(*name))
+            .map(|name| ParameterChange {
+                name: name.clone(),
+                before: before.get(name).cloned(),
+                after: after.get(name).cloned(),
+            })
+            .collect();
+
+        RequestDiff {
+            parameters,
+            messages: diff_messages(before_messages, after_messages),
+        }
+    }
+}
+
+fn take_messages(request: &mut Value) -> Vec<Value> {
+    match request.get_mut("messages").map(Value::take) {
+        Some(Value::Array(messages)) => messages,
+        _ => Vec::new(),
+    }
+}
+
+fn diff_messages(before: Vec<Value>, after: Vec<Value>) -> Vec<MessageChange> {
+    let mut changes = Vec::new();
+    let mut removed: Vec<usize> = Vec::new();
+    let mut added: Vec<usize> = Vec::new();
+    let operations = lcs_diff(&before, &after);
+
+    // Each run of deletions and insertions between two unchanged messages is turned
+    // into changes, pairing messages of the same role as in-place edits.
+    for operation in operations.iter().copied().chain([DiffOp::Equal(0)]) {
+        match operation {
+            DiffOp::Delete(index) => removed.push(index),
+            DiffOp::Insert(index) => added.push(index),
+            DiffOp::Equal(..) => {
+                let mut added_run = added.drain(..).peekable();
+                for index in removed.drain(..) {
+                    let message = before[index].clone();
+                    match added_run.next_if(|&new| after[new].get("role") == message.get("role")) {
+                        Some(new) => changes.push(MessageChange::Changed {
+                            index: new,
+                            before: message,
+                            after: after[new].clone(),
+                        }),
+                        None => changes.push(MessageChange::Removed { index, message }),
+                    }
+                }
+                changes.extend(added_run.map(|index| MessageChange::Added {
+                    index,
+                    message: after[index].clone(),
+                }));
+            }
+        }
+    }
+    changes
+}
+
+#[derive(Clone, Copy, Debug)]
+enum DiffOp {
+    Equal(usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Diffs two sequences via their longest common subsequence, deletions first.
+fn lcs_diff<T: PartialEq>(before: &[T], after: &[T]) -> Vec<DiffOp> {
+    let (n, m) = (before.len(), after.len());
+    // lengths[i][j]: LCS length of before[i..] and after[j..].
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if before[i] == after[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut operations = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && before[i] == after[j] {
+            operations.push(DiffOp::Equal(i));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lengths[i + 1][j] >= lengths[i][j + 1]) {
+            operations.push(DiffOp::Delete(i));
+            i += 1;
+        } else {
+            operations.push(DiffOp::Insert(j));
+            j += 1;
+        }
+    }
+    operations
+}
+
+fn role(message: &Value) -> &str {
+    message
+        .get("role")
+        .and_then(Value::as_str)
+        .unwrap_or("message")
+}
+
+/// The text of a message, or its JSON for multi-part content.
+fn content(message: &Value) -> String {
+    match message.get("content") {
+        Some(Value::String(text)) => text.clone(),
+        Some(content) => content.to_string(),
+        None => String::new(),
+    }
+}
+
+fn write_value(f: &mut fmt::Formatter<'_>, value: &Option<Value>) -> fmt::Result {
+    match value {
+        Some(value) => write!(f, "{value}"),
+        None => f.write_str("(unset)"),
+    }
+}
+
+fn write_lines(f: &mut fmt::Formatter<'_>, prefix: &str, text: &str) -> fmt::Result {
+    text.lines()
+        .try_for_each(|line| writeln!(f, "    {prefix} {line}"))
+}
+
+impl fmt::Display for RequestDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no differences");
+        }
+        if !self.parameters.is_empty() {
+            writeln!(f, "parameters:")?;
+            for change in &self.parameters {
+                write!(f, "  {}: ", change.name)?;
+                write_value(f, &change.before)?;
+                f.write_str(" -> ")?;
+                write_value(f, &change.after)?;
+                writeln!(f)?;
+            }
+        }
+        if !self.messages.is_empty() {
+            writeln!(f, "messages:")?;
+            for change in &self.messages {
+                match change {
+                    MessageChange::Added { index, message } => {
+                        writeln!(f, "  [{index}] {} added:", role(message))?;
+                        write_lines(f, "+", &content(message))?;
+                    }
+                    MessageChange::Removed { index, message } => {
+                        writeln!(f, "  [{index}] {} removed:", role(message))?;
+                        write_lines(f, "-", &content(message))?;
+                    }
+                    MessageChange::Changed {
+                        index,
+                        before,
+                        after,
+                    } => {
+                        writeln!(f, "  [{index}] {} changed:", role(after))?;
+                        let (before, after) = (content(before), content(after));
+                        let before: Vec<&str> = before.lines().collect();
+                        let after: Vec<&str> = after.lines().collect();
+                        for operation in lcs_diff(&before, &after) {
+                            match operation {
+                                DiffOp::Equal(i) => write_lines(f, " ", before[i])?,
+                                DiffOp::Delete(i) => write_lines(f, "-", before[i])?,
+                                DiffOp::Insert(j) => write_lines(f, "+", after[j])?,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::chat_completition::{ChatCompletionRequest, Message};
+use std::sync::Arc;
+
+/// Every chat completion parameter except the messages, shared behind an `Arc`.
+///
+/// Cloning a template is a reference-count bump, so one template can be handed to
+/// many tasks and combined with per-call messages in hot loops.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::chat_completition::*;
+/// use deepinfra_client_rs::request_template::RequestTemplate;
+///
+/// let template = RequestTemplate::from(
+///     ChatCompletionRequest::builder()
+///         .model("meta-llama/Meta-Llama-3.1-8B-Instruct".to_string())
+///         .temperature(0.2)
+///         .messages(Vec::new())
+///         .build(),
+/// );
+///
+/// let request = template.request(vec![Message::User(
+///     UserMessage::builder().content("Hello!").build(),
+/// )]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RequestTemplate {
+    parameters: Arc<ChatCompletionRequest>,
+}
+
+impl RequestTemplate {
+    /// Builds a complete request from this template and `messages`.
+    pub fn request(&self, messages: Vec<Message>) -> ChatCompletionRequest {
+        let mut request = ChatCompletionRequest::clone(&self.parameters);
+        request.messages = messages;
+        request
+    }
+
+    /// Returns the model this template targets.
+    pub fn model(&self) -> &str {
+        &self.parameters.model
+    }
+}
+
+impl From<ChatCompletionRequest> for RequestTemplate {
+    /// Creates a template from a request, discarding its messages.
+    fn from(mut request: ChatCompletionRequest) -> Self {
+        request.messages = Vec::new();
+        Self {
+            parameters: Arc::new(request),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::api_error::{ApiError, DecodeError};
+use crate::client::DeepinfraClient;
+use crate::inference::{InferenceError, InferenceResponse, InferenceStatus};
+use crate::metadata::{InferenceMetrics, ResponseMetadata};
+use crate::shutdown::ShutdownError;
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::instrument;
+
+/// A request to order documents by their relevance to a query.
+#[derive(Clone, Debug, Builder)]
+pub struct RerankRequest {
+    /// The reranker model to use (default: "BAAI/bge-reranker-v2-m3").
+    #[builder(default = "BAAI/bge-reranker-v2-m3".to_string(), into)]
+    model: String,
+    #[builder(into)]
+    query: String,
+    /// The candidate documents, e.g. the passages returned by a vector search.
+    #[builder(with = |documents: impl IntoIterator<Item = impl Into<String>>| {
+        documents.into_iter().map(Into::into).collect()
+    })]
+    documents: Vec<String>,
+    /// Keep only the `top_n` most relevant documents (default: all of them).
+    top_n: Option<usize>,
+    /// Overrides the client's timeout for this request.
+    timeout: Option<Duration>,
+}
+
+/// The body of a [`RerankRequest`] as the API expects it.
+#[derive(Serialize)]
+struct WireRerankRequest<'a> {
+    queries: [&'a str; 1],
+    documents: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct RerankOutput {
+    scores: Vec<f64>,
+    input_tokens: Option<u32>,
+}
+
+/// A document with its relevance score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RerankResult {
+    /// Position of the document in [`RerankRequest`]'s `documents`.
+    pub index: usize,
+    /// Relevance to the query; higher is more relevant. The scale depends on the
+    /// model.
+    pub score: f64,
+    pub document: String,
+}
+
+#[derive(Debug)]
+pub struct RerankResponse {
+    /// The documents, most relevant first.
+    pub results: Vec<RerankResult>,
+    pub input_tokens: Option<u32>,
+    pub request_id: Option<String>,
+    pub inference_status: Option<InferenceStatus>,
+    /// Status and headers of the HTTP response, including DeepInfra runtime headers.
+    pub metadata: ResponseMetadata,
+}
+
+impl RerankResponse {
+    /// Splits the request latency into model runtime, as reported in
+    /// `inference_status`, and queueing plus network overhead.
+    pub fn inference_metrics(&self) -> InferenceMetrics {
+        let runtime = self
+            .inference_status
+            .as_ref()
+            .and_then(InferenceStatus::runtime);
+        self.metadata.inference_metrics_with(runtime)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RerankError {
+    #[error("Request error: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("API error: {0}")]
+    ApiError(#[from] ApiError),
+    #[error("Could not decode response: {0}")]
+    DecodeError(#[from] DecodeError),
+    #[error("Expected {expected} scores, one per document, but got {actual}")]
+    ScoreCountMismatch { expected: usize, actual: usize },
+    #[error("Client shutdown: {0}")]
+    ShutdownError(#[from] ShutdownError),
+}
+
+impl From<InferenceError> for RerankError {
+    fn from(error: InferenceError) -> Self {
+        match error {
+            InferenceError::Transport(error) => error.into(),
+            InferenceError::ApiError(error) => error.into(),
+            InferenceError::DecodeError(error) => error.into(),
+            InferenceError::ShutdownError(error) => error.into(),
+        }
+    }
+}
+
+impl DeepinfraClient {
+    /// Scores documents against a query with a reranker model and returns them most
+    /// relevant first.
+    ///
+    /// A request without documents returns no results without calling the API.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::rerank::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), RerankError> {
+    /// let request = RerankRequest::builder()
+    ///     .query("How do I reset my password?")
+    ///     .documents([
+    ///         "Passwords can be reset from the login page.",
+    ///         "Our office is closed on public holidays.",
+    ///         "Two-factor authentication protects your account.",
+    ///     ])
+    ///     .top_n(2)
+    ///     .build();
+    ///
+    /// let response = client.rerank(request).await?;
+    /// for result in response.results {
+    ///     println!("{:.3} {}", result.score, result.document);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request))]
+    pub async fn rerank(&self, request: RerankRequest) -> Result<RerankResponse, RerankError> {
+        if request.documents.is_empty() {
+            return Ok(RerankResponse {
+                results: Vec::new(),
+                input_tokens: None,
+                request_id: None,
+                inference_status: None,
+                metadata: ResponseMetadata::default(),
+            });
+        }
+        self.lifecycle.track(self.send_rerank(request)).await?
+    }
+
+    async fn send_rerank(&self, request: RerankRequest) -> Result<RerankResponse, RerankError> {
+        let body = WireRerankRequest {
+            queries: [&request.query],
+            documents: &request.documents,
+        };
+        let (response, metadata): (InferenceResponse<RerankOutput>, _) = self
+            .send_inference(&request.model, &body, request.timeout)
+            .await?;
+
+        let RerankOutput {
+            scores,
+            input_tokens,
+        } = response.output;
+        if scores.len() != request.documents.len() {
+            return Err(RerankError::ScoreCountMismatch {
+                expected: request.documents.len(),
+                actual: scores.len(),
+            });
+        }
+
+        let mut results: Vec<RerankResult> = request
+            .documents
+            .into_iter()
+            .zip(scores)
+            .enumerate()
+            .map(|(index, (document, score))| RerankResult {
+                index,
+                score,
+                document,
+            })
+            .collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        if let Some(top_n) = request.top_n {
+            results.truncate(top_n);
+        }
+
+        Ok(RerankResponse {
+            results,
+            input_tokens,
+            request_id: response.request_id,
+            inference_status: response.inference_status,
+            metadata,
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use crate::chat_completition::{ChatCompletionError, ChatCompletionRequest, Message};
+use crate::client::DeepinfraClient;
+use futures::stream::{self, StreamExt};
+use std::collections::{HashMap, HashSet};
+use tracing::instrument;
+
+/// The output produced for a single seed.
+#[derive(Debug)]
+pub struct SeedRun {
+    pub seed: u64,
+    /// Content of the first choice, or the error returned for this seed.
+    pub output: Result<String, ChatCompletionError>,
+}
+
+/// Outputs of a seed sweep together with divergence metrics over the successful runs.
+#[derive(Debug)]
+pub struct SeedSweepReport {
+    /// One entry per seed, in the order the seeds were given.
+    pub runs: Vec<SeedRun>,
+    /// Number of distinct outputs among successful runs.
+    pub distinct_outputs: usize,
+    /// Share of successful runs that produced the most common output (1.0 means fully
+    /// deterministic across seeds).
+    pub modal_share: f64,
+    /// Mean pairwise Jaccard similarity of the whitespace-separated tokens of each
+    /// output, between 0 and 1.
+    pub mean_pairwise_similarity: f64,
+}
+
+impl DeepinfraClient {
+    /// Runs `request` once per seed with at most `concurrency` requests in flight and
+    /// reports how much the outputs diverge.
+    #[instrument(skip(self, request, seeds))]
+    pub async fn seed_sweep(
+        &self,
+        request: ChatCompletionRequest,
+        seeds: impl IntoIterator<Item = u64>,
+        concurrency: usize,
+    ) -> SeedSweepReport {
+        let request = &request;
+
+        let runs: Vec<SeedRun> = stream::iter(seeds)
+            .map(|seed| async move {
+                let mut request = request.clone();
+                request.seed = Some(seed);
+
+                let output = self.chat_completition(request).await.map(|response| {
+                    response
+                        .choices
+                        .into_iter()
+                        .next()
+                        .and_then(|choice| match choice.message {
+                            Message::Assistant(message) => Some(message.content),
+                            _ => None,
+                        })
+                        .unwrap_or_default()
+                });
+
+                SeedRun { seed, output }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let outputs: Vec<&str> = runs
+            .iter()
+            .filter_map(|run| run.output.as_deref().ok())
+            .collect();
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for output in &outputs {
+            *counts.entry(output).or_default() += 1;
+        }
+        let modal_share = match counts.values().max() {
+            Some(max) => *max as f64 / outputs.len() as f64,
+            None => 0.0,
+        };
+
+        SeedSweepReport {
+            distinct_outputs: counts.len(),
+            modal_share,
+            mean_pairwise_similarity: mean_pairwise_similarity(&outputs),
+            runs,
+        }
+    }
+}
+
+fn mean_pairwise_similarity(outputs: &[&str]) -> f64 {
+    let token_sets: Vec<HashSet<&str>> = outputs
+        .iter()
+        .map(|output| output.split_whitespace().collect())
+        .collect();
+
+    let mut total = 0.0;
+    let mut pairs = 0;
+    for (i, a) in token_sets.iter().enumerate() {
+        for b in &token_sets[i + 1..] {
+            let union = a.union(b).count();
+            total += if union == 0 {
+                1.0
+            } else {
+                a.intersection(b).count() as f64 / union as f64
+            };
+            pairs += 1;
+        }
+    }
+
+    if pairs == 0 {
+        1.0
+    } else {
+        total / pairs as f64
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::BoxError;
+
+/// Turns request bodies into the bytes sent on the wire.
+///
+/// The default [`JsonSerializer`] uses `serde_json`. A custom serializer can format
+/// floats differently or reorder keys for gateways that are picky about the exact
+/// bytes. Any `Fn(&dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxError>`
+/// closure is a serializer.
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::client::DeepinfraClient;
+/// use deepinfra_client_rs::erased_serde;
+/// use deepinfra_client_rs::error::BoxError;
+///
+/// let pretty = |body: &dyn erased_serde::Serialize| -> Result<Vec<u8>, BoxError> {
+///     Ok(serde_json::to_vec_pretty(body)?)
+/// };
+///
+/// let client = DeepinfraClient::builder()
+///     .token("your_api_token")
+///     .serializer(pretty)
+///     .build();
+/// ```
+pub trait BodySerializer: Send + Sync {
+    fn serialize(&self, body: &dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxError>;
+}
+
+impl<F> BodySerializer for F
+where
+    F: Fn(&dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxError> + Send + Sync,
+{
+    fn serialize(&self, body: &dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxError> {
+        self(body)
+    }
+}
+
+/// Serializes bodies with `serde_json`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsonSerializer;
+
+impl BodySerializer for JsonSerializer {
+    fn serialize(&self, body: &dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxError> {
+        Ok(serde_json::to_vec(body)?)
+    }
+}
+
+/// A [`BodySerializer`] shared between clones of a client.
+#[derive(Clone)]
+pub struct SharedSerializer(Arc<dyn BodySerializer>);
+
+impl<S: BodySerializer + 'static> From<S> for SharedSerializer {
+    fn from(serializer: S) -> Self {
+        Self(Arc::new(serializer))
+    }
+}
+
+impl SharedSerializer {
+    pub(crate) fn serialize(
+        &self,
+        body: &dyn erased_serde::Serialize,
+    ) -> Result<Vec<u8>, BoxError> {
+        self.0.serialize(body)
+    }
+}
+
+impl Default for SharedSerializer {
+    fn default() -> Self {
+        JsonSerializer.into()
+    }
+}
+
+impl fmt::Debug for SharedSerializer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedSerializer")
+    }
+}
@@ -0,0 +1,211 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::sync::{watch, Notify};
+use tracing::instrument;
+
+use crate::client::DeepinfraClient;
+
+/// Errors returned by requests affected by [`DeepinfraClient::shutdown`].
+#[derive(Error, Debug)]
+pub enum ShutdownError {
+    /// The client is shutting down and no longer accepts new requests.
+    #[error("Client is shutting down")]
+    ShuttingDown,
+    /// The request was still in flight when the shutdown timeout elapsed.
+    #[error("Request aborted by client shutdown")]
+    Aborted,
+}
+
+/// How a call to [`DeepinfraClient::shutdown`] completed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownOutcome {
+    /// Every in-flight request finished before the timeout.
+    Drained,
+    /// The timeout elapsed and this many requests were aborted.
+    Aborted(usize),
+}
+
+/// Tracks in-flight requests shared by every clone of a client.
+#[derive(Debug)]
+pub(crate) struct Lifecycle {
+    accepting: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+    abort: watch::Sender<bool>,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self {
+            accepting: AtomicBool::new(true),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+            abort: watch::Sender::new(false),
+        }
+    }
+}
+
+/// Registration of one in-flight request; deregisters on drop.
+pub(crate) struct InFlight {
+    lifecycle: Arc<Lifecycle>,
+    abort: watch::Receiver<bool>,
+}
+
+impl InFlight {
+    /// Resolves once shutdown decides to abort in-flight requests.
+    pub(crate) async fn aborted(&mut self) {
+        // The sender lives in the lifecycle this registration keeps alive.
+        let _ = self.abort.wait_for(|aborted| *aborted).await;
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.lifecycle.in_flight.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.lifecycle.idle.notify_waiters();
+        }
+    }
+}
+
+impl Lifecycle {
+    /// Registers a new in-flight request, failing if the client is shutting down.
+    pub(crate) fn enter(self: &Arc<Self>) -> Result<InFlight, ShutdownError> {
+        // Register before checking the flag so shutdown never misses this request.
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        let in_flight = InFlight {
+            lifecycle: Arc::clone(self),
+            abort: self.abort.subscribe(),
+        };
+        if !self.accepting.load(Ordering::Acquire) {
+            return Err(ShutdownError::ShuttingDown);
+        }
+        Ok(in_flight)
+    }
+
+    /// Runs `future` as a tracked request, aborting it if shutdown times out.
+    pub(crate) async fn track<F: Future>(
+        self: &Arc<Self>,
+        future: F,
+    ) -> Result<F::Output, ShutdownError> {
+        let mut in_flight = self.enter()?;
+        tokio::select! {
+            output = future => Ok(output),
+            _ = in_flight.aborted() => Err(ShutdownError::Aborted),
+        }
+    }
+
+    async fn wait_idle(&self) {
+        loop {
+            let notified = self.idle.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.in_flight.load(Ordering::Acquire) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+impl DeepinfraClient {
+    /// Stops accepting new requests and waits up to `timeout` for in-flight requests
+    /// to finish, then aborts the remaining ones.
+    ///
+    /// Shutdown applies to every clone of this client. Requests started afterwards fail
+    /// with [`ShutdownError::ShuttingDown`]; aborted ones fail with
+    /// [`ShutdownError::Aborted`].
+    ///
+    /// A request only notices the abort when its future or stream is polled, so after
+    /// aborting, shutdown waits at most `timeout` again for them to wind down. Streams
+    /// nobody reads keep their connection until they are dropped.
+    #[instrument(skip(self))]
+    pub async fn shutdown(&self, timeout: Duration) -> ShutdownOutcome {
+        let lifecycle = &self.lifecycle;
+        lifecycle.accepting.store(false, Ordering::Release);
+
+        if tokio::time::timeout(timeout, lifecycle.wait_idle())
+            .await
+            .is_ok()
+        {
+            return ShutdownOutcome::Drained;
+        }
+
+        let aborted = lifecycle.in_flight.load(Ordering::Acquire);
+        tracing::warn!(aborted, "shutdown timed out, aborting in-flight requests");
+        lifecycle.abort.send_replace(true);
+        if tokio::time::timeout(timeout, lifecycle.wait_idle())
+            .await
+            .is_err()
+        {
+            let remaining = lifecycle.in_flight.load(Ordering::Acquire);
+            tracing::warn!(remaining, "aborted requests are not being polled");
+        }
+
+        ShutdownOutcome::Aborted(aborted)
+    }
+
+    /// Returns `true` once [`DeepinfraClient::shutdown`] has been called.
+    pub fn is_shutting_down(&self) -> bool {
+        !self.lifecycle.accepting.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> DeepinfraClient {
+        DeepinfraClient::builder().token("token").build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn drains_without_in_flight_requests() {
+        let client = client();
+        assert_eq!(
+            client.shutdown(Duration::from_millis(10)).await,
+            ShutdownOutcome::Drained
+        );
+        assert!(client.is_shutting_down());
+        assert!(matches!(
+            client.lifecycle.track(async {}).await,
+            Err(ShutdownError::ShuttingDown)
+        ));
+    }
+
+    #[tokio::test]
+    async fn aborts_tracked_requests() {
+        let client = client();
+        let request = {
+            let client = client.clone();
+            tokio::spawn(async move { client.lifecycle.track(std::future::pending::<()>()).await })
+        };
+        tokio::task::yield_now().await;
+
+        let outcome = client.shutdown(Duration::from_millis(10)).await;
+        assert_eq!(outcome, ShutdownOutcome::Aborted(1));
+        assert!(matches!(
+            request.await.unwrap(),
+            Err(ShutdownError::Aborted)
+        ));
+    }
+
+    #[tokio::test]
+    async fn returns_while_an_idle_stream_holds_its_registration() {
+        let client = client();
+        // An open stream that nobody polls holds its registration like this.
+        let stream = client.lifecycle.enter().unwrap();
+
+        let outcome = tokio::time::timeout(
+            Duration::from_secs(5),
+            client.shutdown(Duration::from_millis(10)),
+        )
+        .await
+        .expect("shutdown hung on an idle stream");
+        assert_eq!(outcome, ShutdownOutcome::Aborted(1));
+        drop(stream);
+    }
+}
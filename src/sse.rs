@@ -0,0 +1,132 @@
+use std::collections::VecDeque;
+
+use futures::stream::{self, Stream, StreamExt};
+
+/// Incremental decoder turning raw bytes into the `data` payloads of SSE events.
+#[derive(Default)]
+struct Decoder {
+    buffer: Vec<u8>,
+    data: Vec<String>,
+    events: VecDeque<String>,
+}
+
+impl Decoder {
+    fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+        while let Some(newline) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            self.line(line.trim_end_matches(['\n', '\r']));
+        }
+    }
+
+    fn finish(&mut self) {
+        if !self.buffer.is_empty() {
+            let line = String::from_utf8_lossy(&std::mem::take(&mut self.buffer)).into_owned();
+            self.line(line.trim_end_matches('\r'));
+        }
+        self.dispatch();
+    }
+
+    fn line(&mut self, line: &str) {
+        if line.is_empty() {
+            self.dispatch();
+        } else if let Some(data) = line.strip_prefix("data:") {
+            self.data
+                .push(data.strip_prefix(' ').unwrap_or(data).to_string());
+        }
+        // Comments (`:`) and the `event`, `id` and `retry` fields are not used by the API.
+    }
+
+    fn dispatch(&mut self) {
+        if !self.data.is_empty() {
+            self.events.push_back(self.data.join("\n"));
+            self.data.clear();
+        }
+    }
+}
+
+/// Streams the `data` payload of every server-sent event in the response body.
+pub(crate) fn data_events(
+    response: reqwest::Response,
+) -> impl Stream<Item = Result<String, reqwest::Error>> + Send + 'static {
+    let bytes = response.bytes_stream().boxed();
+
+    stream::unfold(
+        (bytes, Decoder::default(), false),
+        |(mut bytes, mut decoder, mut done)| async move {
+            loop {
+                if let Some(event) = decoder.events.pop_front() {
+                    return Some((Ok(event), (bytes, decoder, done)));
+                }
+                if done {
+                    return None;
+                }
+                match bytes.next().await {
+                    Some(Ok(chunk)) => decoder.feed(&chunk),
+                    Some(Err(error)) => return Some((Err(error), (bytes, decoder, true))),
+                    None => {
+                        decoder.finish();
+                        done = true;
+                    }
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(chunks: &[&[u8]]) -> Vec<String> {
+        let mut decoder = Decoder::default();
+        for chunk in chunks {
+            decoder.feed(chunk);
+        }
+        decoder.finish();
+        decoder.events.into()
+    }
+
+    #[test]
+    fn joins_lines_split_across_chunks() {
+        let events = decode(&[b"da", b"ta: {\"a\"", b":1}\n", b"\ndata: 2\n\n"]);
+        assert_eq!(events, ["{\"a\":1}", "2"]);
+    }
+
+    #[test]
+    fn joins_characters_split_across_chunks() {
+        let text = "data: caffè\n\n".as_bytes();
+        let split = text.len() - 3;
+        assert_eq!(decode(&[&text[..split], &text[split..]]), ["caffè"]);
+    }
+
+    #[test]
+    fn accepts_crlf_line_endings() {
+        let events = decode(&[b"data: 1\r\n\r\ndata: 2\r", b"\n\r\n"]);
+        assert_eq!(events, ["1", "2"]);
+    }
+
+    #[test]
+    fn joins_multi_line_data_with_newlines() {
+        let events = decode(&[b"data: first\ndata:second\ndata: third\n\n"]);
+        assert_eq!(events, ["first\nsecond\nthird"]);
+    }
+
+    #[test]
+    fn ignores_comments_and_other_fields() {
+        let events = decode(&[b": keep-alive\n\nevent: message\nid: 7\ndata: 1\nretry: 10\n\n"]);
+        assert_eq!(events, ["1"]);
+    }
+
+    #[test]
+    fn passes_the_done_marker_through() {
+        let events = decode(&[b"data: 1\n\ndata: [DONE]\n\n"]);
+        assert_eq!(events, ["1", "[DONE]"]);
+    }
+
+    #[test]
+    fn dispatches_a_trailing_event_without_blank_line() {
+        assert_eq!(decode(&[b"data: 1\n\ndata: 2"]), ["1", "2"]);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ResponseFormat, ResponseFormatType,
+};
+use crate::client::DeepinfraClient;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use tracing::instrument;
+
+#[derive(Debug, thiserror::Error)]
+pub enum StructuredOutputError {
+    #[error("Chat completion error: {0}")]
+    ChatCompletionError(#[from] ChatCompletionError),
+    #[error("The model returned no content")]
+    EmptyResponse,
+    #[error("The model output does not match the schema {0}")]
+    InvalidOutput(#[from] serde_json::Error),
+}
+
+impl ResponseFormat {
+    /// A strict `json_schema` format generated from `T`.
+    pub fn json_schema<T: JsonSchema>() -> Self {
+        let mut schema = schemars::schema_for!(T);
+        schema.remove("$schema");
+        ResponseFormat {
+            response_type: ResponseFormatType::JsonSchema {
+                name: T::schema_name().into_owned(),
+                schema: schema.to_value(),
+                strict: Some(true),
+            },
+        }
+    }
+}
+
+impl DeepinfraClient {
+    /// Sends `request` constrained to the JSON schema of `T` and parses the answer.
+    ///
+    /// Any response format already set on the request is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # use deepinfra_client_rs::structured::StructuredOutputError;
+    /// use schemars::JsonSchema;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, JsonSchema)]
+    /// struct Sentiment {
+    ///     label: String,
+    ///     score: f64,
+    /// }
+    ///
+    /// # async fn run(client: DeepinfraClient) -> Result<(), StructuredOutputError> {
+    /// let request = ChatCompletionRequest::builder()
+    ///     .messages(vec![Message::User(
+    ///         UserMessage::builder().content("Rate: 'I love it!'").build(),
+    ///     )])
+    ///     .build();
+    ///
+    /// let sentiment: Sentiment = client.chat_completion_structured(request).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request))]
+    pub async fn chat_completion_structured<T: DeserializeOwned + JsonSchema>(
+        &self,
+        mut request: ChatCompletionRequest,
+    ) -> Result<T, StructuredOutputError> {
+        request.response_format = Some(ResponseFormat::json_schema::<T>());
+
+        let response = self.chat_completition(request).await?;
+        let content = response
+            .content()
+            .ok_or(StructuredOutputError::EmptyResponse)?;
+
+        Ok(serde_json::from_str(content)?)
+    }
+}
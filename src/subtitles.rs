@@ -0,0 +1,117 @@
+use crate::audio_transcription::AudioTranscriptionResponse;
+use bon::Builder;
+use std::fmt::Write;
+
+/// Layout options for transcript exports.
+#[derive(Clone, Debug, Builder)]
+pub struct SubtitleOptions {
+    /// Maximum number of characters per line; longer segments wrap at word
+    /// boundaries. Words longer than the limit are kept whole.
+    #[builder(default = 42)]
+    pub max_line_length: usize,
+    /// Seconds added to every timestamp, e.g. to align with a video that starts
+    /// before the audio. Negative values shift earlier; times are clamped at zero.
+    #[builder(default = 0.0)]
+    pub offset: f64,
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl AudioTranscriptionResponse {
+    /// Renders the segments as SubRip (SRT) subtitles.
+    ///
+    /// Request the `verbose_json` response format to receive segments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deepinfra_client_rs::audio_transcription::AudioTranscriptionResponse;
+    /// use deepinfra_client_rs::subtitles::SubtitleOptions;
+    ///
+    /// let response: AudioTranscriptionResponse = serde_json::from_str(
+    ///     r#"{"text": "Hello there.", "segments": [{"start": 0.0, "end": 1.5, "text": " Hello there."}]}"#,
+    /// )?;
+    ///
+    /// let options = SubtitleOptions::builder().offset(60.0).build();
+    /// assert_eq!(
+    ///     response.to_srt(&options),
+    ///     "1\n00:01:00,000 --> 00:01:01,500\nHello there.\n\n",
+    /// );
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    pub fn to_srt(&self, options: &SubtitleOptions) -> String {
+        let mut output = String::new();
+        for (index, segment) in self.segments.iter().enumerate() {
+            let _ = writeln!(
+                output,
+                "{}\n{} --> {}\n{}\n",
+                index + 1,
+                timestamp(segment.start + options.offset, ','),
+                timestamp(segment.end + options.offset, ','),
+                wrap_text(&segment.text, options.max_line_length),
+            );
+        }
+        output
+    }
+
+    /// Renders the segments as WebVTT subtitles.
+    ///
+    /// Request the `verbose_json` response format to receive segments.
+    pub fn to_vtt(&self, options: &SubtitleOptions) -> String {
+        let mut output = String::from("WEBVTT\n\n");
+        for segment in &self.segments {
+            let _ = writeln!(
+                output,
+                "{} --> {}\n{}\n",
+                timestamp(segment.start + options.offset, '.'),
+                timestamp(segment.end + options.offset, '.'),
+                wrap_text(&segment.text, options.max_line_length),
+            );
+        }
+        output
+    }
+
+    /// Renders the transcript as plain text wrapped to the configured line length.
+    ///
+    /// Each segment starts on a new line; without segments the whole text is wrapped.
+    pub fn to_plain_text(&self, options: &SubtitleOptions) -> String {
+        if self.segments.is_empty() {
+            return wrap_text(&self.text, options.max_line_length) + "\n";
+        }
+        self.segments
+            .iter()
+            .map(|segment| wrap_text(&segment.text, options.max_line_length) + "\n")
+            .collect()
+    }
+}
+
+/// Breaks `text` into lines of at most `max_line_length` characters.
+fn wrap_text(text: &str, max_line_length: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= max_line_length => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines.join("\n")
+}
+
+/// Formats `seconds` as `HH:MM:SS` followed by `separator` and milliseconds.
+fn timestamp(seconds: f64, separator: char) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000,
+    )
+}
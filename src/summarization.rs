@@ -0,0 +1,334 @@
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, Message, SystemMessage, Usage, UserMessage,
+};
+use crate::chunking::{ApproximateTokenCounter, Chunker, ChunkingStrategy, TokenCounter};
+use crate::client::DeepinfraClient;
+#[cfg(feature = "models")]
+use crate::models::ModelsError;
+use bon::Builder;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use tracing::instrument;
+
+const DEFAULT_INSTRUCTIONS: &str = "Summarize the text provided by the user. \
+    Keep all key facts, names, figures and decisions. Reply with the summary only.";
+
+const REDUCE_INSTRUCTIONS: &str = "The user provides several partial summaries of \
+    consecutive parts of one document. Merge them into a single coherent summary, \
+    removing repetition. Reply with the summary only.";
+
+/// Tokens reserved for the instructions and chat template around each chunk.
+const PROMPT_OVERHEAD_TOKENS: usize = 256;
+
+/// Options for [`DeepinfraClient::summarize_long`].
+#[derive(Clone, Debug, Builder)]
+pub struct SummarizeOptions {
+    /// Model used for both the map and the reduce steps.
+    #[builder(default = "deepseek-ai/DeepSeek-V3".to_string(), into)]
+    pub model: String,
+    /// Context window of `model`, in tokens. When unset, it is looked up in the model
+    /// catalogue, which requires the `models` feature; set it to skip the lookup.
+    pub context_window: Option<usize>,
+    /// Maximum number of tokens generated per summary.
+    #[builder(default = 1024)]
+    pub max_summary_tokens: u32,
+    /// Maximum number of rounds merging partial summaries into fewer ones before
+    /// giving up; the final merge into one summary is not counted.
+    #[builder(default = 8)]
+    pub max_reduce_rounds: usize,
+    /// Maximum number of chunk summaries requested concurrently.
+    #[builder(default = 4)]
+    pub concurrency: usize,
+    /// Instructions replacing the default summarization system prompt.
+    #[builder(into)]
+    pub instructions: Option<String>,
+}
+
+impl Default for SummarizeOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SummarizeError {
+    #[error("Chat completion failed: {0}")]
+    ChatCompletionError(#[from] ChatCompletionError),
+    #[cfg(feature = "models")]
+    #[error("Could not look up the context window: {0}")]
+    ModelsError(#[from] ModelsError),
+    #[error("The context window of {0} is unknown, set it in the options")]
+    UnknownContextWindow(String),
+    #[error(
+        "A context window of {context_window} tokens leaves no room for input next to \
+         {max_summary_tokens} summary tokens and the prompt"
+    )]
+    ContextWindowTooSmall {
+        context_window: usize,
+        max_summary_tokens: u32,
+    },
+    #[error("The partial summaries did not fit in one request after {0} reduce rounds")]
+    TooManyReduceRounds(usize),
+}
+
+/// The result of [`DeepinfraClient::summarize_long`].
+#[derive(Clone, Debug)]
+pub struct Summary {
+    /// The final summary.
+    pub summary: String,
+    /// Summaries of the individual chunks; empty when the input fit in one request.
+    pub chunk_summaries: Vec<String>,
+    /// Usage summed over every request made.
+    pub usage: Option<Usage>,
+}
+
+impl DeepinfraClient {
+    /// Summarizes text of any length.
+    ///
+    /// Input that does not fit in the model context window is split into chunks that
+    /// are summarized concurrently; the partial summaries are then merged, in several
+    /// rounds if necessary, into a single summary.
+    ///
+    /// Fails without sending a request if the context window leaves no room for input,
+    /// and after `max_reduce_rounds` rounds if the partial summaries still do not fit.
+    #[instrument(skip(self, text))]
+    pub async fn summarize_long(
+        &self,
+        text: &str,
+        options: &SummarizeOptions,
+    ) -> Result<Summary, SummarizeError> {
+        let context_window = match options.context_window {
+            Some(context_window) => context_window,
+            None => self.context_window(&options.model).await?,
+        };
+        let budget = input_budget(context_window, options.max_summary_tokens)?;
+        let instructions = options
+            .instructions
+            .as_deref()
+            .unwrap_or(DEFAULT_INSTRUCTIONS);
+
+        if ApproximateTokenCounter.count(text) <= budget {
+            let (summary, usage) = self.summarize_once(text, instructions, options).await?;
+            return Ok(Summary {
+                summary,
+                chunk_summaries: Vec::new(),
+                usage,
+            });
+        }
+
+        let chunker = Chunker::builder()
+            .max_tokens(budget)
+            .overlap_tokens(0)
+            .strategy(ChunkingStrategy::Paragraph)
+            .build();
+
+        let chunks: Vec<String> = chunker.chunk(text).into_iter().map(|c| c.text).collect();
+        let (chunk_summaries, mut usage) =
+            self.summarize_all(&chunks, instructions, options).await?;
+
+        // Merge partial summaries until they fit into a single reduce request.
+        let mut parts = chunk_summaries.clone();
+        let mut rounds = 0;
+        loop {
+            let joined = parts.join("\n\n");
+            if parts.len() == 1 || ApproximateTokenCounter.count(&joined) <= budget {
+                let (summary, step) = self
+                    .summarize_once(&joined, REDUCE_INSTRUCTIONS, options)
+                    .await?;
+                return Ok(Summary {
+                    summary,
+                    chunk_summaries,
+                    usage: add_usage(usage, step),
+                });
+            }
+
+            if rounds == options.max_reduce_rounds {
+                return Err(SummarizeError::TooManyReduceRounds(rounds));
+            }
+            rounds += 1;
+
+            let groups: Vec<String> = chunker.chunk(&joined).into_iter().map(|c| c.text).collect();
+            let (merged, step) = self
+                .summarize_all(&groups, REDUCE_INSTRUCTIONS, options)
+                .await?;
+            usage = add_usage(usage, step);
+            parts = merged;
+        }
+    }
+
+    /// The context window of `model` according to the model catalogue.
+    #[cfg(feature = "models")]
+    async fn context_window(&self, model: &str) -> Result<usize, SummarizeError> {
+        let model = self.resolve_model(model);
+        match self.model(&model).await {
+            Ok(listing) => listing
+                .metadata
+                .context_length
+                .map(|context_length| context_length as usize)
+                .ok_or(SummarizeError::UnknownContextWindow(model)),
+            Err(ModelsError::NotFound(model)) => Err(SummarizeError::UnknownContextWindow(model)),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    #[cfg(not(feature = "models"))]
+    async fn context_window(&self, model: &str) -> Result<usize, SummarizeError> {
+        Err(SummarizeError::UnknownContextWindow(
+            self.resolve_model(model),
+        ))
+    }
+
+    async fn summarize_all(
+        &self,
+        texts: &[String],
+        instructions: &str,
+        options: &SummarizeOptions,
+    ) -> Result<(Vec<String>, Option<Usage>), ChatCompletionError> {
+        let results: Vec<(String, Option<Usage>)> = stream::iter(texts)
+            .map(|text| self.summarize_once(text, instructions, options))
+            .buffered(options.concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        Ok(results.into_iter().fold(
+            (Vec::new(), None),
+            |(mut summaries, total), (summary, usage)| {
+                summaries.push(summary);
+                (summaries, add_usage(total, usage))
+            },
+        ))
+    }
+
+    async fn summarize_once(
+        &self,
+        text: &str,
+        instructions: &str,
+        options: &SummarizeOptions,
+    ) -> Result<(String, Option<Usage>), ChatCompletionError> {
+        let request = ChatCompletionRequest::builder()
+            .model(options.model.clone())
+            .max_tokens(options.max_summary_tokens)
+            .temperature(0.3)
+            .messages(vec![
+                Message::System(SystemMessage::builder().content(instructions).build()),
+                Message::User(UserMessage::builder().content(text).build()),
+            ])
+            .build();
+
+        let mut response = self.chat_completition(request).await?;
+        let summary = response.content().unwrap_or_default().trim().to_string();
+
+        Ok((summary, response.usage.take()))
+    }
+}
+
+/// Tokens of input that fit in one request next to the prompt and the summary.
+fn input_budget(context_window: usize, max_summary_tokens: u32) -> Result<usize, SummarizeError> {
+    context_window
+        .checked_sub(max_summary_tokens as usize + PROMPT_OVERHEAD_TOKENS)
+        .filter(|&budget| budget > 0)
+        .ok_or(SummarizeError::ContextWindowTooSmall {
+            context_window,
+            max_summary_tokens,
+        })
+}
+
+fn add_usage(total: Option<Usage>, step: Option<Usage>) -> Option<Usage> {
+    match (total, step) {
+        (Some(total), Some(step)) => Some(total + step),
+        (total, step) => total.or(step),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    /// Answers every HTTP request with `body` and returns the server's URL.
+    fn serve(body: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for connection in listener.incoming() {
+                let mut reader = BufReader::new(connection.unwrap());
+                let mut content_length = 0;
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    line.clear();
+                }
+                reader.read_exact(&mut vec![0; content_length]).unwrap();
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        format!("http://{address}")
+    }
+
+    fn client(base_url: &str) -> DeepinfraClient {
+        DeepinfraClient::builder()
+            .token("token")
+            .base_url(base_url)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn rejects_context_windows_without_room_for_input() {
+        for context_window in [0, 1024, 1024 + PROMPT_OVERHEAD_TOKENS] {
+            assert!(matches!(
+                input_budget(context_window, 1024),
+                Err(SummarizeError::ContextWindowTooSmall { .. })
+            ));
+        }
+        assert_eq!(
+            input_budget(1024 + PROMPT_OVERHEAD_TOKENS + 1, 1024).unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn gives_up_when_partial_summaries_do_not_shrink() {
+        // Every summary is longer than the input budget, so merging never converges.
+        let summary = "A summary that is longer than the budget. ".repeat(8);
+        let response = serde_json::json!({
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": summary },
+                "finish_reason": "stop",
+            }],
+        });
+        let client = client(&serve(response.to_string()));
+        let options = SummarizeOptions::builder()
+            .context_window(1024 + PROMPT_OVERHEAD_TOKENS + 40)
+            .max_reduce_rounds(2)
+            .build();
+
+        let text = "A paragraph of the long document.\n\n".repeat(20);
+        let error = client.summarize_long(&text, &options).await.unwrap_err();
+        assert!(matches!(error, SummarizeError::TooManyReduceRounds(2)));
+    }
+
+    #[cfg(feature = "models")]
+    #[tokio::test]
+    async fn needs_the_context_window_of_models_missing_from_the_catalogue() {
+        let client = client(&serve(r#"{"data": []}"#.to_string()));
+        let error = client
+            .summarize_long("Some text.", &SummarizeOptions::default())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, SummarizeError::UnknownContextWindow(model) if model == "deepseek-ai/DeepSeek-V3")
+        );
+    }
+}
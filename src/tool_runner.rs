@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+use futures::future::{self, BoxFuture, FutureExt};
+use tracing::{debug, instrument};
+
+use crate::audit::{AuditEvent, AuditTrail};
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse, Message, ToolCall,
+    ToolMessage, Usage,
+};
+use crate::client::DeepinfraClient;
+use crate::error::BoxError;
+
+/// An async tool implementation: takes the JSON arguments generated by the model and
+/// returns the tool output sent back to it.
+pub type ToolHandler = dyn Fn(String) -> BoxFuture<'static, Result<String, BoxError>> + Send + Sync;
+
+/// A set of tool handlers, dispatched by name while a conversation runs.
+///
+/// The runner only executes tools; the matching [`ChatTool`] definitions must be set
+/// on the request so the model knows about them.
+///
+/// [`ChatTool`]: crate::chat_completition::ChatTool
+///
+/// # Example
+///
+/// ```
+/// use deepinfra_client_rs::tool_runner::ToolRunner;
+///
+/// let runner = ToolRunner::new()
+///     .tool("get_time", |_arguments| async { Ok("12:00".to_string()) })
+///     .max_iterations(5);
+/// ```
+#[derive(Clone)]
+pub struct ToolRunner {
+    handlers: HashMap<String, Arc<ToolHandler>>,
+    max_iterations: usize,
+    audit: AuditTrail,
+}
+
+impl Default for ToolRunner {
+    fn default() -> Self {
+        Self {
+            handlers: HashMap::new(),
+            max_iterations: 10,
+            audit: AuditTrail::new(),
+        }
+    }
+}
+
+impl fmt::Debug for ToolRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ToolRunner")
+            .field("tools", &self.handlers.keys().collect::<Vec<_>>())
+            .field("max_iterations", &self.max_iterations)
+            .field("audit", &self.audit)
+            .finish()
+    }
+}
+
+impl ToolRunner {
+    /// Creates a runner without tools that allows 10 model round trips.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handler` for the tool called `name`, replacing any previous one.
+    pub fn tool<F, Fut>(mut self, name: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, BoxError>> + Send + 'static,
+    {
+        let handler: Arc<ToolHandler> = Arc::new(move |arguments| handler(arguments).boxed());
+        self.handlers.insert(name.into(), handler);
+        self
+    }
+
+    /// Sets the maximum number of chat completion requests sent for one run.
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    /// Records every message, response, tool call and tool result of a run in
+    /// `audit`.
+    pub fn audit(mut self, audit: AuditTrail) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// Runs one tool call. Failures are reported to the model as the tool output, so
+    /// it can correct its arguments or answer without the tool.
+    async fn call(&self, iteration: usize, call: &ToolCall) -> Message {
+        let name = call.function().name();
+        self.audit.record(iteration, || AuditEvent::ToolCall {
+            id: call.id().to_string(),
+            name: name.to_string(),
+            arguments: call.function().arguments().to_string(),
+        });
+        let content = match self.handlers.get(name) {
+            Some(handler) => match handler(call.function().arguments().to_string()).await {
+                Ok(output) => output,
+                Err(error) => format!("Error: {error}"),
+            },
+            None => format!("Error: unknown tool {name:?}"),
+        };
+        self.audit.record(iteration, || AuditEvent::ToolResult {
+            id: call.id().to_string(),
+            name: name.to_string(),
+            content: content.clone(),
+        });
+        Message::Tool(
+            ToolMessage::builder()
+                .content(content)
+                .tool_call_id(call.id().to_string())
+                .build(),
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ToolRunnerError {
+    #[error("Chat completion error: {0}")]
+    ChatCompletionError(#[from] ChatCompletionError),
+    #[error("No final answer after {0} requests")]
+    MaxIterations(usize),
+    #[error("The response contained no choices")]
+    EmptyResponse,
+}
+
+/// The outcome of [`DeepinfraClient::run_tools`].
+#[derive(Clone, Debug)]
+pub struct ToolRunOutput {
+    /// The response carrying the final answer.
+    pub response: ChatCompletionResponse,
+    /// The whole conversation, including tool calls, tool outputs and the answer.
+    pub messages: Vec<Message>,
+    /// Number of chat completion requests sent.
+    pub iterations: usize,
+    /// Token usage summed over every request, if the API reported it.
+    pub usage: Option<Usage>,
+}
+
+impl DeepinfraClient {
+    /// Sends `request` and executes the tool calls the model makes with `runner`,
+    /// feeding the outputs back until the model answers without calling a tool.
+    ///
+    /// Tool calls of one response run concurrently. With an [`audit`](ToolRunner::audit)
+    /// trail, the initial messages and every response, tool call and tool result are
+    /// recorded as they happen. Fails with
+    /// [`ToolRunnerError::MaxIterations`] if the model still calls tools after the
+    /// runner's maximum number of requests.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # use deepinfra_client_rs::tool_runner::*;
+    /// # async fn run(client: DeepinfraClient, tools: Vec<ChatTool>) -> Result<(), ToolRunnerError> {
+    /// let runner = ToolRunner::new().tool("get_weather", |arguments| async move {
+    ///     Ok(format!("Sunny, 24°C ({arguments})"))
+    /// });
+    ///
+    /// let request = ChatCompletionRequest::builder()
+    ///     .messages(vec![Message::User(
+    ///         UserMessage::builder().content("What's the weather in Rome?").build(),
+    ///     )])
+    ///     .tools(tools)
+    ///     .build();
+    ///
+    /// let output = client.run_tools(request, &runner).await?;
+    /// println!("{}", output.response.content().unwrap_or_default());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request, runner))]
+    pub async fn run_tools(
+        &self,
+        mut request: ChatCompletionRequest,
+        runner: &ToolRunner,
+    ) -> Result<ToolRunOutput, ToolRunnerError> {
+        let mut usage: Option<Usage> = None;
+        for message in &request.messages {
+            runner.audit.record(1, || AuditEvent::Message {
+                message: message.clone(),
+            });
+        }
+
+        for iteration in 1..=runner.max_iterations {
+            let response = self.chat_completition(request.clone()).await?;
+            if let Some(response_usage) = response.usage.clone() {
+                usage = Some(match usage {
+                    Some(total) => total + response_usage,
+                    None => response_usage,
+                });
+            }
+
+            let message = response
+                .choices
+                .first()
+                .ok_or(ToolRunnerError::EmptyResponse)?
+                .message
+                .clone();
+            runner.audit.record(iteration, || AuditEvent::Response {
+                model: response.model.clone(),
+                request_id: response.metadata.request_id().map(str::to_string),
+                usage: response.usage.clone(),
+                message: message.clone(),
+            });
+            let calls = match &message {
+                Message::Assistant(message) => message.tool_calls().to_vec(),
+                _ => Vec::new(),
+            };
+            request.messages.push(message);
+
+            if calls.is_empty() {
+                return Ok(ToolRunOutput {
+                    response,
+                    messages: request.messages,
+                    iterations: iteration,
+                    usage,
+                });
+            }
+
+            debug!(iteration, calls = calls.len(), "Running tool calls");
+            let outputs =
+                future::join_all(calls.iter().map(|call| runner.call(iteration, call))).await;
+            request.messages.extend(outputs);
+        }
+
+        Err(ToolRunnerError::MaxIterations(runner.max_iterations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditEntry;
+    use crate::chat_completition::UserMessage;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::Mutex;
+
+    /// Answers the HTTP requests with `bodies` in turn and returns the server's URL.
+    fn serve(bodies: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for (connection, body) in listener.incoming().zip(bodies) {
+                let mut reader = BufReader::new(connection.unwrap());
+                let mut content_length = 0;
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                    line.clear();
+                }
+                reader.read_exact(&mut vec![0; content_length]).unwrap();
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn audits_every_step_of_a_run() {
+        let base_url = serve(vec![
+            r#"{"model": "m", "choices": [{"index": 0, "finish_reason": "tool_calls",
+                "message": {"role": "assistant", "content": "", "tool_calls": [{"id": "call_1",
+                "type": "function", "function": {"name": "get_time", "arguments": "{}"}}]}}]}"#,
+            r#"{"model": "m", "choices": [{"index": 0, "finish_reason": "stop",
+                "message": {"role": "assistant", "content": "It is noon."}}]}"#,
+        ]);
+        let client = DeepinfraClient::builder()
+            .token("token")
+            .base_url(&base_url)
+            .build()
+            .unwrap();
+
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&entries);
+        let runner = ToolRunner::new()
+            .tool("get_time", |_arguments| async { Ok("12:00".to_string()) })
+            .audit(AuditTrail::new().log(move |entry: &AuditEntry| {
+                seen.lock()
+                    .unwrap()
+                    .push(serde_json::to_value(entry).unwrap());
+            }));
+        let request = ChatCompletionRequest::builder()
+            .model("m".to_string())
+            .messages(vec![Message::User(
+                UserMessage::builder().content("What time is it?").build(),
+            )])
+            .build();
+
+        client.run_tools(request, &runner).await.unwrap();
+
+        let entries = entries.lock().unwrap();
+        let steps: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry["iteration"].as_u64().unwrap(),
+                    entry["event"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                (1, "message"),
+                (1, "response"),
+                (1, "tool_call"),
+                (1, "tool_result"),
+                (2, "response"),
+            ]
+        );
+        assert_eq!(entries[3]["content"], "12:00");
+        assert_eq!(entries[4]["message"]["content"], "It is noon.");
+    }
+}
@@ -0,0 +1,136 @@
+use crate::api_error::{decode, request_id, ApiError};
+use crate::audio_transcription::{
+    AudioForm, AudioTranscriptionError, AudioTranscriptionRequest, AUDIO_TRANSCRIPTION_PATH,
+};
+use crate::client::{with_timeout, DeepinfraClient};
+use crate::shutdown::ShutdownError;
+use crate::sse;
+use futures::future;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use http::Method;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tracing::instrument;
+
+/// An event of a streamed transcription, following the OpenAI transcription
+/// streaming protocol.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum TranscriptionEvent {
+    /// A piece of transcript text, to be appended to the previous ones.
+    #[serde(rename = "transcript.text.delta")]
+    Delta { delta: String },
+    /// The complete transcript, sent once at the end.
+    #[serde(rename = "transcript.text.done")]
+    Done { text: String },
+    /// An event type this crate does not know about.
+    #[serde(other)]
+    Unknown,
+}
+
+/// A stream of [`TranscriptionEvent`]s returned by
+/// [`DeepinfraClient::audio_transcription_stream`].
+pub struct TranscriptionStream {
+    inner: BoxStream<'static, Result<TranscriptionEvent, AudioTranscriptionError>>,
+}
+
+impl Stream for TranscriptionStream {
+    type Item = Result<TranscriptionEvent, AudioTranscriptionError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(cx)
+    }
+}
+
+impl fmt::Debug for TranscriptionStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TranscriptionStream")
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeepinfraClient {
+    /// Transcribes an audio file and streams the transcript as it is produced.
+    ///
+    /// Only models that support streamed transcription emit partial results; the API
+    /// rejects the request with an [`ApiError`] for the others.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::audio_transcription::*;
+    /// # use deepinfra_client_rs::transcription_stream::*;
+    /// # use futures::StreamExt;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), AudioTranscriptionError> {
+    /// let request = AudioTranscriptionRequest::builder()
+    ///     .source(FileSource::Filepath(std::path::Path::new("meeting.mp3").into()))
+    ///     .build();
+    ///
+    /// let mut stream = client.audio_transcription_stream(request).await?;
+    /// while let Some(event) = stream.next().await {
+    ///     if let TranscriptionEvent::Delta { delta } = event? {
+    ///         print!("{delta}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request))]
+    pub async fn audio_transcription_stream(
+        &self,
+        request: AudioTranscriptionRequest,
+    ) -> Result<TranscriptionStream, AudioTranscriptionError> {
+        let AudioForm {
+            form,
+            model,
+            timeout,
+        } = self.transcription_form(request).await?;
+        let form = form.text("stream", "true");
+
+        // The registration and request permit live as long as the stream, like for
+        // streamed chat completions.
+        let mut in_flight = self.lifecycle.enter()?;
+        let permit = tokio::select! {
+            permit = self.throttle_stream(&model) => permit,
+            _ = in_flight.aborted() => return Err(ShutdownError::Aborted.into()),
+        };
+        let response = tokio::select! {
+            response = self.send(with_timeout(self.request(Method::POST, AUDIO_TRANSCRIPTION_PATH), timeout).multipart(form)) => response?,
+            _ = in_flight.aborted() => return Err(ShutdownError::Aborted.into()),
+        };
+        let response = ApiError::check(response).await?;
+        let request_id = request_id(response.headers());
+
+        let events = sse::data_events(response)
+            .take_while(|event| future::ready(!matches!(event, Ok(data) if data == "[DONE]")))
+            .map(move |event| {
+                let data = event?;
+                let event = decode::<TranscriptionEvent>(data.as_bytes())
+                    .map_err(|error| error.with_request_id(request_id.clone()))?;
+                Ok(event)
+            })
+            .boxed();
+
+        let inner = stream::unfold(
+            (events, in_flight, permit, false),
+            |(mut events, mut in_flight, permit, aborted)| async move {
+                if aborted {
+                    return None;
+                }
+                tokio::select! {
+                    event = events.next() => event.map(|event| (event, (events, in_flight, permit, false))),
+                    _ = in_flight.aborted() => {
+                        let error = ShutdownError::Aborted.into();
+                        Some((Err(error), (events, in_flight, permit, true)))
+                    }
+                }
+            },
+        )
+        .boxed();
+
+        Ok(TranscriptionStream { inner })
+    }
+}
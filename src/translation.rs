@@ -0,0 +1,105 @@
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, Message, ResponseFormat, ResponseFormatType,
+    SystemMessage, UserMessage,
+};
+use crate::client::DeepinfraClient;
+use bon::Builder;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+/// Options for [`DeepinfraClient::translate`].
+#[derive(Clone, Debug, Builder)]
+pub struct TranslateOptions {
+    /// Model used for the translation.
+    #[builder(default = "deepseek-ai/DeepSeek-V3".to_string(), into)]
+    pub model: String,
+    /// Source language, when known. Detected by the model otherwise.
+    #[builder(into)]
+    pub source_language: Option<String>,
+    /// Sampling temperature; low values keep translations literal.
+    #[builder(default = 0.2)]
+    pub temperature: f64,
+    /// Maximum number of tokens generated.
+    #[builder(default = 4096)]
+    pub max_tokens: u32,
+}
+
+impl Default for TranslateOptions {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+/// A translated text together with the detected source language.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Translation {
+    /// ISO-639-1 code of the source language, as detected by the model.
+    pub source_language: String,
+    /// The translated text.
+    pub translation: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TranslationError {
+    #[error("Chat completion error: {0}")]
+    ChatCompletionError(#[from] ChatCompletionError),
+    #[error("The model returned no content")]
+    EmptyResponse,
+    #[error("The model returned invalid JSON {0}")]
+    InvalidJson(#[from] serde_json::Error),
+}
+
+impl DeepinfraClient {
+    /// Translates `text` into `target_language` (a language name or ISO-639-1 code).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::translation::*;
+    /// # async fn run(client: DeepinfraClient) -> Result<(), TranslationError> {
+    /// let result = client
+    ///     .translate("Buongiorno a tutti", "English", &TranslateOptions::default())
+    ///     .await?;
+    /// println!("{} -> {}", result.source_language, result.translation);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, text))]
+    pub async fn translate(
+        &self,
+        text: &str,
+        target_language: &str,
+        options: &TranslateOptions,
+    ) -> Result<Translation, TranslationError> {
+        let source = match &options.source_language {
+            Some(language) => format!("The source language is {language}."),
+            None => "Detect the source language.".to_string(),
+        };
+        let instructions = format!(
+            "You are a professional translator. Translate the text provided by the user \
+             into {target_language}. {source} Preserve meaning, tone and formatting, and \
+             leave code, URLs and proper nouns untouched. Reply with a JSON object of the \
+             form {{\"source_language\": \"<ISO-639-1 code>\", \"translation\": \"<text>\"}} \
+             and nothing else."
+        );
+
+        let request = ChatCompletionRequest::builder()
+            .model(options.model.clone())
+            .temperature(options.temperature)
+            .max_tokens(options.max_tokens)
+            .response_format(ResponseFormat {
+                response_type: ResponseFormatType::JsonObject,
+            })
+            .messages(vec![
+                Message::System(SystemMessage::builder().content(instructions).build()),
+                Message::User(UserMessage::builder().content(text).build()),
+            ])
+            .build();
+
+        let response = self.chat_completition(request).await?;
+        let content = response.content().ok_or(TranslationError::EmptyResponse)?;
+
+        Ok(serde_json::from_str(content)?)
+    }
+}
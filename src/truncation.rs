@@ -0,0 +1,284 @@
+use crate::chat_completition::{
+    ChatCompletionError, ChatCompletionRequest, ChatCompletionResponse,
+};
+use crate::chunking::{ApproximateTokenCounter, TokenCounter};
+use crate::client::DeepinfraClient;
+use crate::summarization::{SummarizeError, SummarizeOptions};
+use bon::Builder;
+use std::fmt;
+use std::sync::Arc;
+use tracing::instrument;
+
+const ELISION_MARKER: &str = "\n[...]\n";
+
+/// A custom truncation function: receives the content and the token limit.
+pub type TruncateFn = dyn Fn(&str, usize) -> String + Send + Sync;
+
+/// How an over-long message is shortened.
+#[derive(Clone)]
+pub enum TruncationStrategy {
+    /// Keep the beginning of the message.
+    Hard,
+    /// Keep the beginning and the end, dropping the middle.
+    MiddleOut,
+    /// Replace the message with a model-written summary.
+    Summarize(SummarizeOptions),
+    /// Custom truncation function.
+    Custom(Arc<TruncateFn>),
+}
+
+impl fmt::Debug for TruncationStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TruncationStrategy::Hard => f.write_str("Hard"),
+            TruncationStrategy::MiddleOut => f.write_str("MiddleOut"),
+            TruncationStrategy::Summarize(options) => {
+                f.debug_tuple("Summarize").field(options).finish()
+            }
+            TruncationStrategy::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+/// Limits applied to every individual message before sending.
+#[derive(Clone, Debug, Builder)]
+pub struct TruncationPolicy {
+    /// Maximum number of tokens a single message may contain.
+    pub max_message_tokens: usize,
+    /// Strategy used for messages above the limit.
+    #[builder(default = TruncationStrategy::MiddleOut)]
+    pub strategy: TruncationStrategy,
+}
+
+/// Which strategy was applied to a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppliedTruncation {
+    Hard,
+    MiddleOut,
+    Summarize,
+    Custom,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TruncationError {
+    #[error("Chat completion failed: {0}")]
+    ChatCompletionError(#[from] ChatCompletionError),
+    #[error("Summarization failed: {0}")]
+    SummarizeError(#[from] SummarizeError),
+}
+
+/// Describes a message that was shortened by a [`TruncationPolicy`].
+#[derive(Clone, Debug)]
+pub struct TruncationReport {
+    /// Index of the message in the request.
+    pub message_index: usize,
+    pub strategy: AppliedTruncation,
+    /// Tokens before truncation, according to the counter used.
+    pub original_tokens: usize,
+    /// Tokens after truncation, according to the counter used.
+    pub truncated_tokens: usize,
+}
+
+impl DeepinfraClient {
+    /// Shortens every message of `request` that exceeds the policy limit, counting
+    /// tokens with the [`ApproximateTokenCounter`].
+    ///
+    /// The summarize strategy falls back to a hard cut if the summary itself is still
+    /// too long.
+    pub async fn apply_truncation(
+        &self,
+        request: &mut ChatCompletionRequest,
+        policy: &TruncationPolicy,
+    ) -> Result<Vec<TruncationReport>, TruncationError> {
+        self.apply_truncation_with(request, policy, &ApproximateTokenCounter)
+            .await
+    }
+
+    /// Like [`apply_truncation`](Self::apply_truncation), counting tokens with the
+    /// given counter, e.g. the exact tokenizer of the model.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use deepinfra_client_rs::prelude::*;
+    /// # use deepinfra_client_rs::chat_completition::*;
+    /// # use deepinfra_client_rs::truncation::*;
+    /// # async fn run(client: DeepinfraClient, mut request: ChatCompletionRequest) -> Result<(), TruncationError> {
+    /// let policy = TruncationPolicy::builder().max_message_tokens(2_000).build();
+    /// let words = |text: &str| text.split_whitespace().count();
+    /// let reports = client.apply_truncation_with(&mut request, &policy, &words).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(skip(self, request, counter))]
+    pub async fn apply_truncation_with(
+        &self,
+        request: &mut ChatCompletionRequest,
+        policy: &TruncationPolicy,
+        counter: &(impl TokenCounter + Sync),
+    ) -> Result<Vec<TruncationReport>, TruncationError> {
+        let limit = policy.max_message_tokens;
+        let mut reports = Vec::new();
+
+        for (message_index, message) in request.messages.iter_mut().enumerate() {
+            // Multi-part content with images is left untouched.
+            let Some(content) = message.content_mut() else {
+                continue;
+            };
+            let original_tokens = counter.count(content);
+            if original_tokens <= limit {
+                continue;
+            }
+
+            let (truncated, strategy) = match &policy.strategy {
+                TruncationStrategy::Hard => (
+                    truncate_head(content, limit, counter),
+                    AppliedTruncation::Hard,
+                ),
+                TruncationStrategy::MiddleOut => (
+                    truncate_middle(content, limit, counter),
+                    AppliedTruncation::MiddleOut,
+                ),
+                TruncationStrategy::Summarize(options) => {
+                    let options = SummarizeOptions {
+                        max_summary_tokens: limit as u32,
+                        ..options.clone()
+                    };
+                    let summary = self.summarize_long(content, &options).await?.summary;
+                    (
+                        truncate_head(&summary, limit, counter),
+                        AppliedTruncation::Summarize,
+                    )
+                }
+                TruncationStrategy::Custom(truncate) => {
+                    (truncate(content, limit), AppliedTruncation::Custom)
+                }
+            };
+
+            reports.push(TruncationReport {
+                message_index,
+                strategy,
+                original_tokens,
+                truncated_tokens: counter.count(&truncated),
+            });
+            *content = truncated;
+        }
+
+        Ok(reports)
+    }
+
+    /// Applies `policy` to `request` and sends it, returning the response together
+    /// with the truncations that were applied.
+    #[instrument(skip(self, request))]
+    pub async fn chat_completion_truncated(
+        &self,
+        mut request: ChatCompletionRequest,
+        policy: &TruncationPolicy,
+    ) -> Result<(ChatCompletionResponse, Vec<TruncationReport>), TruncationError> {
+        let reports = self.apply_truncation(&mut request, policy).await?;
+        let response = self.chat_completition(request).await?;
+        Ok((response, reports))
+    }
+}
+
+/// Longest prefix of `text` that fits in `limit` tokens.
+fn truncate_head(text: &str, limit: usize, counter: &impl TokenCounter) -> String {
+    text[..head_end(text, limit, counter)].to_string()
+}
+
+/// Head and tail of `text` around an elision marker, fitting in `limit` tokens.
+fn truncate_middle(text: &str, limit: usize, counter: &impl TokenCounter) -> String {
+    let budget = limit.saturating_sub(counter.count(ELISION_MARKER));
+    let head = head_end(text, budget.div_ceil(2), counter);
+    let tail = tail_start(text, budget / 2, counter).max(head);
+    format!("{}{ELISION_MARKER}{}", &text[..head], &text[tail..])
+}
+
+/// Byte offset where the longest prefix of `text` fitting in `limit` tokens ends.
+///
+/// Binary searches the character boundaries, assuming longer text never has fewer
+/// tokens.
+fn head_end(text: &str, limit: usize, counter: &impl TokenCounter) -> usize {
+    let boundaries = char_boundaries(text);
+    let fitting = boundaries.partition_point(|&end| counter.count(&text[..end]) <= limit);
+    fitting.checked_sub(1).map_or(0, |last| boundaries[last])
+}
+
+/// Byte offset where the longest suffix of `text` fitting in `limit` tokens starts.
+fn tail_start(text: &str, limit: usize, counter: &impl TokenCounter) -> usize {
+    let boundaries = char_boundaries(text);
+    let first = boundaries.partition_point(|&start| counter.count(&text[start..]) > limit);
+    boundaries.get(first).copied().unwrap_or(text.len())
+}
+
+/// Byte offsets of every character boundary of `text`, including its end.
+fn char_boundaries(text: &str) -> Vec<usize> {
+    text.char_indices()
+        .map(|(index, _)| index)
+        .chain([text.len()])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat_completition::{Message, UserMessage};
+
+    fn words(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    #[test]
+    fn keeps_the_longest_fitting_head() {
+        assert_eq!(truncate_head("one two three four", 2, &words), "one two ");
+        assert_eq!(
+            truncate_head("abcdefghij", 2, &ApproximateTokenCounter),
+            "abcdefgh"
+        );
+        assert_eq!(truncate_head("anything", 0, &words), "");
+    }
+
+    #[test]
+    fn keeps_head_and_tail_around_the_marker() {
+        let truncated = truncate_middle("one two three four five six seven", 5, &words);
+        assert_eq!(truncated, format!("one two {ELISION_MARKER} six seven"));
+    }
+
+    #[test]
+    fn cuts_on_character_boundaries() {
+        let text = "àèìòù".repeat(20);
+        let head = truncate_head(&text, 3, &ApproximateTokenCounter);
+        assert_eq!(head.chars().count(), 12);
+        let middle = truncate_middle(&text, 6, &ApproximateTokenCounter);
+        assert!(ApproximateTokenCounter.count(&middle) <= 6);
+    }
+
+    #[tokio::test]
+    async fn counts_with_the_given_counter() {
+        let client = DeepinfraClient::builder().token("token").build().unwrap();
+        let user = UserMessage::builder()
+            .content("one two three four five six")
+            .build();
+        let mut request = ChatCompletionRequest::builder()
+            .messages(vec![Message::User(user)])
+            .build();
+        let policy = TruncationPolicy::builder()
+            .max_message_tokens(3)
+            .strategy(TruncationStrategy::Hard)
+            .build();
+
+        let reports = client
+            .apply_truncation_with(&mut request, &policy, &words)
+            .await
+            .unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].original_tokens, 6);
+        assert_eq!(reports[0].truncated_tokens, 3);
+        assert_eq!(
+            request.messages[0]
+                .content_mut()
+                .map(|content| content.as_str()),
+            Some("one two three ")
+        );
+    }
+}